@@ -22,7 +22,7 @@ use risc0_zkp::core::{
     hash::sha::{Impl, Sha256, BLOCK_BYTES, SHA256_INIT},
 };
 use risc0_zkvm_platform::{
-    memory::{GUEST_MAX_MEM, MEM_SIZE, PAGE_TABLE},
+    memory::{GUEST_MAX_MEM, MEM_SIZE, PAGE_TABLE, PRELOAD_INPUT, PRELOAD_INPUT_LEN},
     syscall::DIGEST_BYTES,
 };
 use serde::{Deserialize, Serialize};
@@ -207,6 +207,43 @@ impl MemoryImage {
         Ok(img)
     }
 
+    /// Write `data` to the [PRELOAD_INPUT] region, and its length in bytes to
+    /// [PRELOAD_INPUT_LEN], and update the image merkle tree.
+    ///
+    /// The guest reads the data directly from memory, so only the pages it
+    /// touches are paged in. Because the data is part of the image, it is
+    /// committed to by the image ID.
+    pub fn preload(&mut self, data: &[u8]) -> Result<()> {
+        ensure!(
+            data.len() <= PRELOAD_INPUT.len_bytes(),
+            "preloaded data of {} bytes exceeds the preload region of {} bytes",
+            data.len(),
+            PRELOAD_INPUT.len_bytes()
+        );
+        ensure!(
+            self.pages
+                .range(self.info.get_page_index(PRELOAD_INPUT.start() as u32)..)
+                .next()
+                .map_or(true, |(&idx, _)| self.info.get_page_addr(idx) as usize
+                    >= PRELOAD_INPUT.end()),
+            "preload region is not empty"
+        );
+
+        self.store_region_in_page(PRELOAD_INPUT_LEN as u32, &(data.len() as u32).to_le_bytes());
+        let mut addr = PRELOAD_INPUT.start() as u32;
+        let mut rest = data;
+        while !rest.is_empty() {
+            let page_end = self.info.get_page_addr(self.info.get_page_index(addr) + 1);
+            let (chunk, tail) = rest.split_at(rest.len().min((page_end - addr) as usize));
+            self.store_region_in_page(addr, chunk);
+            addr += chunk.len() as u32;
+            rest = tail;
+        }
+
+        self.hash_pages();
+        Ok(())
+    }
+
     /// Load a page specified by page_idx. If no page is found, a zero page is
     /// returned.
     pub fn load_page(&self, page_idx: u32) -> Vec<u8> {
@@ -356,9 +393,14 @@ fn hash_page_bytes(page: &[u8]) -> Digest {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use risc0_zkvm_methods::MULTI_TEST_ELF;
     use risc0_zkvm_platform::{
-        memory::{GUEST_MAX_MEM, PAGE_TABLE, STACK_TOP, SYSTEM, TEXT_START},
+        memory::{
+            GUEST_MAX_MEM, PAGE_TABLE, PRELOAD_INPUT, PRELOAD_INPUT_LEN, STACK_TOP, SYSTEM,
+            TEXT_START,
+        },
         syscall::DIGEST_BYTES,
     };
     use test_log::test;
@@ -388,6 +430,44 @@ mod tests {
         image.check(image.info.root_page_addr).unwrap();
     }
 
+    #[test]
+    fn preload() {
+        const PAGE_SIZE: u32 = 1024;
+        let program = Program {
+            entry: TEXT_START,
+            image: BTreeMap::from([(TEXT_START, 0x00000073)]),
+        };
+        let mut image = MemoryImage::new(&program, PAGE_SIZE).unwrap();
+        let image_id = image.compute_id();
+
+        let data: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+        image.preload(&data).unwrap();
+        assert_ne!(image.compute_id(), image_id);
+        let start = PRELOAD_INPUT.start() as u32;
+        image.check(PRELOAD_INPUT_LEN as u32).unwrap();
+        image.check(start).unwrap();
+        image.check(start + data.len() as u32).unwrap();
+
+        let mut len = [0u8; 4];
+        image
+            .load_region_in_page(PRELOAD_INPUT_LEN as u32, &mut len)
+            .unwrap();
+        assert_eq!(u32::from_le_bytes(len), data.len() as u32);
+        let mut first = [0u8; 16];
+        image.load_region_in_page(start, &mut first).unwrap();
+        assert_eq!(first, data[..16]);
+        let mut tail = [0u8; 8];
+        image
+            .load_region_in_page(start + data.len() as u32 - 8, &mut tail)
+            .unwrap();
+        assert_eq!(tail, data[data.len() - 8..]);
+
+        let mut image = MemoryImage::new(&program, PAGE_SIZE).unwrap();
+        assert!(image
+            .preload(&vec![0; PRELOAD_INPUT.len_bytes() + 1])
+            .is_err());
+    }
+
     #[test]
     fn page_table_info() {
         const PAGE_SIZE_1K: u32 = 1024;
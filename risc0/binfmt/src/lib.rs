@@ -44,3 +44,18 @@ pub fn compute_image_id(elf: &[u8]) -> anyhow::Result<risc0_zkp::core::digest::D
     let image = MemoryImage::new(&program, PAGE_SIZE as u32)?;
     Ok(image.compute_id())
 }
+
+/// Compute and return the ImageID of the specified ELF binary, with `data`
+/// preloaded into guest memory as by [MemoryImage::preload].
+#[cfg(not(target_os = "zkvm"))]
+pub fn compute_image_id_with_preload(
+    elf: &[u8],
+    data: &[u8],
+) -> anyhow::Result<risc0_zkp::core::digest::Digest> {
+    use risc0_zkvm_platform::{memory::GUEST_MAX_MEM, PAGE_SIZE};
+
+    let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)?;
+    let mut image = MemoryImage::new(&program, PAGE_SIZE as u32)?;
+    image.preload(data)?;
+    Ok(image.compute_id())
+}
@@ -0,0 +1,288 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use quote::ToTokens;
+use syn::{Attribute, FnArg, GenericArgument, Item, PathArguments, ReturnType, Type, UseTree};
+
+/// The path of the typed entry attribute.
+const ENTRY_ATTR: [&str; 3] = ["risc0_zkvm", "guest", "typed_entry"];
+
+/// Types that resolve without a path wherever methods.rs is included.
+const PRELUDE_TYPES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64", "String", "Vec", "Option", "Result", "Box",
+];
+
+/// The input and output types of a guest entrypoint marked with
+/// `#[risc0_zkvm::guest::typed_entry]`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct EntrySignature {
+    input: String,
+    output: String,
+}
+
+impl EntrySignature {
+    /// Finds the typed entrypoint in the source of a guest binary, if it has
+    /// one.
+    ///
+    /// Returns an error if the entrypoint uses a type that would not resolve
+    /// in the methods crate.
+    pub(crate) fn from_source(src: &str) -> Result<Option<Self>> {
+        let Ok(file) = syn::parse_file(src) else {
+            return Ok(None);
+        };
+        let imports = imports(&file.items);
+        file.items
+            .iter()
+            .find_map(|item| match item {
+                Item::Fn(func) if func.attrs.iter().any(|attr| is_entry_attr(attr, &imports)) => {
+                    Some(Self::new(&func.sig))
+                }
+                _ => None,
+            })
+            .transpose()
+    }
+
+    fn new(sig: &syn::Signature) -> Result<Self> {
+        let mut inputs = Vec::new();
+        for arg in &sig.inputs {
+            if let FnArg::Typed(pat) = arg {
+                check_type(&pat.ty)?;
+                inputs.push(pat.ty.to_token_stream().to_string());
+            }
+        }
+        // The guest reads each argument in turn, which is the same encoding as
+        // the host writing them as a tuple.
+        let input = match inputs.len() {
+            1 => inputs.pop().unwrap(),
+            _ => format!("({})", inputs.join(", ")),
+        };
+        let output = match &sig.output {
+            ReturnType::Default => "()".to_string(),
+            ReturnType::Type(_, ty) => {
+                check_type(ty)?;
+                ty.to_token_stream().to_string()
+            }
+        };
+        Ok(Self { input, output })
+    }
+
+    /// Generates the typed `GuestMethod` constant for the guest.
+    ///
+    /// The types are emitted as written in the guest, which [check_type]
+    /// ensures are primitives, prelude types or fully qualified paths.
+    pub(crate) fn codegen_const(&self, upper: &str) -> String {
+        let Self { input, output } = self;
+        format!(
+            r##"
+pub const {upper}: risc0_zkvm::GuestMethod<{input}, {output}> =
+    risc0_zkvm::GuestMethod::new({upper}_ELF, {upper}_ID);
+"##
+        )
+    }
+}
+
+/// Ensures that a type copied out of the guest source resolves in the methods
+/// crate, which does not share the guest's imports.
+fn check_type(ty: &Type) -> Result<()> {
+    match ty {
+        Type::Paren(inner) => check_type(&inner.elem),
+        Type::Group(inner) => check_type(&inner.elem),
+        Type::Array(array) => check_type(&array.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().try_for_each(check_type),
+        Type::Path(path) if path.qself.is_none() => {
+            let segments = &path.path.segments;
+            let first = segments[0].ident.to_string();
+            let qualified = path.path.leading_colon.is_some()
+                || first == "std"
+                || first == "core"
+                || (segments.len() == 1 && PRELUDE_TYPES.contains(&first.as_str()));
+            if !qualified {
+                bail!(
+                    "The type `{}` of the typed entrypoint must be fully qualified, e.g. `::common::Point`, so that it resolves in the methods crate",
+                    ty.to_token_stream()
+                );
+            }
+            for segment in segments {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let GenericArgument::Type(ty) = arg {
+                            check_type(ty)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        _ => bail!(
+            "The type `{}` is not supported by the typed entrypoint",
+            ty.to_token_stream()
+        ),
+    }
+}
+
+/// Collects the names brought into scope by the top-level `use` items of a
+/// guest, mapped to the full paths they refer to.
+fn imports(items: &[Item]) -> HashMap<String, Vec<String>> {
+    fn walk(tree: &UseTree, prefix: &mut Vec<String>, out: &mut HashMap<String, Vec<String>>) {
+        match tree {
+            UseTree::Path(path) => {
+                prefix.push(path.ident.to_string());
+                walk(&path.tree, prefix, out);
+                prefix.pop();
+            }
+            UseTree::Name(name) => {
+                let mut full = prefix.clone();
+                full.push(name.ident.to_string());
+                out.insert(name.ident.to_string(), full);
+            }
+            UseTree::Rename(rename) => {
+                let mut full = prefix.clone();
+                full.push(rename.ident.to_string());
+                out.insert(rename.rename.to_string(), full);
+            }
+            UseTree::Group(group) => group.items.iter().for_each(|tree| walk(tree, prefix, out)),
+            UseTree::Glob(_) => {}
+        }
+    }
+
+    let mut out = HashMap::new();
+    for item in items {
+        if let Item::Use(item) = item {
+            walk(&item.tree, &mut Vec::new(), &mut out);
+        }
+    }
+    out
+}
+
+/// Matches `#[risc0_zkvm::guest::typed_entry]`, or a shorter path to it
+/// brought into scope by a `use` item.
+fn is_entry_attr(attr: &Attribute, imports: &HashMap<String, Vec<String>>) -> bool {
+    let mut segments: Vec<String> = attr
+        .path()
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    if attr.path().leading_colon.is_none() {
+        if let Some(full) = imports.get(&segments[0]) {
+            segments.splice(..1, full.iter().cloned());
+        }
+    }
+    segments == ENTRY_ATTR
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntrySignature;
+
+    fn signature(input: &str, output: &str) -> Option<EntrySignature> {
+        Some(EntrySignature {
+            input: input.to_string(),
+            output: output.to_string(),
+        })
+    }
+
+    #[test]
+    fn single_argument() {
+        let src = r#"
+            #![no_main]
+            use risc0_zkvm::guest::env;
+
+            #[risc0_zkvm::guest::typed_entry]
+            fn main(input: Vec<u8>) -> u32 {
+                input.len() as u32
+            }
+        "#;
+        assert_eq!(
+            EntrySignature::from_source(src).unwrap(),
+            signature("Vec < u8 >", "u32")
+        );
+    }
+
+    #[test]
+    fn multiple_arguments() {
+        let src = r#"
+            use risc0_zkvm::guest::typed_entry;
+
+            #[typed_entry]
+            fn run(a: u64, b: ::common::Point) {}
+        "#;
+        assert_eq!(
+            EntrySignature::from_source(src).unwrap(),
+            signature("(u64, :: common :: Point)", "()")
+        );
+    }
+
+    #[test]
+    fn no_arguments() {
+        let src = r#"
+            use risc0_zkvm::{guest, serde};
+
+            #[guest::typed_entry]
+            fn main() -> [u8; 4] { [0; 4] }
+        "#;
+        assert_eq!(
+            EntrySignature::from_source(src).unwrap(),
+            signature("()", "[u8 ; 4]")
+        );
+    }
+
+    #[test]
+    fn untyped_entry() {
+        let src = r#"
+            risc0_zkvm::guest::entry!(main);
+
+            #[other::typed_entry]
+            fn main() {}
+
+            #[entry]
+            fn other() {}
+
+            #[typed_entry]
+            fn another() {}
+        "#;
+        assert_eq!(EntrySignature::from_source(src).unwrap(), None);
+    }
+
+    #[test]
+    fn unqualified_types() {
+        for ty in [
+            "Point",
+            "common::Point",
+            "Vec<Point>",
+            "crate::Point",
+            "&[u8]",
+        ] {
+            let src = format!("#[risc0_zkvm::guest::typed_entry] fn main(x: {ty}) {{}}");
+            assert!(EntrySignature::from_source(&src).is_err(), "{ty}");
+        }
+        let src = "#[risc0_zkvm::guest::typed_entry] fn main() -> Option<common::Point> { None }";
+        assert!(EntrySignature::from_source(src).is_err());
+    }
+
+    #[test]
+    fn codegen() {
+        let src = "#[risc0_zkvm::guest::typed_entry] fn main(x: u32) -> u64 { x as u64 }";
+        let code = EntrySignature::from_source(src)
+            .unwrap()
+            .unwrap()
+            .codegen_const("MULTIPLY");
+        assert!(code.contains("pub const MULTIPLY: risc0_zkvm::GuestMethod<u32, u64>"));
+        assert!(code.contains("risc0_zkvm::GuestMethod::new(MULTIPLY_ELF, MULTIPLY_ID)"));
+    }
+}
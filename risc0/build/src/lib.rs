@@ -18,6 +18,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod docker;
+mod entry;
 
 use std::{
     borrow::Cow,
@@ -39,6 +40,8 @@ use serde::Deserialize;
 
 pub use docker::{docker_build, BuildStatus, TARGET_DIR};
 
+use crate::entry::EntrySignature;
+
 const RUSTUP_TOOLCHAIN_NAME: &str = "risc0";
 
 /// Get the path used by cargo-risczero that stores downloaded toolchains
@@ -199,6 +202,21 @@ fn is_debug() -> bool {
     get_env_var("RISC0_BUILD_DEBUG") == "1"
 }
 
+/// Returns the typed entrypoint of the given guest binary, if it has one.
+fn guest_entry(pkg: &Package, name: &str) -> Result<Option<EntrySignature>> {
+    let Some(target) = pkg
+        .targets
+        .iter()
+        .find(|target| target.name == name && target.kind.iter().any(|kind| kind == "bin"))
+    else {
+        return Ok(None);
+    };
+    let Ok(src) = fs::read_to_string(&target.src_path) else {
+        return Ok(None);
+    };
+    EntrySignature::from_source(&src).with_context(|| format!("Invalid entrypoint in {name}"))
+}
+
 /// Returns all methods associated with the given guest crate.
 fn guest_methods(pkg: &Package, target_dir: impl AsRef<Path>) -> Vec<GuestListEntry> {
     let profile = if is_debug() { "debug" } else { "release" };
@@ -605,6 +623,12 @@ pub fn embed_methods_with_options(
             methods_file
                 .write_all(method.codegen_consts().as_bytes())
                 .unwrap();
+            if let Some(entry) = guest_entry(&guest_pkg, &method.name).unwrap() {
+                let upper = method.name.to_uppercase().replace('-', "_");
+                methods_file
+                    .write_all(entry.codegen_const(&upper).as_bytes())
+                    .unwrap();
+            }
 
             #[cfg(feature = "guest-list")]
             guest_list_codegen.push(method.codegen_list_entry());
@@ -651,6 +675,14 @@ pub fn embed_methods_with_options(
 /// to uppercase.  For instance, if you have a method named
 /// "my_method", the image ID and elf contents will be defined as
 /// "MY_METHOD_ID" and "MY_METHOD_ELF" respectively.
+///
+/// If the guest's entrypoint is marked with `#[risc0_zkvm::guest::typed_entry]`, a
+/// typed `risc0_zkvm::GuestMethod` is also generated as "MY_METHOD", which
+/// requires `risc0-zkvm` to be a dependency of the methods crate. The input
+/// and output types are copied from the guest's signature, so they must be
+/// primitives, prelude types or fully qualified paths (e.g. `::common::Point`
+/// from a crate shared between the host and guest); other types are a build
+/// error.
 pub fn embed_methods() -> Vec<GuestListEntry> {
     embed_methods_with_options(HashMap::new())
 }
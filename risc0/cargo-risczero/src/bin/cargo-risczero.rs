@@ -32,6 +32,7 @@ fn main() -> Result<()> {
         RisczeroCmd::New(cmd) => cmd.run(),
         RisczeroCmd::Deploy(cmd) => cmd.run(),
         RisczeroCmd::Verify(cmd) => cmd.run(),
+        RisczeroCmd::SolidityVerifier(cmd) => cmd.run(),
         #[cfg(feature = "experimental")]
         RisczeroCmd::BuildCrate(build) => build.run(BuildSubcommand::Build),
         #[cfg(feature = "experimental")]
@@ -19,4 +19,5 @@ pub mod build_toolchain;
 pub mod deploy;
 pub mod install;
 pub mod new;
+pub mod solidity_verifier;
 pub mod verify;
@@ -0,0 +1,100 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use hex::FromHex;
+use risc0_groth16::{solidity, VerifyingKeyJson};
+use risc0_zkvm::{sha::Digest, Groth16ReceiptVerifierParameters};
+
+/// `cargo risczero solidity-verifier`
+#[derive(Parser, Debug, Clone, PartialEq, Eq)]
+pub struct SolidityVerifierCommand {
+    /// Path to a Groth16 verifier contract generated by snarkJS, whose
+    /// constants are replaced with the verifying key.
+    ///
+    /// For instance, `contracts/src/groth16/Groth16Verifier.sol` in
+    /// risc0-ethereum.
+    #[arg(long)]
+    template: PathBuf,
+
+    /// Path to a snarkJS `verification_key.json` to generate the verifier for.
+    ///
+    /// Defaults to the verifying key of the RISC Zero Groth16 prover.
+    #[arg(long)]
+    verifying_key: Option<PathBuf>,
+
+    /// The hex-encoded control root accepted by the receipt verifier.
+    ///
+    /// Defaults to the control root of this version of the zkVM.
+    #[arg(long)]
+    control_root: Option<String>,
+
+    /// The hex-encoded BN254 control ID accepted by the receipt verifier.
+    ///
+    /// Defaults to the BN254 control ID of this version of the zkVM.
+    #[arg(long)]
+    bn254_control_id: Option<String>,
+
+    /// Only emit the Groth16 verifier, without the RISC Zero receipt verifier.
+    #[arg(long)]
+    groth16_only: bool,
+
+    /// Directory to write `Groth16Verifier.sol` and
+    /// `RiscZeroGroth16Verifier.sol` to.
+    #[arg(long, short, default_value = ".")]
+    out_dir: PathBuf,
+}
+
+impl SolidityVerifierCommand {
+    pub fn run(&self) -> Result<()> {
+        let params = Groth16ReceiptVerifierParameters::default();
+        let verifying_key = match &self.verifying_key {
+            Some(path) => {
+                let json = std::fs::read_to_string(path)
+                    .with_context(|| format!("failed to read {}", path.display()))?;
+                serde_json::from_str::<VerifyingKeyJson>(&json)?.verifying_key()?
+            }
+            None => params.verifying_key,
+        };
+        let template = std::fs::read_to_string(&self.template)
+            .with_context(|| format!("failed to read {}", self.template.display()))?;
+        write(
+            &self.out_dir.join("Groth16Verifier.sol"),
+            &solidity::groth16_verifier(&template, &verifying_key)?,
+        )?;
+
+        if !self.groth16_only {
+            let control_root = match &self.control_root {
+                Some(hex) => Digest::from_hex(hex)?,
+                None => params.control_root,
+            };
+            let bn254_control_id = match &self.bn254_control_id {
+                Some(hex) => Digest::from_hex(hex)?,
+                None => params.bn254_control_id,
+            };
+            write(
+                &self.out_dir.join("RiscZeroGroth16Verifier.sol"),
+                &solidity::receipt_verifier(control_root, bn254_control_id)?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn write(path: &Path, source: &str) -> Result<()> {
+    std::fs::write(path, source).with_context(|| format!("failed to write {}", path.display()))
+}
@@ -104,6 +104,9 @@ impl VerifyCommand {
 }
 
 fn parse_receipt(receipt_raw: Vec<u8>) -> Result<Receipt> {
-    let receipt: Receipt = bincode::deserialize(&receipt_raw)?;
-    Ok(receipt)
+    // Receipts written before the canonical encoding was introduced are plain bincode.
+    if !receipt_raw.starts_with(&Receipt::MAGIC) {
+        return Ok(bincode::deserialize(&receipt_raw)?);
+    }
+    Receipt::decode(&receipt_raw)
 }
@@ -30,7 +30,8 @@ use clap::{Parser, Subcommand};
 use self::commands::build::BuildCommand;
 use self::commands::{
     build_guest::BuildGuest, build_toolchain::BuildToolchain, deploy::DeployCommand,
-    install::Install, new::NewCommand, verify::VerifyCommand,
+    install::Install, new::NewCommand, solidity_verifier::SolidityVerifierCommand,
+    verify::VerifyCommand,
 };
 
 #[derive(Parser)]
@@ -65,6 +66,8 @@ pub enum RisczeroCmd {
     Deploy(DeployCommand),
     /// Verifies if a receipt is valid.
     Verify(VerifyCommand),
+    /// Generates a Solidity verifier contract for Groth16 receipts.
+    SolidityVerifier(SolidityVerifierCommand),
     /// Build a crate for RISC Zero.
     #[cfg(feature = "experimental")]
     BuildCrate(BuildCommand),
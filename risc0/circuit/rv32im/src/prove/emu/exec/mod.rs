@@ -15,7 +15,7 @@
 #[cfg(test)]
 mod tests;
 
-use std::{array, cell::RefCell, collections::BTreeSet, mem, rc::Rc};
+use std::{array, cell::RefCell, collections::BTreeSet, mem, ops::Range, rc::Rc};
 
 use anyhow::{bail, ensure, Result};
 use crypto_bigint::{CheckedMul as _, Encoding as _, NonZero, U256, U512};
@@ -30,10 +30,10 @@ use risc0_zkp::{
 };
 use risc0_zkvm_platform::{
     align_up,
-    memory::{is_guest_memory, GUEST_MAX_MEM},
+    memory::{is_guest_memory, GUEST_MAX_MEM, GUEST_MIN_MEM, STACK_TOP, TEXT_START},
     syscall::{
         bigint, ecall, halt,
-        reg_abi::{REG_A0, REG_A1, REG_A2, REG_A3, REG_A4, REG_MAX, REG_T0},
+        reg_abi::{REG_A0, REG_A1, REG_A2, REG_A3, REG_A4, REG_MAX, REG_SP, REG_T0},
         IO_CHUNK_WORDS,
     },
     PAGE_SIZE, WORD_SIZE,
@@ -66,6 +66,15 @@ pub trait Syscall {
         ctx: &mut dyn SyscallContext,
         into_guest: &mut [u32],
     ) -> Result<(u32, u32)>;
+
+    /// Returns the input digest, when none was given to [Executor::new].
+    ///
+    /// This is called once, when the digest is first needed by a segment or
+    /// read by the guest, so it can be computed from input the guest has
+    /// already consumed.
+    fn input_digest(&self) -> Result<Digest> {
+        Ok(Digest::ZERO)
+    }
 }
 
 /// Access to memory and machine state for syscalls.
@@ -101,6 +110,30 @@ pub struct ExecutorResult {
     pub pre_state: SystemState,
     pub post_state: SystemState,
     pub output_digest: Option<Digest>,
+    pub memory: MemoryStats,
+}
+
+/// Guest memory usage observed over the course of an execution.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryStats {
+    /// The highest guest address written within the heap region, or zero if
+    /// no such write occurred. Because the guest allocator only ever bumps its
+    /// position upward, this tracks the top of the heap.
+    pub heap_high_water: u32,
+
+    /// The lowest value the stack pointer reached below [STACK_TOP].
+    pub stack_low_water: u32,
+
+    /// The number of distinct guest memory pages touched across all segments.
+    pub touched_pages: usize,
+}
+
+#[derive(Default)]
+struct MemoryTracker {
+    heap: Range<u32>,
+    heap_high_water: u32,
+    stack_low_water: u32,
+    touched_pages: BTreeSet<u32>,
 }
 
 #[derive(Default)]
@@ -132,11 +165,13 @@ pub struct Executor<'a, 'b, S: Syscall> {
     exit_code: Option<ExitCode>,
     syscalls: Vec<SyscallRecord>,
     syscall_handler: &'a S,
-    input_digest: Digest,
+    input_digest: Option<Digest>,
     output_digest: Option<Digest>,
     pending: PendingState,
     trace: Vec<Rc<RefCell<dyn TraceCallback + 'b>>>,
     cycles: SessionCycles,
+    memory: MemoryTracker,
+    stack_guard: u32,
 }
 
 impl PendingState {
@@ -164,7 +199,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             exit_code: None,
             syscalls: Vec::new(),
             syscall_handler,
-            input_digest: input_digest.unwrap_or_default(),
+            input_digest,
             output_digest: None,
             pending: PendingState {
                 pc,
@@ -177,9 +212,34 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             },
             trace,
             cycles: SessionCycles::default(),
+            memory: MemoryTracker {
+                heap: TEXT_START..GUEST_MAX_MEM as u32,
+                ..Default::default()
+            },
+            stack_guard: GUEST_MIN_MEM as u32,
         }
     }
 
+    /// Set the lowest address the stack pointer may reach before execution
+    /// fails with a stack overflow.
+    ///
+    /// Defaults to [GUEST_MIN_MEM], so unless a guard is set here the stack
+    /// may grow down through the entire stack region before it is detected.
+    pub fn with_stack_guard(mut self, stack_guard: u32) -> Self {
+        self.stack_guard = stack_guard;
+        self
+    }
+
+    /// Set the range of addresses the guest heap occupies, used to track the
+    /// heap high-water mark. Stores outside this range, such as to static data
+    /// or preloaded input, are not counted as heap usage.
+    ///
+    /// Defaults to everything from [TEXT_START] up to [GUEST_MAX_MEM].
+    pub fn with_heap_region(mut self, heap: Range<u32>) -> Self {
+        self.memory.heap = heap;
+        self
+    }
+
     pub fn run<F: FnMut(Segment) -> Result<()>>(
         &mut self,
         segment_po2: usize,
@@ -234,6 +294,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
                 );
 
                 // split
+                self.memory.record_pages(&self.pager);
                 let (pre_state, partial_image, post_state) = self.pager.commit(self.pc);
                 callback(Segment {
                     partial_image,
@@ -244,7 +305,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
                     po2: segment_po2,
                     exit_code: ExitCode::SystemSplit,
                     index: segments,
-                    input_digest: self.input_digest,
+                    input_digest: self.input_digest()?,
                     output_digest: self.output_digest,
                 })?;
                 segments += 1;
@@ -258,6 +319,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             }
         }
 
+        self.memory.record_pages(&self.pager);
         let (pre_state, partial_image, post_state) = self.pager.commit(self.pc);
         let segment_cycles = self.insn_cycles + self.pager.cycles + RESERVED_CYCLES;
         let po2 = log2_ceil(segment_cycles.next_power_of_two());
@@ -272,7 +334,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             po2,
             exit_code,
             index: segments,
-            input_digest: self.input_digest,
+            input_digest: self.input_digest()?,
             output_digest: self.output_digest,
         })?;
         segments += 1;
@@ -296,6 +358,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
             pre_state: initial_state,
             post_state,
             output_digest: self.output_digest,
+            memory: self.memory.stats(),
         })
     }
 
@@ -337,6 +400,44 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
         self.pending.reset(self.pc);
         self.cycles.user = 0;
         self.cycles.total = 0;
+        self.memory = MemoryTracker {
+            heap: self.memory.heap.clone(),
+            stack_low_water: STACK_TOP,
+            ..Default::default()
+        };
+    }
+}
+
+impl MemoryTracker {
+    fn record_store(&mut self, addr: WordAddr) {
+        let addr = addr.baddr().0;
+        if self.heap.contains(&addr) {
+            self.heap_high_water = self.heap_high_water.max(addr + WORD_SIZE as u32);
+        }
+    }
+
+    fn record_stack_ptr(&mut self, sp: u32) {
+        // The entry point briefly loads the address of a static into sp before
+        // dereferencing it, so only values within the stack region count.
+        if is_guest_memory(sp) && sp <= STACK_TOP {
+            self.stack_low_water = self.stack_low_water.min(sp);
+        }
+    }
+
+    fn record_pages(&mut self, pager: &PagedMemory) {
+        self.touched_pages.extend(
+            pager
+                .page_indices()
+                .filter(|idx| (*idx as usize) * PAGE_SIZE < GUEST_MAX_MEM),
+        );
+    }
+
+    fn stats(&self) -> MemoryStats {
+        MemoryStats {
+            heap_high_water: self.heap_high_water,
+            stack_low_water: self.stack_low_water,
+            touched_pages: self.touched_pages.len(),
+        }
     }
 }
 
@@ -362,11 +463,20 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
         Ok(true)
     }
 
+    fn input_digest(&mut self) -> Result<Digest> {
+        if let Some(digest) = self.input_digest {
+            return Ok(digest);
+        }
+        let digest = self.syscall_handler.input_digest()?;
+        self.input_digest = Some(digest);
+        Ok(digest)
+    }
+
     fn ecall_input(&mut self) -> Result<bool> {
         tracing::debug!("[{}] ecall_input", self.insn_cycles);
         let a0 = self.load_register(REG_A0)? as usize;
         ensure!(a0 < DIGEST_WORDS, "sys_input index out of range");
-        let word = self.input_digest.as_words()[a0];
+        let word = self.input_digest()?.as_words()[a0];
         self.store_register(REG_A0, word)?;
 
         self.pending.cycles += 1;
@@ -525,6 +635,19 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
         Ok(true)
     }
 
+    fn check_stack_ptr(&self, sp: u32) -> Result<()> {
+        // Values above STACK_TOP are not stack addresses; the entry point
+        // briefly loads the address of a static into sp.
+        if sp <= STACK_TOP && sp < self.stack_guard {
+            bail!(
+                "stack overflow at pc: {:?}, sp: 0x{sp:08x} is below the stack guard 0x{:08x}",
+                self.pc,
+                self.stack_guard
+            );
+        }
+        Ok(())
+    }
+
     fn check_guest_addr(addr: ByteAddr) -> Result<ByteAddr> {
         if !is_guest_memory(addr.0) {
             bail!("{addr:?} is an invalid guest address");
@@ -619,6 +742,7 @@ impl<'a, 'b, S: Syscall> Executor<'a, 'b, S> {
 
     fn raw_store_memory(&mut self, addr: WordAddr, data: u32) -> Result<()> {
         // tracing::trace!("store_mem({:?}, 0x{data:08x})", addr.baddr());
+        self.memory.record_store(addr);
         self.pager.store(addr, data)
     }
 }
@@ -682,6 +806,10 @@ impl<'a, 'b, S: Syscall> EmuContext for Executor<'a, 'b, S> {
     fn store_register(&mut self, idx: usize, data: u32) -> Result<()> {
         if idx != 0 {
             // tracing::trace!("store_reg: x{idx} <= 0x{data:08x}");
+            if idx == REG_SP {
+                self.check_stack_ptr(data)?;
+                self.memory.record_stack_ptr(data);
+            }
             self.pager.store(SYSTEM_START + idx, data)?;
             if !self.trace.is_empty() {
                 self.pending
@@ -23,7 +23,7 @@ use risc0_zkvm_platform::{
 };
 use test_log::test;
 
-use super::{Syscall, SyscallContext};
+use super::{Executor, Syscall, SyscallContext};
 use crate::prove::emu::{
     addr::ByteAddr,
     exec::DEFAULT_SEGMENT_LIMIT_PO2,
@@ -102,6 +102,56 @@ fn basic() {
     assert_eq!(segment.exit_code, ExitCode::Halted(0));
 }
 
+#[test]
+fn memory_stats() {
+    let program = testutil::memory_usage();
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32).unwrap();
+
+    let result = super::execute(
+        image,
+        DEFAULT_SEGMENT_LIMIT_PO2,
+        DEFAULT_SESSION_LIMIT,
+        &BasicSyscall::default(),
+        None,
+    )
+    .unwrap();
+
+    let memory = result.result.memory;
+    assert_eq!(memory.heap_high_water, 0x0030_0004);
+    assert_eq!(memory.stack_low_water, 0x0020_0000);
+    assert_eq!(memory.touched_pages, 2);
+}
+
+#[test]
+fn memory_stats_heap_region() {
+    let program = testutil::memory_usage();
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32).unwrap();
+    let syscall = BasicSyscall::default();
+
+    // The store to 0x0030_0000 lies below the heap, so it is not counted.
+    let result = Executor::new(image, &syscall, None, vec![])
+        .with_heap_region(0x0040_0000..0x0050_0000)
+        .run(DEFAULT_SEGMENT_LIMIT_PO2, DEFAULT_SESSION_LIMIT, |_| Ok(()))
+        .unwrap();
+    assert_eq!(result.memory.heap_high_water, 0);
+}
+
+#[test]
+fn stack_overflow() {
+    let program = testutil::memory_usage();
+    let image = MemoryImage::new(&program, PAGE_SIZE as u32).unwrap();
+    let syscall = BasicSyscall::default();
+
+    let err = Executor::new(image, &syscall, None, vec![])
+        .with_stack_guard(0x0020_0100)
+        .run(DEFAULT_SEGMENT_LIMIT_PO2, DEFAULT_SESSION_LIMIT, |_| Ok(()))
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("stack overflow at pc: 0x00004000"));
+}
+
 #[test]
 fn system_split() {
     let program = testutil::simple_loop();
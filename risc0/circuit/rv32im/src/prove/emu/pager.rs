@@ -218,6 +218,11 @@ impl PagedMemory {
         faults
     }
 
+    /// Returns the indices of all pages touched within the current segment.
+    pub fn page_indices(&self) -> impl Iterator<Item = u32> + '_ {
+        self.page_states.keys().copied()
+    }
+
     fn load_page(&mut self, page_idx: u32) {
         tracing::trace!("load_page: 0x{page_idx:05x}");
         let page = self.image.load_page(page_idx);
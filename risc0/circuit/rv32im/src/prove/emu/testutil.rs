@@ -49,6 +49,19 @@ pub fn basic() -> Program {
     }
 }
 
+pub fn memory_usage() -> Program {
+    Program {
+        entry: 0x4000,
+        image: BTreeMap::from([
+            (0x4000, 0x00200137), // lui sp, 0x200
+            (0x4004, 0x00300337), // lui t1, 0x300
+            (0x4008, 0x00032023), // sw zero, 0(t1)
+            (0x400c, 0x000045b7), // lui a1, 0x4
+            (0x4010, 0x00000073), // ecall(halt)
+        ]),
+    }
+}
+
 pub fn simple_loop() -> Program {
     // loop.asm:
     //
@@ -17,11 +17,37 @@ extern crate alloc;
 use alloc::{string::String, vec, vec::Vec};
 
 use anyhow::{anyhow, Error, Result};
-use ark_bn254::Bn254;
+use ark_bn254::{Bn254, G1Affine, G2Affine};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use core::str::FromStr;
 use serde::{Deserialize, Serialize};
 
-use crate::{from_u256, g1_from_bytes, g2_from_bytes, Fr, VerifyingKey};
+use crate::{from_u256, g1_from_bytes, g1_to_bytes, g2_from_bytes, g2_to_bytes, Fr, VerifyingKey};
+
+/// Byte encoding of a [Seal].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SealFormat {
+    /// Big-endian affine coordinates of `a`, `b` and `c`, with the coordinates of `b` in the order
+    /// used by the EVM (i.e. imaginary part first). 256 bytes and untagged, as produced by
+    /// [Seal::to_vec].
+    Uncompressed,
+
+    /// The [Seal::COMPRESSED_TAG] byte followed by the compressed points `a`, `b` and `c` in the
+    /// arkworks compressed point encoding. 129 bytes, as produced by [Seal::to_compressed_vec].
+    Compressed,
+}
+
+impl SealFormat {
+    /// Determine the format of an encoded seal.
+    pub fn of(data: &[u8]) -> Result<Self, Error> {
+        match data.len() {
+            Seal::SIZE => Ok(Self::Uncompressed),
+            Seal::COMPRESSED_SIZE if data[0] == Seal::COMPRESSED_TAG => Ok(Self::Compressed),
+            _ => Err(anyhow!("Unrecognized seal encoding")),
+        }
+    }
+}
 
 /// Groth16 seal object encoded in big endian.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -39,6 +65,10 @@ impl Seal {
     const G1_GROUP_SIZE: usize = Self::ELEMENT_SIZE * 2;
     const G2_GROUP_SIZE: usize = Self::ELEMENT_SIZE * 4;
     const SIZE: usize = Self::G1_GROUP_SIZE * 2 + Self::G2_GROUP_SIZE;
+    const COMPRESSED_SIZE: usize = 1 + Self::ELEMENT_SIZE * 4;
+
+    /// Tag byte prefixed to a seal in the [SealFormat::Compressed] encoding.
+    pub const COMPRESSED_TAG: u8 = 0x01;
 
     /// Serialize the Groth16 `Seal` into a `Vec<u8>`
     pub fn to_vec(&self) -> Vec<u8> {
@@ -99,6 +129,55 @@ impl Seal {
 
         Ok(Seal { a, b, c })
     }
+
+    /// Serialize the Groth16 `Seal` with point compression, in the [SealFormat::Compressed]
+    /// encoding.
+    pub fn to_compressed_vec(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::with_capacity(Self::COMPRESSED_SIZE);
+        result.push(Self::COMPRESSED_TAG);
+        g1_from_bytes(&self.a)?
+            .serialize_compressed(&mut result)
+            .map_err(|err| anyhow!(err))?;
+        g2_from_bytes(&self.b)?
+            .serialize_compressed(&mut result)
+            .map_err(|err| anyhow!(err))?;
+        g1_from_bytes(&self.c)?
+            .serialize_compressed(&mut result)
+            .map_err(|err| anyhow!(err))?;
+        Ok(result)
+    }
+
+    /// Method to convert back from the [SealFormat::Compressed] encoding.
+    pub fn from_compressed_vec(data: &[u8]) -> Result<Seal, Error> {
+        if SealFormat::of(data)? != SealFormat::Compressed {
+            return Err(anyhow!("Seal is not in the compressed encoding"));
+        }
+        let mut reader = &data[1..];
+        let a = G1Affine::deserialize_compressed(&mut reader).map_err(|err| anyhow!(err))?;
+        let b = G2Affine::deserialize_compressed(&mut reader).map_err(|err| anyhow!(err))?;
+        let c = G1Affine::deserialize_compressed(&mut reader).map_err(|err| anyhow!(err))?;
+        Ok(Seal {
+            a: g1_to_bytes(&a)?,
+            b: g2_to_bytes(&b)?,
+            c: g1_to_bytes(&c)?,
+        })
+    }
+
+    /// Serialize the Groth16 `Seal` in the given format.
+    pub fn encode(&self, format: SealFormat) -> Result<Vec<u8>, Error> {
+        match format {
+            SealFormat::Uncompressed => Ok(self.to_vec()),
+            SealFormat::Compressed => self.to_compressed_vec(),
+        }
+    }
+
+    /// Deserialize a Groth16 `Seal` from any [SealFormat], determined with [SealFormat::of].
+    pub fn decode(data: &[u8]) -> Result<Seal, Error> {
+        match SealFormat::of(data)? {
+            SealFormat::Uncompressed => Self::from_vec(data),
+            SealFormat::Compressed => Self::from_compressed_vec(data),
+        }
+    }
 }
 
 impl TryFrom<ProofJson> for Seal {
@@ -395,4 +474,28 @@ mod tests {
         assert_eq!(vk.n_public, 1);
         vk.verifying_key().unwrap();
     }
+
+    #[test]
+    fn test_compressed_seal_round_trip() {
+        let proof: ProofJson =
+            serde_json::from_str(include_str!("../tests/data/proof.json")).unwrap();
+        let seal = Seal::try_from(proof).unwrap();
+
+        let uncompressed = seal.encode(SealFormat::Uncompressed).unwrap();
+        let compressed = seal.encode(SealFormat::Compressed).unwrap();
+        assert_eq!(uncompressed, seal.to_vec());
+        assert_eq!(compressed.len(), 129);
+        assert_eq!(compressed[0], Seal::COMPRESSED_TAG);
+        assert_eq!(
+            SealFormat::of(&uncompressed).unwrap(),
+            SealFormat::Uncompressed
+        );
+        assert_eq!(SealFormat::of(&compressed).unwrap(), SealFormat::Compressed);
+
+        assert_eq!(Seal::from_compressed_vec(&compressed).unwrap(), seal);
+        assert_eq!(Seal::decode(&compressed).unwrap(), seal);
+        assert_eq!(Seal::decode(&uncompressed).unwrap(), seal);
+        assert!(Seal::from_compressed_vec(&uncompressed).is_err());
+        assert!(Seal::decode(&compressed[1..]).is_err());
+    }
 }
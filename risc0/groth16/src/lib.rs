@@ -64,12 +64,13 @@
 
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::str::FromStr;
 
 use anyhow::{anyhow, Error, Result};
 use ark_bn254::{G1Affine, G2Affine};
-use ark_serialize::CanonicalDeserialize;
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use num_bigint::BigInt;
 use risc0_zkp::core::digest::Digest;
 
@@ -80,12 +81,13 @@ pub mod docker;
 mod seal_format;
 #[cfg(feature = "prove")]
 mod seal_to_json;
+pub mod solidity;
 mod verifier;
 
-pub use data_structures::{ProofJson, PublicInputsJson, Seal, VerifyingKeyJson};
+pub use data_structures::{ProofJson, PublicInputsJson, Seal, SealFormat, VerifyingKeyJson};
 #[cfg(feature = "prove")]
 pub use seal_to_json::to_json;
-pub use verifier::{verifying_key, Fr, Verifier, VerifyingKey};
+pub use verifier::{verifying_key, Fr, PreparedVerifyingKey, Verifier, VerifyingKey};
 
 /// Splits the digest in half returning a scalar for each halve.
 pub fn split_digest(d: Digest) -> Result<(Fr, Fr), Error> {
@@ -143,6 +145,30 @@ pub(crate) fn g2_from_bytes(elem: &[Vec<Vec<u8>>]) -> Result<G2Affine, Error> {
     G2Affine::deserialize_uncompressed(&*g2_affine).map_err(|err| anyhow!(err))
 }
 
+// Serialize an element over the G1 group into bytes in big-endian format
+pub(crate) fn g1_to_bytes(point: &G1Affine) -> Result<Vec<Vec<u8>>, Error> {
+    let (x, y) = point.xy().ok_or(anyhow!("G1 point at infinity"))?;
+    Ok(vec![fq_to_bytes(x)?, fq_to_bytes(y)?])
+}
+
+// Serialize an element over the G2 group into bytes in big-endian format
+pub(crate) fn g2_to_bytes(point: &G2Affine) -> Result<Vec<Vec<Vec<u8>>>, Error> {
+    let (x, y) = point.xy().ok_or(anyhow!("G2 point at infinity"))?;
+    Ok(vec![
+        vec![fq_to_bytes(&x.c1)?, fq_to_bytes(&x.c0)?],
+        vec![fq_to_bytes(&y.c1)?, fq_to_bytes(&y.c0)?],
+    ])
+}
+
+// Serialize a base field element into bytes in big-endian format
+fn fq_to_bytes(elem: &ark_bn254::Fq) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    elem.serialize_uncompressed(&mut bytes)
+        .map_err(|err| anyhow!(err))?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
 // Convert the U256 value to a byte array in big-endian format
 pub(crate) fn from_u256(value: &str) -> Result<Vec<u8>, Error> {
     if let Some(stripped) = value.strip_prefix("0x") {
@@ -0,0 +1,336 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of Solidity verifier contracts.
+//!
+//! The contracts produced here embed a [VerifyingKey] and the verifier
+//! parameters of a RISC Zero Groth16 receipt as constants, so that an on-chain
+//! verifier can be regenerated whenever they change instead of being maintained
+//! by hand.
+//!
+//! * [groth16_verifier] updates a `Groth16Verifier` contract generated by
+//!   [snarkJS](https://github.com/iden3/snarkjs), such as the one in
+//!   [risc0-ethereum](https://github.com/risc0/risc0-ethereum), with the
+//!   constants of a [VerifyingKey]. The contract itself is not distributed with
+//!   this crate, as snarkJS is licensed under the GPL.
+//! * [receipt_verifier] emits a `RiscZeroGroth16Verifier` contract deriving
+//!   from `Groth16Verifier`, which checks a RISC Zero Groth16 seal against a
+//!   claim digest, using a fixed control root and BN254 control ID.
+
+use alloc::{format, string::String, vec::Vec};
+use core::ops::Range;
+
+use anyhow::{anyhow, ensure, Result};
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_serialize::CanonicalSerialize;
+use num_bigint::BigUint;
+use risc0_zkp::core::digest::Digest;
+
+use crate::{split_digest, Fr, VerifyingKey};
+
+const HEADER: &str = r#"// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+// This file is automatically generated by risc0-groth16.
+
+pragma solidity ^0.8.9;
+"#;
+
+/// Update the Solidity source of a `Groth16Verifier` contract for `verifying_key`.
+///
+/// `template` is the source of a Groth16 verifier generated by snarkJS, e.g.
+/// `contracts/src/groth16/Groth16Verifier.sol` in risc0-ethereum. Its
+/// verifying key constants (`alphax` to `deltay2`, and `IC0x` onwards) are
+/// replaced with those of `verifying_key`, and the rest of the source is kept
+/// as is, including its license. The template must check as many public inputs
+/// as `verifying_key` has.
+pub fn groth16_verifier(template: &str, verifying_key: &VerifyingKey) -> Result<String> {
+    let vk = &verifying_key.0;
+    ensure!(
+        !vk.gamma_abc_g1.is_empty(),
+        "verifying key has no IC points"
+    );
+    let ics = vk.gamma_abc_g1.len();
+    ensure!(
+        find_constant(template, &format!("IC{ics}x")).is_none(),
+        "template has more public inputs than the verifying key"
+    );
+
+    let mut constants = Vec::new();
+    let (x, y) = g1_to_decimal(&vk.alpha_g1)?;
+    constants.extend([(String::from("alphax"), x), (String::from("alphay"), y)]);
+    for (name, point) in [
+        ("beta", &vk.beta_g2),
+        ("gamma", &vk.gamma_g2),
+        ("delta", &vk.delta_g2),
+    ] {
+        let [x1, x2, y1, y2] = g2_to_decimal(point)?;
+        constants.extend([
+            (format!("{name}x1"), x1),
+            (format!("{name}x2"), x2),
+            (format!("{name}y1"), y1),
+            (format!("{name}y2"), y2),
+        ]);
+    }
+    for (i, ic) in vk.gamma_abc_g1.iter().enumerate() {
+        let (x, y) = g1_to_decimal(ic)?;
+        constants.extend([(format!("IC{i}x"), x), (format!("IC{i}y"), y)]);
+    }
+
+    let mut source = String::from(template);
+    for (name, value) in constants {
+        let range = find_constant(&source, &name)
+            .ok_or_else(|| anyhow!("constant {name} not found in template"))?;
+        source.replace_range(range, &value);
+    }
+    Ok(source)
+}
+
+/// Generate the Solidity source of a `RiscZeroGroth16Verifier` contract.
+///
+/// The contract derives from the `Groth16Verifier` contract in
+/// `Groth16Verifier.sol`, as produced by [groth16_verifier] for the verifying
+/// key of the RISC Zero Groth16 prover. It exposes
+/// `verifyIntegrity(seal, claimDigest)`, which returns whether `seal`, encoded
+/// as by [Seal::to_vec][crate::Seal::to_vec], attests to the receipt claim with
+/// the given digest under `control_root` and `bn254_control_id`. These take the
+/// same values as the fields of the same name in the verifier parameters of a
+/// RISC Zero Groth16 receipt.
+pub fn receipt_verifier(control_root: Digest, bn254_control_id: Digest) -> Result<String> {
+    let (control_root_0, control_root_1) = split_digest(control_root)?;
+    let mut bn254_control_id = bn254_control_id;
+    // The control ID is interpreted as a little-endian scalar, as in Groth16Receipt verification.
+    bn254_control_id.as_mut_bytes().reverse();
+    let bn254_control_id = crate::fr_from_hex_string(&hex::encode(bn254_control_id))?;
+
+    Ok(format!(
+        r#"{HEADER}
+import {{Groth16Verifier}} from "./Groth16Verifier.sol";
+
+contract RiscZeroGroth16Verifier is Groth16Verifier {{
+    // Control root, split into two 128-bit halves.
+    uint256 public constant CONTROL_ROOT_0 = {control_root_0};
+    uint256 public constant CONTROL_ROOT_1 = {control_root_1};
+    // Control ID of the BN254 identity program.
+    uint256 public constant BN254_CONTROL_ID = {bn254_control_id};
+
+    /// Verify that `seal` attests to the receipt claim with digest `claimDigest`.
+    function verifyIntegrity(bytes calldata seal, bytes32 claimDigest) public view returns (bool) {{
+        if (seal.length != 256) {{
+            return false;
+        }}
+        (uint256[2] memory pA, uint256[2][2] memory pB, uint256[2] memory pC) =
+            abi.decode(seal, (uint256[2], uint256[2][2], uint256[2]));
+        (uint256 claim0, uint256 claim1) = splitDigest(claimDigest);
+        return this.verifyProof(
+            pA, pB, pC, [CONTROL_ROOT_0, CONTROL_ROOT_1, claim0, claim1, BN254_CONTROL_ID]
+        );
+    }}
+
+    /// Split a digest into two 128-bit halves, read as a little-endian integer.
+    function splitDigest(bytes32 digest) internal pure returns (uint256, uint256) {{
+        uint256 reversed = reverseByteOrderUint256(uint256(digest));
+        return (uint256(uint128(reversed)), reversed >> 128);
+    }}
+
+    /// Reverse the byte order of a uint256.
+    function reverseByteOrderUint256(uint256 input) internal pure returns (uint256 v) {{
+        v = input;
+        // swap bytes
+        v = ((v & 0xFF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00) >> 8)
+            | ((v & 0x00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF00FF) << 8);
+        // swap 2-byte long pairs
+        v = ((v & 0xFFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000) >> 16)
+            | ((v & 0x0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF0000FFFF) << 16);
+        // swap 4-byte long pairs
+        v = ((v & 0xFFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000) >> 32)
+            | ((v & 0x00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF00000000FFFFFFFF) << 32);
+        // swap 8-byte long pairs
+        v = ((v & 0xFFFFFFFFFFFFFFFF0000000000000000FFFFFFFFFFFFFFFF0000000000000000) >> 64)
+            | ((v & 0x0000000000000000FFFFFFFFFFFFFFFF0000000000000000FFFFFFFFFFFFFFFF) << 64);
+        // swap 16-byte long pairs
+        v = (v >> 128) | (v << 128);
+    }}
+}}
+"#,
+        control_root_0 = fr_to_decimal(&control_root_0),
+        control_root_1 = fr_to_decimal(&control_root_1),
+        bn254_control_id = fr_to_decimal(&bn254_control_id),
+    ))
+}
+
+// Find the value of the `constant {name} = {value};` declaration in `source`.
+fn find_constant(source: &str, name: &str) -> Option<Range<usize>> {
+    source
+        .match_indices("constant ")
+        .find_map(|(start, keyword)| {
+            let rest = source[start + keyword.len()..].trim_start();
+            let value = rest.strip_prefix(name)?.trim_start().strip_prefix('=')?;
+            let value = value.trim_start();
+            let len = value.find(|c: char| !c.is_ascii_digit())?;
+            (len > 0 && value[len..].trim_start().starts_with(';')).then(|| {
+                let offset = source.len() - value.len();
+                offset..offset + len
+            })
+        })
+}
+
+// Format a base field element as a decimal integer.
+fn fq_to_decimal(x: &Fq) -> String {
+    let mut buffer = Vec::<u8>::new();
+    // Serialization into a Vec should never fail.
+    x.serialize_uncompressed(&mut buffer).unwrap();
+    BigUint::from_bytes_le(&buffer).to_str_radix(10)
+}
+
+// Format a scalar field element as a decimal integer.
+fn fr_to_decimal(x: &Fr) -> String {
+    let mut buffer = Vec::<u8>::new();
+    // Serialization into a Vec should never fail.
+    x.0.serialize_uncompressed(&mut buffer).unwrap();
+    BigUint::from_bytes_le(&buffer).to_str_radix(10)
+}
+
+fn g1_to_decimal(p: &G1Affine) -> Result<(String, String)> {
+    let (x, y) = p.xy().ok_or(anyhow!("G1 point at infinity"))?;
+    Ok((fq_to_decimal(x), fq_to_decimal(y)))
+}
+
+// Solidity orders the coefficients of G2 coordinates as (imaginary, real).
+fn g2_to_decimal(p: &G2Affine) -> Result<[String; 4]> {
+    let (x, y): (&Fq2, &Fq2) = p.xy().ok_or(anyhow!("G2 point at infinity"))?;
+    Ok([
+        fq_to_decimal(&x.c1),
+        fq_to_decimal(&x.c0),
+        fq_to_decimal(&y.c1),
+        fq_to_decimal(&y.c0),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec, vec::Vec};
+
+    use ark_bn254::Bn254;
+    use risc0_zkp::core::digest::{digest, Digest};
+
+    use super::{groth16_verifier, receipt_verifier};
+    use crate::{
+        fr_from_hex_string, from_u256, g1_from_bytes, g2_from_bytes, verifying_key, VerifyingKey,
+    };
+
+    // A stand-in for a snarkJS verifier, declaring the verifying key constants for `ics` IC points.
+    fn template(ics: usize) -> String {
+        let mut source = String::from("contract Groth16Verifier {\n");
+        source += "    uint256 constant alphax  = 0;\n    uint256 constant alphay  = 0;\n";
+        for name in ["beta", "gamma", "delta"] {
+            for coord in ["x1", "x2", "y1", "y2"] {
+                source += &format!("    uint256 constant {name}{coord}  = 0;\n");
+            }
+        }
+        for i in 0..ics {
+            source +=
+                &format!("    uint256 constant IC{i}x = 0;\n    uint256 constant IC{i}y = 0;\n");
+        }
+        source + "}\n"
+    }
+
+    // Extract the value of a `uint256 constant` from generated Solidity.
+    fn constant(source: &str, name: &str) -> String {
+        let start = super::find_constant(source, name).unwrap().start;
+        let end = start + source[start..].find(';').unwrap();
+        String::from(&source[start..end])
+    }
+
+    fn u256(source: &str, name: &str) -> Vec<u8> {
+        from_u256(&constant(source, name)).unwrap()
+    }
+
+    #[test]
+    fn constants_match_verifying_key() {
+        let vk = verifying_key();
+        let source = groth16_verifier(&template(vk.0.gamma_abc_g1.len()), &vk).unwrap();
+
+        let g2 = |name: &str| {
+            g2_from_bytes(&[
+                vec![
+                    u256(&source, &format!("{name}x1")),
+                    u256(&source, &format!("{name}x2")),
+                ],
+                vec![
+                    u256(&source, &format!("{name}y1")),
+                    u256(&source, &format!("{name}y2")),
+                ],
+            ])
+            .unwrap()
+        };
+        let parsed = VerifyingKey(ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1_from_bytes(&[u256(&source, "alphax"), u256(&source, "alphay")]).unwrap(),
+            beta_g2: g2("beta"),
+            gamma_g2: g2("gamma"),
+            delta_g2: g2("delta"),
+            gamma_abc_g1: (0..vk.0.gamma_abc_g1.len())
+                .map(|i| {
+                    g1_from_bytes(&[
+                        u256(&source, &format!("IC{i}x")),
+                        u256(&source, &format!("IC{i}y")),
+                    ])
+                    .unwrap()
+                })
+                .collect(),
+        });
+        assert_eq!(parsed.0, vk.0);
+    }
+
+    #[test]
+    fn template_must_match_public_inputs() {
+        let vk = verifying_key();
+        let ics = vk.0.gamma_abc_g1.len();
+        assert!(groth16_verifier(&template(ics - 1), &vk).is_err());
+        assert!(groth16_verifier(&template(ics + 1), &vk).is_err());
+    }
+
+    #[test]
+    fn receipt_verifier_constants() {
+        let control_root: Digest =
+            digest!("a516a057c9fbf5629106300934d48e0e775d4230e41e503347cad96fcbde7e2e");
+        let bn254_control_id: Digest =
+            digest!("51b54a62f2aa599aef768744c95de8c7d89bf716e11b1179f05d6cf0bcfeb60e");
+        let source = receipt_verifier(control_root, bn254_control_id).unwrap();
+
+        let scalar = |name: &str| constant(&source, name).parse::<ark_bn254::Fr>().unwrap();
+        let (a0, a1) = crate::split_digest(control_root).unwrap();
+        assert_eq!(scalar("CONTROL_ROOT_0"), a0.0);
+        assert_eq!(scalar("CONTROL_ROOT_1"), a1.0);
+        let id_bn254 =
+            fr_from_hex_string("0eb6febcf06c5df079111be116f79bd8c7e85dc9448776ef9a59aaf2624ab551")
+                .unwrap();
+        assert_eq!(scalar("BN254_CONTROL_ID"), id_bn254.0);
+        assert!(source.contains("contract RiscZeroGroth16Verifier is Groth16Verifier"));
+    }
+}
@@ -17,12 +17,16 @@ extern crate alloc;
 use alloc::{vec, vec::Vec};
 
 use anyhow::{anyhow, Error, Result};
-use ark_bn254::{Bn254, G1Projective};
-use ark_ec::AffineRepr;
-use ark_groth16::{Groth16, PreparedVerifyingKey, Proof};
+use ark_bn254::{Bn254, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, Zero};
+use ark_groth16::{Groth16, Proof};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use risc0_binfmt::{tagged_iter, tagged_struct, Digestible};
-use risc0_zkp::core::{digest::Digest, hash::sha::Sha256};
+use risc0_zkp::core::{
+    digest::Digest,
+    hash::sha::{self, Sha256},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -92,28 +96,28 @@ impl Verifier {
         public_inputs: &[Fr],
         verifying_key: &VerifyingKey,
     ) -> Result<Self, Error> {
-        let pvk = ark_groth16::prepare_verifying_key(&verifying_key.0);
+        Self::with_prepared_key(seal, public_inputs, &verifying_key.prepare())
+    }
+
+    /// Creates a new Groth16 `Verifier` instance from an already [PreparedVerifyingKey].
+    pub fn with_prepared_key(
+        seal: &Seal,
+        public_inputs: &[Fr],
+        prepared_key: &PreparedVerifyingKey,
+    ) -> Result<Self, Error> {
         let mut encoded_pvk = Vec::new();
-        pvk.serialize_uncompressed(&mut encoded_pvk)
+        prepared_key
+            .0
+            .serialize_uncompressed(&mut encoded_pvk)
             .map_err(|err| anyhow!(err))?;
 
         let mut encoded_proof = Vec::new();
-        let proof = Proof::<Bn254> {
-            a: g1_from_bytes(&seal.a)?,
-            b: g2_from_bytes(&seal.b)?,
-            c: g1_from_bytes(&seal.c)?,
-        };
-        proof
+        proof_from_seal(seal)?
             .serialize_uncompressed(&mut encoded_proof)
             .map_err(|err| anyhow!(err))?;
 
         let mut encoded_prepared_inputs = Vec::new();
-        let prepared_inputs = Groth16::<Bn254>::prepare_inputs(
-            &pvk,
-            &public_inputs.iter().map(|x| x.0).collect::<Vec<_>>(),
-        )
-        .map_err(|err| anyhow!(err))?;
-        prepared_inputs
+        prepare_inputs(prepared_key, public_inputs)?
             .serialize_uncompressed(&mut encoded_prepared_inputs)
             .map_err(|err| anyhow!(err))?;
 
@@ -140,7 +144,7 @@ impl Verifier {
 
     /// Verifies the Groth16 proof.
     pub fn verify(&self) -> Result<(), Error> {
-        let pvk = &PreparedVerifyingKey::deserialize_uncompressed(&*self.encoded_pvk)
+        let pvk = &ark_groth16::PreparedVerifyingKey::deserialize_uncompressed(&*self.encoded_pvk)
             .map_err(|err| anyhow!(err))?;
         let proof =
             &Proof::deserialize_uncompressed(&*self.encoded_proof).map_err(|err| anyhow!(err))?;
@@ -154,10 +158,88 @@ impl Verifier {
             false => Err(anyhow!("Invalid proof")),
         }
     }
+
+    /// Verifies a batch of Groth16 proofs with a single multi-pairing.
+    ///
+    /// Each entry of `proofs` is a seal and the public inputs it is checked against, and all of
+    /// them are verified with the given key, which is prepared only once for the whole batch. The
+    /// verification equation of each proof is weighted by a 128-bit scalar derived by hashing
+    /// every proof and set of public inputs in the batch, and the weighted equations are checked
+    /// together. If any proof is invalid, the batch fails except with negligible probability. A
+    /// failed batch does not indicate which proof is invalid; verify each of them on its own to
+    /// find out.
+    pub fn verify_batch(
+        prepared_key: &PreparedVerifyingKey,
+        proofs: &[(Seal, Vec<Fr>)],
+    ) -> Result<(), Error> {
+        if proofs.is_empty() {
+            return Ok(());
+        }
+        let pvk = &prepared_key.0;
+        let proofs = proofs
+            .iter()
+            .map(|(seal, public_inputs)| {
+                Ok((
+                    proof_from_seal(seal)?,
+                    prepare_inputs(prepared_key, public_inputs)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Derive the weights from a hash of the whole batch, so that they cannot be known before
+        // the proofs are fixed.
+        let mut transcript = Vec::new();
+        pvk.serialize_uncompressed(&mut transcript)
+            .map_err(|err| anyhow!(err))?;
+        for (proof, prepared_inputs) in proofs.iter() {
+            proof
+                .serialize_uncompressed(&mut transcript)
+                .map_err(|err| anyhow!(err))?;
+            prepared_inputs
+                .serialize_uncompressed(&mut transcript)
+                .map_err(|err| anyhow!(err))?;
+        }
+        let seed = *sha::Impl::hash_bytes(&transcript);
+
+        let mut g1 = Vec::<G1Affine>::with_capacity(proofs.len() + 3);
+        let mut g2 = Vec::<G2Affine>::with_capacity(proofs.len() + 3);
+        let mut r_sum = ark_bn254::Fr::zero();
+        let mut r_inputs = G1Projective::zero();
+        let mut r_c = G1Projective::zero();
+        for (i, (proof, prepared_inputs)) in proofs.iter().enumerate() {
+            let mut preimage = seed.as_bytes().to_vec();
+            preimage.extend_from_slice(&(i as u64).to_le_bytes());
+            let hash = sha::Impl::hash_bytes(&preimage);
+            let r = ark_bn254::Fr::from_le_bytes_mod_order(&hash.as_bytes()[..16]);
+
+            g1.push((proof.a * r).into_affine());
+            g2.push(proof.b);
+            r_sum += r;
+            r_inputs += *prepared_inputs * r;
+            r_c += proof.c * r;
+        }
+
+        // Each proof satisfies e(A, B) = e(alpha, beta) * e(L, gamma) * e(C, delta), where L are the
+        // prepared public inputs. Check the sum of the equations weighted by r, moving every term
+        // to the left-hand side so that the product of pairings must be the identity.
+        g1.push(r_inputs.into_affine());
+        g2.push(-pvk.vk.gamma_g2);
+        g1.push(r_c.into_affine());
+        g2.push(-pvk.vk.delta_g2);
+        g1.push((pvk.vk.alpha_g1 * -r_sum).into_affine());
+        g2.push(pvk.vk.beta_g2);
+
+        let result = Bn254::final_exponentiation(Bn254::multi_miller_loop(g1, g2))
+            .ok_or_else(|| anyhow!("Invalid proof"))?;
+        match result.0.is_one() {
+            true => Ok(()),
+            false => Err(anyhow!("Invalid proof")),
+        }
+    }
 }
 
 /// Verifying key for Groth16 proofs.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Fr(#[serde(with = "serde_ark")] pub(crate) ark_bn254::Fr);
 
 impl Digestible for Fr {
@@ -176,10 +258,39 @@ impl Digestible for Fr {
     }
 }
 
+/// Convert a [Seal] into an arkworks Groth16 proof.
+fn proof_from_seal(seal: &Seal) -> Result<Proof<Bn254>, Error> {
+    Ok(Proof::<Bn254> {
+        a: g1_from_bytes(&seal.a)?,
+        b: g2_from_bytes(&seal.b)?,
+        c: g1_from_bytes(&seal.c)?,
+    })
+}
+
+/// Combine the public inputs with the verifying key into the single G1 point used by the
+/// verification equation.
+fn prepare_inputs(
+    prepared_key: &PreparedVerifyingKey,
+    public_inputs: &[Fr],
+) -> Result<G1Projective, Error> {
+    Groth16::<Bn254>::prepare_inputs(
+        &prepared_key.0,
+        &public_inputs.iter().map(|x| x.0).collect::<Vec<_>>(),
+    )
+    .map_err(|err| anyhow!(err))
+}
+
 /// Verifying key for Groth16 proofs.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VerifyingKey(#[serde(with = "serde_ark")] pub(crate) ark_groth16::VerifyingKey<Bn254>);
 
+/// Groth16 [VerifyingKey] with the pairings that do not depend on the proof precomputed.
+///
+/// Preparing the key is a significant part of the cost of verifying a single proof, so a prepared
+/// key should be reused when verifying many proofs, e.g. with [Verifier::verify_batch].
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey(ark_groth16::PreparedVerifyingKey<Bn254>);
+
 /// Hash a point on G1 or G2 by hashing the concatenated big-endian representation of (x, y).
 fn hash_point<S: Sha256>(p: impl AffineRepr) -> Digest {
     let mut buffer = Vec::<u8>::new();
@@ -247,33 +358,50 @@ pub fn verifying_key() -> VerifyingKey {
 
 // try_verifying_key executes entirely over const data and so should never error.
 fn try_verifying_key() -> Result<VerifyingKey, Error> {
-    let alpha_g1 = g1_from_bytes(&[from_u256(ALPHA_X)?, from_u256(ALPHA_Y)?])?;
-    let beta_g2 = g2_from_bytes(&[
-        vec![from_u256(BETA_X1)?, from_u256(BETA_X2)?],
-        vec![from_u256(BETA_Y1)?, from_u256(BETA_Y2)?],
-    ])?;
-    let gamma_g2 = g2_from_bytes(&[
-        vec![from_u256(GAMMA_X1)?, from_u256(GAMMA_X2)?],
-        vec![from_u256(GAMMA_Y1)?, from_u256(GAMMA_Y2)?],
-    ])?;
-    let delta_g2 = g2_from_bytes(&[
-        vec![from_u256(DELTA_X1)?, from_u256(DELTA_X2)?],
-        vec![from_u256(DELTA_Y1)?, from_u256(DELTA_Y2)?],
-    ])?;
-
-    let ic0 = g1_from_bytes(&[from_u256(IC0_X)?, from_u256(IC0_Y)?])?;
-    let ic1 = g1_from_bytes(&[from_u256(IC1_X)?, from_u256(IC1_Y)?])?;
-    let ic2 = g1_from_bytes(&[from_u256(IC2_X)?, from_u256(IC2_Y)?])?;
-    let ic3 = g1_from_bytes(&[from_u256(IC3_X)?, from_u256(IC3_Y)?])?;
-    let ic4 = g1_from_bytes(&[from_u256(IC4_X)?, from_u256(IC4_Y)?])?;
-    let ic5 = g1_from_bytes(&[from_u256(IC5_X)?, from_u256(IC5_Y)?])?;
-    let gamma_abc_g1 = vec![ic0, ic1, ic2, ic3, ic4, ic5];
-
-    Ok(VerifyingKey(ark_groth16::VerifyingKey::<Bn254> {
-        alpha_g1,
-        beta_g2,
-        gamma_g2,
-        delta_g2,
-        gamma_abc_g1,
-    }))
+    VerifyingKey::from_u256(
+        [ALPHA_X, ALPHA_Y],
+        [[BETA_X1, BETA_X2], [BETA_Y1, BETA_Y2]],
+        [[GAMMA_X1, GAMMA_X2], [GAMMA_Y1, GAMMA_Y2]],
+        [[DELTA_X1, DELTA_X2], [DELTA_Y1, DELTA_Y2]],
+        &[
+            [IC0_X, IC0_Y],
+            [IC1_X, IC1_Y],
+            [IC2_X, IC2_Y],
+            [IC3_X, IC3_Y],
+            [IC4_X, IC4_Y],
+            [IC5_X, IC5_Y],
+        ],
+    )
+}
+
+impl VerifyingKey {
+    /// Prepare this key for verifying proofs.
+    pub fn prepare(&self) -> PreparedVerifyingKey {
+        PreparedVerifyingKey(ark_groth16::prepare_verifying_key(&self.0))
+    }
+
+    /// Construct a [VerifyingKey] from the coordinates of its points, given as decimal or
+    /// `0x`-prefixed hex strings in the order used by the `Groth16Verifier.sol` contract.
+    pub fn from_u256(
+        alpha: [&str; 2],
+        beta: [[&str; 2]; 2],
+        gamma: [[&str; 2]; 2],
+        delta: [[&str; 2]; 2],
+        ic: &[[&str; 2]],
+    ) -> Result<Self, Error> {
+        let g1 = |[x, y]: [&str; 2]| g1_from_bytes(&[from_u256(x)?, from_u256(y)?]);
+        let g2 = |[[x1, x2], [y1, y2]]: [[&str; 2]; 2]| {
+            g2_from_bytes(&[
+                vec![from_u256(x1)?, from_u256(x2)?],
+                vec![from_u256(y1)?, from_u256(y2)?],
+            ])
+        };
+        Ok(Self(ark_groth16::VerifyingKey::<Bn254> {
+            alpha_g1: g1(alpha)?,
+            beta_g2: g2(beta)?,
+            gamma_g2: g2(gamma)?,
+            delta_g2: g2(delta)?,
+            gamma_abc_g1: ic.iter().copied().map(g1).collect::<Result<_>>()?,
+        }))
+    }
 }
@@ -14,7 +14,7 @@
 
 #[cfg(test)]
 mod tests {
-    use risc0_groth16::{ProofJson, PublicInputsJson, Verifier, VerifyingKeyJson};
+    use risc0_groth16::{ProofJson, PublicInputsJson, Seal, Verifier, VerifyingKeyJson};
 
     const TEST_VERIFICATION_KEY: &str = include_str!("data/verification_key.json");
     const TEST_PROOF: &str = include_str!("data/proof.json");
@@ -30,4 +30,29 @@ mod tests {
         let verifier = Verifier::from_json(proof, public_inputs, verifying_key).unwrap();
         verifier.verify().unwrap();
     }
+
+    fn proof(public_input: &str) -> (Seal, Vec<risc0_groth16::Fr>) {
+        let proof: ProofJson = serde_json::from_str(TEST_PROOF).unwrap();
+        let public_inputs = PublicInputsJson {
+            values: vec![public_input.to_string()],
+        };
+        (
+            proof.try_into().unwrap(),
+            public_inputs.to_scalar().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let verifying_key: VerifyingKeyJson = serde_json::from_str(TEST_VERIFICATION_KEY).unwrap();
+        let pvk = verifying_key.verifying_key().unwrap().prepare();
+
+        Verifier::verify_batch(&pvk, &[]).unwrap();
+        Verifier::verify_batch(&pvk, &[proof("33"), proof("33"), proof("33")]).unwrap();
+
+        let (seal, public_inputs) = proof("34");
+        let invalid = Verifier::with_prepared_key(&seal, &public_inputs, &pvk).unwrap();
+        assert!(invalid.verify().is_err());
+        assert!(Verifier::verify_batch(&pvk, &[proof("33"), proof("34"), proof("33")]).is_err());
+    }
 }
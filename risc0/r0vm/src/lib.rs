@@ -31,6 +31,10 @@ struct Cli {
     #[arg(long)]
     receipt: Option<PathBuf>,
 
+    /// The encoding of the receipt output file.
+    #[arg(long, value_enum, default_value_t = ReceiptFormat::Bincode)]
+    receipt_format: ReceiptFormat,
+
     /// The hash function to use to produce a proof.
     #[arg(long, value_enum, default_value_t = HashFn::Poseidon2)]
     hashfn: HashFn,
@@ -92,6 +96,16 @@ enum HashFn {
     Poseidon2,
 }
 
+#[derive(Clone, PartialEq, ValueEnum)]
+enum ReceiptFormat {
+    /// bincode, as also returned by Bonsai.
+    #[value(name = "bincode")]
+    Bincode,
+    /// The versioned encoding of `Receipt::encode`.
+    #[value(name = "canonical")]
+    Canonical,
+}
+
 #[derive(Clone, PartialEq, ValueEnum)]
 enum ReceiptKind {
     #[value(name = "composite")]
@@ -150,14 +164,30 @@ pub fn main() {
         exec.run().unwrap()
     };
 
+    if args.verbose > 0 {
+        let stats = session.stats();
+        eprintln!(
+            "Executed {} segments: {} total cycles, {} user cycles",
+            stats.segments, stats.total_cycles, stats.user_cycles
+        );
+        if let Some(memory) = stats.memory {
+            eprintln!(
+                "Memory: heap high-water mark 0x{:08x}, stack depth {} bytes, {} pages touched",
+                memory.heap_high_water, memory.stack_depth, memory.touched_pages
+            );
+        }
+    }
+
     let prover = args.get_prover();
     let ctx = VerifierContext::default();
     let receipt = prover.prove_session(&ctx, &session).unwrap().receipt;
 
-    let receipt_data = bincode::serialize(&receipt).unwrap();
-    let receipt_bytes = bytemuck::cast_slice(&receipt_data);
+    let receipt_data = match args.receipt_format {
+        ReceiptFormat::Bincode => bincode::serialize(&receipt).unwrap(),
+        ReceiptFormat::Canonical => receipt.encode(),
+    };
     if let Some(receipt_file) = args.receipt.as_ref() {
-        fs::write(receipt_file, receipt_bytes).expect("Unable to write receipt file");
+        fs::write(receipt_file, &receipt_data).expect("Unable to write receipt file");
         if args.verbose > 0 {
             eprintln!(
                 "Wrote {} bytes of receipt to {}",
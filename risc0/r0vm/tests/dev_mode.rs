@@ -28,12 +28,14 @@ fn run_dev_mode() -> Receipt {
         .arg(MULTI_TEST_PATH)
         .arg("--receipt")
         .arg(&*receipt_file)
+        .arg("--receipt-format")
+        .arg("canonical")
         .write_stdin(bytemuck::cast_slice(&input));
 
     cmd.assert().success();
 
     let data = std::fs::read(receipt_file).unwrap();
-    bincode::deserialize(&data).unwrap()
+    Receipt::decode(&data).unwrap()
 }
 
 #[test]
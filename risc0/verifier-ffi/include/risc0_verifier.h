@@ -0,0 +1,161 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#ifndef RISC0_VERIFIER_H
+#define RISC0_VERIFIER_H
+
+// This file is generated by cbindgen from src/lib.rs. Do not edit it by hand; run
+// `RISC0_UPDATE_HEADER=1 cargo test -p risc0-verifier-ffi --test header` to regenerate it.
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status returned by the fallible functions of this library.
+typedef enum R0Status {
+  // The call succeeded.
+  R0_STATUS_OK = 0,
+  // A pointer argument that must not be null was null.
+  R0_STATUS_NULL_POINTER = 1,
+  // An argument was invalid, e.g. an unknown release.
+  R0_STATUS_INVALID_ARGUMENT = 2,
+  // The receipt could not be decoded, or exceeds the given limits.
+  R0_STATUS_DECODE_ERROR = 3,
+  // The receipt failed verification.
+  R0_STATUS_VERIFICATION_ERROR = 4,
+  // The library panicked while handling the call.
+  R0_STATUS_PANIC = 5,
+} R0Status;
+
+// A decoded receipt.
+//
+// Created by [r0_receipt_decode], and freed with [r0_receipt_free].
+typedef struct R0Receipt R0Receipt;
+
+// Context for the verification of receipts.
+//
+// Created by [r0_verifier_context_new] or [r0_verifier_context_for_release], and freed with
+// [r0_verifier_context_free].
+typedef struct R0VerifierContext R0VerifierContext;
+
+// Limits on the size of receipts, as in [VerifierLimits]. A limit of zero is unbounded.
+typedef struct R0VerifierLimits {
+  // Maximum size in bytes of the seal of any receipt.
+  size_t max_seal_bytes;
+  // Maximum number of segments in a composite receipt.
+  size_t max_segments;
+  // Maximum number of assumption receipts in a composite receipt.
+  size_t max_assumptions;
+  // Maximum size in bytes of the journal.
+  size_t max_journal_bytes;
+  // Maximum size in bytes of the encoded receipt. Only checked by [r0_receipt_decode].
+  size_t max_encoded_bytes;
+} R0VerifierLimits;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Decode a receipt from the canonical binary encoding produced by `Receipt::encode`.
+//
+// If `limits` is not null, receipts exceeding them are rejected with [R0Status::DecodeError]. On
+// success, the receipt is written to `out`, and must be freed with [r0_receipt_free].
+//
+// # Safety
+//
+// `data` must point to `len` readable bytes, `limits` must be null or point to a valid
+// [R0VerifierLimits], and `out` must be valid for writes.
+enum R0Status r0_receipt_decode(const uint8_t *data,
+                                size_t len,
+                                const struct R0VerifierLimits *limits,
+                                struct R0Receipt **out);
+
+// Free a receipt returned by [r0_receipt_decode]. Does nothing if `receipt` is null.
+//
+// # Safety
+//
+// `receipt` must be null or a receipt returned by [r0_receipt_decode] that has not been freed.
+void r0_receipt_free(struct R0Receipt *receipt);
+
+// Verify that the receipt proves a successful execution of the guest with the given image ID.
+//
+// The receipt is verified with `ctx`, or with the default context of this release if `ctx` is
+// null. Returns [R0Status::VerificationError] if the receipt is not valid, or if it is or includes
+// a fake receipt, even when the `RISC0_DEV_MODE` environment variable is set.
+//
+// # Safety
+//
+// `receipt` must be a valid receipt, `ctx` must be null or a valid context, and `image_id` must
+// point to 32 readable bytes.
+enum R0Status r0_receipt_verify(const struct R0Receipt *receipt,
+                                const struct R0VerifierContext *ctx,
+                                const uint8_t *image_id);
+
+// Get the journal of a receipt.
+//
+// On success, `data` and `len` are set to the bytes of the journal, which remain valid until the
+// receipt is freed.
+//
+// # Safety
+//
+// `receipt` must be a valid receipt, and `data` and `len` must be valid for writes.
+enum R0Status r0_receipt_journal(const struct R0Receipt *receipt,
+                                 const uint8_t **data,
+                                 size_t *len);
+
+// Create a verifier context with the verifier parameters of this release and no limits.
+//
+// The context must be freed with [r0_verifier_context_free].
+struct R0VerifierContext *r0_verifier_context_new(void);
+
+// Create a verifier context with the verifier parameters of the given release, as a
+// full version string (e.g. "1.0.0-rc.5").
+//
+// Returns [R0Status::InvalidArgument] if the release is unknown. On success, the context is
+// written to `out`, and must be freed with [r0_verifier_context_free].
+//
+// # Safety
+//
+// `release` must be a valid NUL-terminated string, and `out` must be valid for writes.
+enum R0Status r0_verifier_context_for_release(const char *release, struct R0VerifierContext **out);
+
+// Set the limits on the size of receipts verified with the given context.
+//
+// # Safety
+//
+// `ctx` must be a valid context, and `limits` must point to a valid [R0VerifierLimits].
+enum R0Status r0_verifier_context_set_limits(struct R0VerifierContext *ctx,
+                                             const struct R0VerifierLimits *limits);
+
+// Free a context returned by [r0_verifier_context_new] or [r0_verifier_context_for_release].
+// Does nothing if `ctx` is null.
+//
+// # Safety
+//
+// `ctx` must be null or a context that has not been freed.
+void r0_verifier_context_free(struct R0VerifierContext *ctx);
+
+// Message describing the error of the last call on the current thread to a function of this
+// library returning an [R0Status], or null if that call succeeded.
+//
+// The message remains valid until the next such call on the same thread.
+const char *r0_last_error(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RISC0_VERIFIER_H */
@@ -0,0 +1,374 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A C ABI for verifying RISC Zero receipts from services written in other languages.
+//!
+//! Receipts are decoded from the canonical binary encoding produced by [Receipt::encode], and
+//! verified as with [Receipt::verify_with_context]. Fake receipts, which carry no proof and are
+//! accepted by the zkVM in dev mode, are always rejected. The C header for this library is
+//! `include/risc0_verifier.h`, and is generated from this file with cbindgen.
+//!
+//! Every fallible function returns an [R0Status]. When it is not [R0Status::Ok], a message
+//! describing the error can be retrieved with [r0_last_error] on the same thread.
+//!
+//! ```c
+//! R0Receipt *receipt = NULL;
+//! if (r0_receipt_decode(bytes, len, NULL, &receipt) != R0_STATUS_OK ||
+//!     r0_receipt_verify(receipt, NULL, image_id) != R0_STATUS_OK) {
+//!   fprintf(stderr, "invalid receipt: %s\n", r0_last_error());
+//! }
+//! r0_receipt_free(receipt);
+//! ```
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+    ptr, slice,
+};
+
+use risc0_zkvm::{
+    sha::{Digest, DIGEST_BYTES},
+    CompositeReceipt, InnerAssumptionReceipt, InnerReceipt, Receipt, VerifierContext,
+    VerifierLimits,
+};
+
+/// Status returned by the fallible functions of this library.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum R0Status {
+    /// The call succeeded.
+    Ok = 0,
+
+    /// A pointer argument that must not be null was null.
+    NullPointer = 1,
+
+    /// An argument was invalid, e.g. an unknown release.
+    InvalidArgument = 2,
+
+    /// The receipt could not be decoded, or exceeds the given limits.
+    DecodeError = 3,
+
+    /// The receipt failed verification.
+    VerificationError = 4,
+
+    /// The library panicked while handling the call.
+    Panic = 5,
+}
+
+/// A decoded receipt.
+///
+/// Created by [r0_receipt_decode], and freed with [r0_receipt_free].
+pub struct R0Receipt(Receipt);
+
+/// Context for the verification of receipts.
+///
+/// Created by [r0_verifier_context_new] or [r0_verifier_context_for_release], and freed with
+/// [r0_verifier_context_free].
+pub struct R0VerifierContext(VerifierContext);
+
+/// Limits on the size of receipts, as in [VerifierLimits]. A limit of zero is unbounded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct R0VerifierLimits {
+    /// Maximum size in bytes of the seal of any receipt.
+    pub max_seal_bytes: usize,
+
+    /// Maximum number of segments in a composite receipt.
+    pub max_segments: usize,
+
+    /// Maximum number of assumption receipts in a composite receipt.
+    pub max_assumptions: usize,
+
+    /// Maximum size in bytes of the journal.
+    pub max_journal_bytes: usize,
+
+    /// Maximum size in bytes of the encoded receipt. Only checked by [r0_receipt_decode].
+    pub max_encoded_bytes: usize,
+}
+
+impl From<&R0VerifierLimits> for VerifierLimits {
+    fn from(limits: &R0VerifierLimits) -> Self {
+        let bounded = |limit| (limit != 0).then_some(limit);
+        let mut result = VerifierLimits::default();
+        result.max_seal_bytes = bounded(limits.max_seal_bytes);
+        result.max_segments = bounded(limits.max_segments);
+        result.max_assumptions = bounded(limits.max_assumptions);
+        result.max_journal_bytes = bounded(limits.max_journal_bytes);
+        result.max_encoded_bytes = bounded(limits.max_encoded_bytes);
+        result
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+struct Error {
+    status: R0Status,
+    message: String,
+}
+
+impl Error {
+    fn new(status: R0Status, message: impl ToString) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+        }
+    }
+
+    fn null(name: &str) -> Self {
+        Self::new(R0Status::NullPointer, format!("{name} must not be null"))
+    }
+}
+
+/// Run the body of an exported function, recording its error for [r0_last_error].
+fn ffi_call(f: impl FnOnce() -> Result<(), Error>) -> R0Status {
+    let (status, message) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => (R0Status::Ok, None),
+        Ok(Err(err)) => (err.status, Some(err.message)),
+        Err(payload) => (R0Status::Panic, Some(panic_message(payload))),
+    };
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = message.map(|message| CString::new(message).unwrap_or_default());
+    });
+    status
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown error");
+    format!("panic: {message}")
+}
+
+unsafe fn as_ref<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, Error> {
+    ptr.as_ref().ok_or_else(|| Error::null(name))
+}
+
+unsafe fn as_mut<'a, T>(ptr: *mut T, name: &str) -> Result<&'a mut T, Error> {
+    ptr.as_mut().ok_or_else(|| Error::null(name))
+}
+
+unsafe fn as_slice<'a>(data: *const u8, len: usize, name: &str) -> Result<&'a [u8], Error> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if data.is_null() {
+        return Err(Error::null(name));
+    }
+    Ok(slice::from_raw_parts(data, len))
+}
+
+/// Whether the receipt is, or includes as an assumption, a fake receipt.
+fn contains_fake(inner: &InnerReceipt) -> bool {
+    match inner {
+        InnerReceipt::Fake(_) => true,
+        InnerReceipt::Composite(inner) => contains_fake_assumption(inner),
+        _ => false,
+    }
+}
+
+fn contains_fake_assumption(receipt: &CompositeReceipt) -> bool {
+    receipt
+        .assumption_receipts
+        .iter()
+        .any(|assumption| match assumption {
+            InnerAssumptionReceipt::Fake(_) => true,
+            InnerAssumptionReceipt::Composite(inner) => contains_fake_assumption(inner),
+            _ => false,
+        })
+}
+
+/// Decode a receipt from the canonical binary encoding produced by `Receipt::encode`.
+///
+/// If `limits` is not null, receipts exceeding them are rejected with [R0Status::DecodeError]. On
+/// success, the receipt is written to `out`, and must be freed with [r0_receipt_free].
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, `limits` must be null or point to a valid
+/// [R0VerifierLimits], and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn r0_receipt_decode(
+    data: *const u8,
+    len: usize,
+    limits: *const R0VerifierLimits,
+    out: *mut *mut R0Receipt,
+) -> R0Status {
+    ffi_call(|| {
+        let out = as_mut(out, "out")?;
+        let data = as_slice(data, len, "data")?;
+        let receipt = match limits.as_ref() {
+            Some(limits) => Receipt::decode_with_limits(data, &limits.into()),
+            None => Receipt::decode(data),
+        }
+        .map_err(|err| Error::new(R0Status::DecodeError, format!("{err:#}")))?;
+        *out = Box::into_raw(Box::new(R0Receipt(receipt)));
+        Ok(())
+    })
+}
+
+/// Free a receipt returned by [r0_receipt_decode]. Does nothing if `receipt` is null.
+///
+/// # Safety
+///
+/// `receipt` must be null or a receipt returned by [r0_receipt_decode] that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn r0_receipt_free(receipt: *mut R0Receipt) {
+    if !receipt.is_null() {
+        drop(Box::from_raw(receipt));
+    }
+}
+
+/// Verify that the receipt proves a successful execution of the guest with the given image ID.
+///
+/// The receipt is verified with `ctx`, or with the default context of this release if `ctx` is
+/// null. Returns [R0Status::VerificationError] if the receipt is not valid, or if it is or includes
+/// a fake receipt, even when the `RISC0_DEV_MODE` environment variable is set.
+///
+/// # Safety
+///
+/// `receipt` must be a valid receipt, `ctx` must be null or a valid context, and `image_id` must
+/// point to 32 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn r0_receipt_verify(
+    receipt: *const R0Receipt,
+    ctx: *const R0VerifierContext,
+    image_id: *const u8,
+) -> R0Status {
+    ffi_call(|| {
+        let receipt = &as_ref(receipt, "receipt")?.0;
+        let image_id = as_slice(image_id, DIGEST_BYTES, "image_id")?;
+        let image_id = Digest::try_from(image_id).unwrap();
+        if contains_fake(&receipt.inner) {
+            return Err(Error::new(
+                R0Status::VerificationError,
+                "fake receipts carry no proof and are not accepted",
+            ));
+        }
+        match ctx.as_ref() {
+            Some(ctx) => receipt.verify_with_context(&ctx.0, image_id),
+            None => receipt.verify(image_id),
+        }
+        .map_err(|err| Error::new(R0Status::VerificationError, err))
+    })
+}
+
+/// Get the journal of a receipt.
+///
+/// On success, `data` and `len` are set to the bytes of the journal, which remain valid until the
+/// receipt is freed.
+///
+/// # Safety
+///
+/// `receipt` must be a valid receipt, and `data` and `len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn r0_receipt_journal(
+    receipt: *const R0Receipt,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> R0Status {
+    ffi_call(|| {
+        let journal = &as_ref(receipt, "receipt")?.0.journal.bytes;
+        let data = as_mut(data, "data")?;
+        let len = as_mut(len, "len")?;
+        *data = journal.as_ptr();
+        *len = journal.len();
+        Ok(())
+    })
+}
+
+/// Create a verifier context with the verifier parameters of this release and no limits.
+///
+/// The context must be freed with [r0_verifier_context_free].
+#[no_mangle]
+pub extern "C" fn r0_verifier_context_new() -> *mut R0VerifierContext {
+    Box::into_raw(Box::new(R0VerifierContext(VerifierContext::default())))
+}
+
+/// Create a verifier context with the verifier parameters of the given release, as a
+/// full version string (e.g. "1.0.0-rc.5").
+///
+/// Returns [R0Status::InvalidArgument] if the release is unknown. On success, the context is
+/// written to `out`, and must be freed with [r0_verifier_context_free].
+///
+/// # Safety
+///
+/// `release` must be a valid NUL-terminated string, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn r0_verifier_context_for_release(
+    release: *const c_char,
+    out: *mut *mut R0VerifierContext,
+) -> R0Status {
+    ffi_call(|| {
+        let out = as_mut(out, "out")?;
+        if release.is_null() {
+            return Err(Error::null("release"));
+        }
+        let release = CStr::from_ptr(release)
+            .to_str()
+            .map_err(|err| Error::new(R0Status::InvalidArgument, err))?;
+        let ctx = VerifierContext::for_release(release)
+            .map_err(|err| Error::new(R0Status::InvalidArgument, err))?;
+        *out = Box::into_raw(Box::new(R0VerifierContext(ctx)));
+        Ok(())
+    })
+}
+
+/// Set the limits on the size of receipts verified with the given context.
+///
+/// # Safety
+///
+/// `ctx` must be a valid context, and `limits` must point to a valid [R0VerifierLimits].
+#[no_mangle]
+pub unsafe extern "C" fn r0_verifier_context_set_limits(
+    ctx: *mut R0VerifierContext,
+    limits: *const R0VerifierLimits,
+) -> R0Status {
+    ffi_call(|| {
+        let ctx = as_mut(ctx, "ctx")?;
+        ctx.0.limits = as_ref(limits, "limits")?.into();
+        Ok(())
+    })
+}
+
+/// Free a context returned by [r0_verifier_context_new] or [r0_verifier_context_for_release].
+/// Does nothing if `ctx` is null.
+///
+/// # Safety
+///
+/// `ctx` must be null or a context that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn r0_verifier_context_free(ctx: *mut R0VerifierContext) {
+    if !ctx.is_null() {
+        drop(Box::from_raw(ctx));
+    }
+}
+
+/// Message describing the error of the last call on the current thread to a function of this
+/// library returning an [R0Status], or null if that call succeeded.
+///
+/// The message remains valid until the next such call on the same thread.
+#[no_mangle]
+pub extern "C" fn r0_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
@@ -0,0 +1,50 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ptr;
+
+use risc0_verifier_ffi::*;
+use risc0_zkvm::{sha::Digest, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim};
+
+#[test]
+fn reject_fake_receipt_in_dev_mode() {
+    let image_id = Digest::from([1u32; 8]);
+    let journal = b"journal".to_vec();
+    let bytes = Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+            image_id,
+            journal.clone(),
+        ))),
+        journal,
+    )
+    .encode();
+
+    let mut receipt = ptr::null_mut();
+    let status =
+        unsafe { r0_receipt_decode(bytes.as_ptr(), bytes.len(), ptr::null(), &mut receipt) };
+    assert_eq!(status, R0Status::Ok);
+
+    let ctx = r0_verifier_context_new();
+    temp_env::with_var("RISC0_DEV_MODE", Some("1"), || {
+        for ctx in [ptr::null(), ctx.cast_const()] {
+            let status = unsafe { r0_receipt_verify(receipt, ctx, image_id.as_bytes().as_ptr()) };
+            assert_eq!(status, R0Status::VerificationError);
+        }
+    });
+
+    unsafe {
+        r0_verifier_context_free(ctx);
+        r0_receipt_free(receipt);
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{ffi::CStr, ptr, slice};
+
+use risc0_verifier_ffi::*;
+use risc0_zkvm::{
+    sha::{Digest, Digestible},
+    FakeReceipt, Groth16Receipt, Groth16ReceiptVerifierParameters, InnerReceipt, Receipt,
+    ReceiptClaim,
+};
+
+const JOURNAL: &[u8] = b"journal";
+
+fn last_error() -> Option<String> {
+    let message = r0_last_error();
+    (!message.is_null()).then(|| {
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned()
+    })
+}
+
+fn decode(bytes: &[u8], limits: Option<&R0VerifierLimits>) -> Result<*mut R0Receipt, R0Status> {
+    let limits = limits.map_or(ptr::null(), |limits| limits as *const _);
+    let mut receipt = ptr::null_mut();
+    match unsafe { r0_receipt_decode(bytes.as_ptr(), bytes.len(), limits, &mut receipt) } {
+        R0Status::Ok => Ok(receipt),
+        status => Err(status),
+    }
+}
+
+fn fake_receipt() -> Receipt {
+    Receipt::new(
+        InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+            Digest::ZERO,
+            JOURNAL.to_vec(),
+        ))),
+        JOURNAL.to_vec(),
+    )
+}
+
+// A Groth16 receipt with an invalid seal, which is only rejected once the seal is verified.
+fn groth16_receipt() -> Receipt {
+    Receipt::new(
+        InnerReceipt::Groth16(Groth16Receipt::new(
+            vec![1u8; 256],
+            ReceiptClaim::ok(Digest::ZERO, JOURNAL.to_vec()),
+            Groth16ReceiptVerifierParameters::default().digest(),
+        )),
+        JOURNAL.to_vec(),
+    )
+}
+
+#[test]
+fn decode_rejects_malformed_receipts() {
+    assert_eq!(decode(b"not a receipt", None), Err(R0Status::DecodeError));
+    assert!(last_error().unwrap().contains("magic"));
+
+    let bytes = fake_receipt().encode();
+    let status =
+        unsafe { r0_receipt_decode(bytes.as_ptr(), bytes.len(), ptr::null(), ptr::null_mut()) };
+    assert_eq!(status, R0Status::NullPointer);
+    assert_eq!(last_error().unwrap(), "out must not be null");
+}
+
+#[test]
+fn verify_rejects_invalid_receipts() {
+    let receipt = decode(&fake_receipt().encode(), None).unwrap();
+    assert_eq!(last_error(), None);
+
+    let mut data = ptr::null();
+    let mut len = 0;
+    assert_eq!(
+        unsafe { r0_receipt_journal(receipt, &mut data, &mut len) },
+        R0Status::Ok
+    );
+    assert_eq!(unsafe { slice::from_raw_parts(data, len) }, JOURNAL);
+
+    let image_id = Digest::ZERO;
+    let status = unsafe { r0_receipt_verify(receipt, ptr::null(), image_id.as_bytes().as_ptr()) };
+    assert_eq!(status, R0Status::VerificationError);
+    assert!(last_error().unwrap().contains("fake receipts"));
+
+    let status = unsafe { r0_receipt_verify(receipt, ptr::null(), ptr::null()) };
+    assert_eq!(status, R0Status::NullPointer);
+
+    unsafe { r0_receipt_free(receipt) };
+
+    let receipt = decode(&groth16_receipt().encode(), None).unwrap();
+    let status = unsafe { r0_receipt_verify(receipt, ptr::null(), image_id.as_bytes().as_ptr()) };
+    assert_eq!(status, R0Status::VerificationError);
+    assert_eq!(last_error().unwrap(), "invalid receipt format");
+
+    unsafe { r0_receipt_free(receipt) };
+}
+
+#[test]
+fn limits_reject_oversized_receipts() {
+    let bytes = groth16_receipt().encode();
+    let encoded_limits = R0VerifierLimits {
+        max_encoded_bytes: bytes.len() - 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        decode(&bytes, Some(&encoded_limits)),
+        Err(R0Status::DecodeError)
+    );
+    assert!(last_error().unwrap().contains("encoded size"));
+
+    let limits = R0VerifierLimits {
+        max_journal_bytes: JOURNAL.len() - 1,
+        ..Default::default()
+    };
+    assert_eq!(decode(&bytes, Some(&limits)), Err(R0Status::DecodeError));
+    assert!(last_error().unwrap().contains("journal size"));
+
+    let receipt = decode(&bytes, None).unwrap();
+    let ctx = r0_verifier_context_new();
+    assert_eq!(
+        unsafe { r0_verifier_context_set_limits(ctx, &limits) },
+        R0Status::Ok
+    );
+    let image_id = Digest::ZERO;
+    let status = unsafe { r0_receipt_verify(receipt, ctx, image_id.as_bytes().as_ptr()) };
+    assert_eq!(status, R0Status::VerificationError);
+    assert!(last_error().unwrap().contains("journal size"));
+
+    unsafe {
+        r0_verifier_context_free(ctx);
+        r0_receipt_free(receipt);
+    }
+}
+
+#[test]
+fn verifier_context_for_release() {
+    let mut ctx = ptr::null_mut();
+    let status = unsafe { r0_verifier_context_for_release(c"1.0.0-rc.5".as_ptr(), &mut ctx) };
+    assert_eq!(status, R0Status::Ok);
+    unsafe { r0_verifier_context_free(ctx) };
+
+    let status = unsafe { r0_verifier_context_for_release(c"0.1".as_ptr(), &mut ctx) };
+    assert_eq!(status, R0Status::InvalidArgument);
+    assert!(last_error().unwrap().contains("release 0.1"));
+}
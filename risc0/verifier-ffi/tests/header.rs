@@ -0,0 +1,38 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+// The header is checked in so that it can be used without a Rust toolchain. Set
+// RISC0_UPDATE_HEADER to regenerate it after changing the exported API.
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::generate_with_config(crate_dir, config)
+        .unwrap()
+        .write(&mut generated);
+
+    let header = crate_dir.join("include/risc0_verifier.h");
+    if std::env::var_os("RISC0_UPDATE_HEADER").is_some() {
+        fs::write(&header, &generated).unwrap();
+    }
+    assert!(
+        fs::read(&header).unwrap() == generated,
+        "{} is out of date; regenerate it with \
+         `RISC0_UPDATE_HEADER=1 cargo test -p risc0-verifier-ffi --test header`",
+        header.display()
+    );
+}
@@ -71,6 +71,11 @@ pub enum VerificationError {
     UnresolvedAssumption {
         digest: Digest,
     },
+    ResourceLimitExceeded {
+        resource: &'static str,
+        size: usize,
+        limit: usize,
+    },
 }
 
 impl fmt::Debug for VerificationError {
@@ -115,6 +120,16 @@ impl fmt::Display for VerificationError {
             VerificationError::UnresolvedAssumption { digest } => {
                 write!(f, "receipt contains an unresolved assumption: {digest}")
             }
+            VerificationError::ResourceLimitExceeded {
+                resource,
+                size,
+                limit,
+            } => {
+                write!(
+                    f,
+                    "receipt {resource} of {size} exceeds the verifier limit of {limit}"
+                )
+            }
         }
     }
 }
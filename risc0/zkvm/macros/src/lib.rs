@@ -0,0 +1,164 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{spanned::Spanned, Error, FnArg, ItemFn, ReturnType};
+
+/// Marks a function as the typed entrypoint of a guest.
+///
+/// Each argument of the function is read from the host with `env::read`, in
+/// order, and the returned value is committed to the journal with
+/// `env::commit`. As with the `risc0_zkvm::guest::entry!` macro, the guest must be
+/// built with `#![no_main]`.
+///
+/// When the guest is built with `risc0_build::embed_methods`, a typed
+/// `GuestMethod` constant is generated for it, so that mismatches between the
+/// types used by the host and the guest are compile errors.
+///
+/// # Example
+///
+/// ```ignore
+/// #![no_main]
+/// #![no_std]
+///
+/// #[risc0_zkvm::guest::typed_entry]
+/// fn main(a: u64, b: u64) -> u64 {
+///     a.checked_mul(b).expect("Integer overflow")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn typed_entry(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_entry(attr.into(), item.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_entry(attr: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    if !attr.is_empty() {
+        return Err(Error::new(
+            attr.span(),
+            "typed_entry does not take any arguments",
+        ));
+    }
+
+    let func: ItemFn = syn::parse2(item)?;
+    let sig = &func.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(Error::new_spanned(asyncness, "typed_entry cannot be async"));
+    }
+    if !sig.generics.params.is_empty() || sig.generics.where_clause.is_some() {
+        return Err(Error::new_spanned(
+            &sig.generics,
+            "typed_entry cannot be generic",
+        ));
+    }
+    if let Some(variadic) = &sig.variadic {
+        return Err(Error::new_spanned(
+            variadic,
+            "typed_entry cannot be variadic",
+        ));
+    }
+
+    // Each argument is read from stdin in order, so a host writing the
+    // arguments as a tuple supplies them all.
+    let mut reads = Vec::new();
+    for arg in &sig.inputs {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                return Err(Error::new_spanned(receiver, "typed_entry cannot take self"));
+            }
+            FnArg::Typed(_) => reads.push(quote!(::risc0_zkvm::guest::env::read())),
+        }
+    }
+
+    let ident = &sig.ident;
+    let commit = match &sig.output {
+        ReturnType::Default => quote!(),
+        ReturnType::Type(..) => quote!(::risc0_zkvm::guest::env::commit(&output);),
+    };
+
+    Ok(quote! {
+        #func
+
+        // Include generated main in a module so we don't conflict
+        // with any other definitions of "main" in this file.
+        mod zkvm_generated_main {
+            #[no_mangle]
+            fn main() {
+                #[allow(clippy::let_unit_value)]
+                let output = super::#ident(#(#reads),*);
+                #commit
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::expand_entry;
+
+    #[test]
+    fn reads_each_argument() {
+        let item = quote!(
+            fn main(a: u32, b: Vec<u8>) -> u64 {
+                a as u64
+            }
+        );
+        let expanded = expand_entry(quote!(), item).unwrap().to_string();
+        assert_eq!(expanded.matches("env :: read ()").count(), 2);
+        assert!(expanded.contains("env :: commit (& output)"));
+    }
+
+    #[test]
+    fn unit_return_is_not_committed() {
+        let item = quote!(
+            fn main() {}
+        );
+        let expanded = expand_entry(quote!(), item).unwrap().to_string();
+        assert!(!expanded.contains("env :: read"));
+        assert!(!expanded.contains("env :: commit"));
+    }
+
+    #[test]
+    fn rejects_invalid_signatures() {
+        assert!(expand_entry(
+            quote!(foo),
+            quote!(
+                fn main() {}
+            )
+        )
+        .is_err());
+        assert!(expand_entry(
+            quote!(),
+            quote!(
+                async fn main() {}
+            )
+        )
+        .is_err());
+        assert!(expand_entry(
+            quote!(),
+            quote!(
+                fn main<T>(t: T) {}
+            )
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![no_std]
+#![no_main]
+
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::entry!(main);
+
+// Reads only the first word of its input, relying on the rest being hashed
+// when the guest halts.
+fn main() {
+    env::bind_input();
+    let first: u32 = env::read();
+    env::commit(&first);
+}
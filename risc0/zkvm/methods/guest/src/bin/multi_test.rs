@@ -28,9 +28,14 @@ use core::arch::asm;
 use getrandom::getrandom;
 use risc0_zkp::core::hash::sha::testutil::test_sha_impl;
 use risc0_zkvm::{
-    guest::{env, memory_barrier, sha},
+    guest::{
+        env,
+        fs::{self, SeekFrom},
+        memory_barrier, sha,
+    },
+    serde::Encoding,
     sha::{Digest, Sha256},
-    Assumption, ReceiptClaim,
+    Assumption, JournalTree, ReceiptClaim,
 };
 use risc0_zkvm_methods::multi_test::{MultiTestSpec, SYS_MULTI_TEST, SYS_MULTI_TEST_WORDS};
 use risc0_zkvm_platform::{
@@ -152,6 +157,43 @@ fn main() {
             let host_data = env::send_recv_slice::<u64, u32>(SYS_MULTI_TEST_WORDS, &input);
             assert_eq!(host_data, &[0x05060708, 0x01020304]);
         }
+        MultiTestSpec::HostCall { a, b } => {
+            let sum: u64 = env::host_call(SYS_MULTI_TEST, &(a, b)).unwrap();
+            env::commit(&sum);
+        }
+        MultiTestSpec::MountedFile { path } => {
+            let contents = fs::read(&path).ok();
+            let size = fs::size(&path).ok();
+            let tail = fs::File::open(&path).ok().map(|mut file| {
+                assert_eq!(file.seek(SeekFrom::Start(1)), Some(1));
+                let mut tail = vec![];
+                file.read_to_end(&mut tail);
+                tail
+            });
+            env::commit(&(contents, size, tail));
+        }
+        MultiTestSpec::JournalTree => {
+            let mut tree = JournalTree::new();
+            tree.add("name", "alice").unwrap();
+            tree.add("score", &42u32).unwrap();
+            env::commit_tree(&tree);
+        }
+        MultiTestSpec::CompactEcho => {
+            // The host writes a tuple, which is read and committed one field at a time.
+            env::set_input_serde_encoding(Encoding::V2);
+            env::set_journal_serde_encoding(Encoding::V2);
+            let bytes: Vec<u8> = env::read();
+            let int: i16 = env::read();
+            let flag: bool = env::read();
+            env::commit(&bytes);
+            env::commit(&int);
+            env::commit(&flag);
+        }
+        MultiTestSpec::Preloaded { offsets } => {
+            let data = env::preloaded();
+            let bytes: Vec<u8> = offsets.iter().map(|&i| data[i as usize]).collect();
+            env::commit(&(data.len() as u32, bytes));
+        }
         MultiTestSpec::DoRandom => {
             // Test random number generation in the zkvm
             // Test for a combination of lengths and data alignments to make sure all cases
@@ -253,6 +295,17 @@ fn main() {
             let f = black_box(1.0_f32);
             black_box(f.min(1.0));
         }
+        MultiTestSpec::HeapPastPreload => {
+            use core::hint::black_box;
+            // Without preloaded input, the heap grows over the preload region, and
+            // what the guest writes there must not be mistaken for preloaded data.
+            let start = memory::PRELOAD_INPUT.start();
+            let mut data: Vec<u8> = Vec::with_capacity(start);
+            let offset = start - data.as_ptr() as usize;
+            data.spare_capacity_mut()[offset].write(1);
+            assert!(env::preloaded().is_empty());
+            black_box(vec![0_u8; 16]);
+        }
         MultiTestSpec::Oom => {
             use core::hint::black_box;
             // SYSTEM memory starts above the guest memory so this is guaranteed
@@ -15,7 +15,7 @@
 // Definitions for test selection codes used by the "multi_test" test.
 extern crate alloc;
 
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use risc0_zkvm::{declare_syscall, sha::Digest};
 use risc0_zkvm_platform::syscall::bigint;
@@ -48,7 +48,20 @@ pub enum MultiTestSpec {
         count: u32,
     },
     SyscallWords,
+    HostCall {
+        a: u32,
+        b: u32,
+    },
     DoRandom,
+    MountedFile {
+        path: String,
+    },
+    CompactEcho,
+    Preloaded {
+        // Offsets of the bytes to commit.
+        offsets: Vec<u32>,
+    },
+    JournalTree,
     SysInput(Digest),
     SysRead {
         // Buffer to read to
@@ -86,6 +99,7 @@ pub enum MultiTestSpec {
         /// Busy loop until the guest has run for at least this number of cycles
         cycles: u64,
     },
+    HeapPastPreload,
     LibM,
     Oom,
     OutOfBounds,
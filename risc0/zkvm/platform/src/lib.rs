@@ -41,6 +41,7 @@ pub mod fileno {
     pub const STDOUT: u32 = 1;
     pub const STDERR: u32 = 2;
     pub const JOURNAL: u32 = 3;
+    pub const JOURNAL_FIELDS: u32 = 4;
 }
 
 /// Align address upwards.
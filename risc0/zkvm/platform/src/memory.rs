@@ -12,6 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! The layout of zkVM memory.
+//!
+//! Guest memory spans [GUEST_MIN_MEM] to [GUEST_MAX_MEM]:
+//!
+//! | Address range               | Contents                                              |
+//! | --------------------------- | ----------------------------------------------------- |
+//! | `0x0000_0400 - 0x0020_03FF` | Stack, growing down from [STACK_TOP]                  |
+//! | `0x0020_0400 - 0x0020_0403` | Length of the preloaded input, [PRELOAD_INPUT_LEN]    |
+//! | `0x0020_0800 - ...`         | Program text, data and bss, from [TEXT_START]         |
+//! | `... - 0x09FF_FFFF`         | Heap, from the end of the program                     |
+//! | `0x0A00_0000 - 0x0BFF_FFFF` | [PRELOAD_INPUT], or more heap if nothing is preloaded |
+//!
+//! The preloaded input has its own region inside guest memory, rather than
+//! sharing [PRE_LOAD], because [PRE_LOAD] lies in the page table above
+//! [GUEST_MAX_MEM] where the guest cannot read it. The guest allocator stops
+//! short of [PRELOAD_INPUT] when the host preloaded any input, and is free to
+//! grow over it otherwise.
+
 use super::WORD_SIZE;
 
 pub const MEM_BITS: usize = 28;
@@ -24,10 +42,26 @@ pub const STACK_TOP: u32 = 0x0020_0400;
 /// Program (text followed by data and then bss) gets loaded in
 /// starting at this location.  HEAP begins right afterwards.
 pub const TEXT_START: u32 = 0x0020_0800;
+/// Input data placed in guest memory by the host before execution starts.
+/// When this region is in use, the heap ends where it begins.
+pub const PRELOAD_INPUT: Region = Region::new(0x0A00_0000, mb(32));
+/// Address of the length in bytes of the data in [PRELOAD_INPUT], which is
+/// zero if the host preloaded nothing. It lies between the stack and the
+/// program, so unlike the preload region it is never part of the heap.
+pub const PRELOAD_INPUT_LEN: usize = STACK_TOP as usize;
 pub const SYSTEM: Region = Region::new(0x0C00_0000, mb(16));
 pub const PAGE_TABLE: Region = Region::new(0x0D00_0000, mb(16));
 pub const PRE_LOAD: Region = Region::new(0x0D70_0000, mb(9));
 
+// The preloaded input lies above the program and below system memory, and its
+// length word lies between the stack and the program.
+const _: () =
+    assert!((TEXT_START as usize) < PRELOAD_INPUT.start() && PRELOAD_INPUT.end() <= SYSTEM.start());
+const _: () = assert!(
+    (STACK_TOP as usize) <= PRELOAD_INPUT_LEN
+        && PRELOAD_INPUT_LEN + WORD_SIZE <= TEXT_START as usize
+);
+
 pub struct Region {
     start: usize,
     len_bytes: usize,
@@ -32,6 +32,12 @@ pub mod halt {
     pub const SPLIT: u32 = 2;
 }
 
+pub mod seek {
+    pub const SET: u32 = 0;
+    pub const CUR: u32 = 1;
+    pub const END: u32 = 2;
+}
+
 pub mod reg_abi {
     pub const REG_ZERO: usize = 0; // zero constant
     pub const REG_RA: usize = 1; // return address
@@ -128,12 +134,16 @@ macro_rules! declare_syscall {
 pub mod nr {
     declare_syscall!(pub SYS_ARGC);
     declare_syscall!(pub SYS_ARGV);
+    declare_syscall!(pub SYS_CLOSE);
     declare_syscall!(pub SYS_CYCLE_COUNT);
     declare_syscall!(pub SYS_GETENV);
     declare_syscall!(pub SYS_LOG);
+    declare_syscall!(pub SYS_OPEN);
     declare_syscall!(pub SYS_PANIC);
     declare_syscall!(pub SYS_RANDOM);
     declare_syscall!(pub SYS_READ);
+    declare_syscall!(pub SYS_SEEK);
+    declare_syscall!(pub SYS_STAT);
     declare_syscall!(pub SYS_VERIFY_INTEGRITY);
     declare_syscall!(pub SYS_WRITE);
 }
@@ -593,6 +603,60 @@ pub unsafe extern "C" fn sys_write(fd: u32, write_ptr: *const u8, nbytes: usize)
     }
 }
 
+/// Opens a read-only file mounted by the host at the given path.
+///
+/// Returns a file descriptor that can be used with [sys_read], [sys_seek] and
+/// [sys_close], or u32::MAX if no file is mounted at the path.
+///
+/// # Safety
+///
+/// `path` must be aligned and dereferenceable.
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub unsafe extern "C" fn sys_open(path: *const u8, path_len: usize) -> u32 {
+    let Return(a0, _) = syscall_2(nr::SYS_OPEN, null_mut(), 0, path as u32, path_len as u32);
+    a0
+}
+
+/// Moves the read position of a file opened with [sys_open], posix-style.
+///
+/// `whence` is one of the constants in [seek]. Returns the new position from
+/// the start of the file, or u64::MAX if the resulting position would be
+/// negative.
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub extern "C" fn sys_seek(fd: u32, offset: i64, whence: u32) -> u64 {
+    let offset = offset as u64;
+    let Return(hi, lo) = unsafe {
+        syscall_4(
+            nr::SYS_SEEK,
+            null_mut(),
+            0,
+            fd,
+            offset as u32,
+            (offset >> 32) as u32,
+            whence,
+        )
+    };
+    ((hi as u64) << 32) + lo as u64
+}
+
+/// Returns the size, in bytes, of the file mounted by the host at the given
+/// path, or u64::MAX if no file is mounted at the path.
+///
+/// # Safety
+///
+/// `path` must be aligned and dereferenceable.
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub unsafe extern "C" fn sys_stat(path: *const u8, path_len: usize) -> u64 {
+    let Return(hi, lo) = syscall_2(nr::SYS_STAT, null_mut(), 0, path as u32, path_len as u32);
+    ((hi as u64) << 32) + lo as u64
+}
+
+/// Closes a file descriptor returned by [sys_open].
+#[cfg_attr(feature = "export-syscalls", no_mangle)]
+pub extern "C" fn sys_close(fd: u32) {
+    unsafe { syscall_1(nr::SYS_CLOSE, null_mut(), 0, fd) };
+}
+
 /// Retrieves the value of an environment variable, and stores as much
 /// of it as it can it in the memory at [out_words, out_words +
 /// out_nwords).
@@ -714,8 +778,13 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let ptr = heap_pos as *mut u8;
     heap_pos += bytes;
 
-    // Check to make sure heap doesn't collide with SYSTEM memory.
-    if crate::memory::SYSTEM.start() < heap_pos {
+    // Check to make sure heap doesn't collide with SYSTEM memory, or with
+    // preloaded input if the host provided any.
+    if crate::memory::SYSTEM.start() < heap_pos
+        || (cfg!(target_os = "zkvm")
+            && crate::memory::PRELOAD_INPUT.start() < heap_pos
+            && unsafe { (crate::memory::PRELOAD_INPUT_LEN as *const u32).read_volatile() } != 0)
+    {
         const MSG: &[u8] = "Out of memory!".as_bytes();
         unsafe { sys_panic(MSG.as_ptr(), MSG.len()) };
     }
@@ -0,0 +1,632 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ethereum ABI encoding for journals.
+//!
+//! Contracts that consume a journal usually decode it with Solidity's
+//! `abi.decode`. This module implements the [contract ABI encoding] for common
+//! Solidity types, so a guest can commit values with
+//! [env::commit_abi][crate::guest::env::commit_abi] and a host can read them
+//! back with [Journal::decode_abi][crate::Journal::decode_abi].
+//!
+//! Rust types map to Solidity types as follows:
+//!
+//! | Rust                           | Solidity                      |
+//! | ------------------------------ | ----------------------------- |
+//! | `bool`                         | `bool`                        |
+//! | `u8`, `u16`, ..., `u128`       | `uint8`, `uint16`, ..., `uint128` |
+//! | `i8`, `i16`, ..., `i128`       | `int8`, `int16`, ..., `int128`    |
+//! | [U256]                         | `uint256`                     |
+//! | [Address]                      | `address`                     |
+//! | [FixedBytes<N>], [Digest]      | `bytesN`, `bytes32`           |
+//! | [Bytes]                        | `bytes`                       |
+//! | `String`                       | `string`                      |
+//! | `Vec<T>`                       | `T[]`                         |
+//! | `[T; N]`                       | `T[N]`                        |
+//! | `(A, B, ...)`                  | `(A, B, ...)`                 |
+//!
+//! A tuple passed to [encode] is encoded as a parameter list, matching
+//! `abi.encode(a, b, ...)` and `abi.decode(data, (A, B, ...))`. Any other value
+//! is encoded as a single parameter.
+//!
+//! # Example
+//!
+//! ```rust
+//! use risc0_zkvm::abi::{self, Bytes, U256};
+//!
+//! let value = (U256::from(42u128), Bytes(b"hello".to_vec()));
+//! let encoded = abi::encode(&value);
+//! assert_eq!(encoded.len(), 4 * 32);
+//! let decoded: (U256, Bytes) = abi::decode(&encoded).unwrap();
+//! assert_eq!(value, decoded);
+//! ```
+//!
+//! [contract ABI encoding]: https://docs.soliditylang.org/en/latest/abi-spec.html
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::sha::Digest;
+
+const WORD: usize = 32;
+
+/// Errors from decoding ABI-encoded data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The data ended before the value was fully decoded.
+    UnexpectedEnd,
+    /// An offset or length points outside of the data or does not fit in a
+    /// `usize`.
+    InvalidOffset,
+    /// A word does not hold a valid value of the expected type, e.g. a `bool`
+    /// other than 0 or 1, or a `uint8` with high bits set.
+    InvalidValue,
+    /// A `string` is not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnexpectedEnd => "unexpected end of ABI-encoded data",
+            Self::InvalidOffset => "invalid offset or length in ABI-encoded data",
+            Self::InvalidValue => "invalid value in ABI-encoded data",
+            Self::InvalidUtf8 => "invalid UTF-8 string in ABI-encoded data",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A specialized [Result](core::result::Result) for ABI decoding.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// A type with a Solidity ABI encoding.
+pub trait AbiType: Sized {
+    /// True if the encoded length of this type depends on its value. Dynamic
+    /// types are encoded out of line and referenced by an offset.
+    const DYNAMIC: bool;
+
+    /// The number of bytes this type occupies in the head of an enclosing
+    /// tuple: its encoded length if static, or one word for the offset if
+    /// dynamic.
+    const HEAD_LEN: usize;
+
+    /// Appends the encoding of this value to `out`.
+    fn encode_to(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value whose encoding starts at the beginning of `data`.
+    /// Trailing bytes are ignored.
+    fn decode_from(data: &[u8]) -> Result<Self>;
+
+    /// Appends the encoding of this value as a parameter list to `out`.
+    ///
+    /// A tuple is its own parameter list, anything else is a list of one.
+    #[doc(hidden)]
+    fn encode_params(&self, out: &mut Vec<u8>) {
+        let mut encoder = TupleEncoder::new(out, Self::HEAD_LEN);
+        encoder.push(self);
+        encoder.finish();
+    }
+
+    /// Decodes a parameter list, as encoded by [AbiType::encode_params].
+    #[doc(hidden)]
+    fn decode_params(data: &[u8]) -> Result<Self> {
+        TupleDecoder::new(data).next()
+    }
+}
+
+/// Encode `value` as in Solidity's `abi.encode`.
+pub fn encode<T: AbiType>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.encode_params(&mut out);
+    out
+}
+
+/// Decode `data` as in Solidity's `abi.decode`.
+pub fn decode<T: AbiType>(data: &[u8]) -> Result<T> {
+    T::decode_params(data)
+}
+
+/// Writes the heads of a tuple in place, and collects the tails of its dynamic
+/// members to append at the end.
+struct TupleEncoder<'a> {
+    out: &'a mut Vec<u8>,
+    head_len: usize,
+    tail: Vec<u8>,
+}
+
+impl<'a> TupleEncoder<'a> {
+    fn new(out: &'a mut Vec<u8>, head_len: usize) -> Self {
+        Self {
+            out,
+            head_len,
+            tail: Vec::new(),
+        }
+    }
+
+    fn push<T: AbiType>(&mut self, value: &T) {
+        if T::DYNAMIC {
+            encode_usize(self.head_len + self.tail.len(), self.out);
+            value.encode_to(&mut self.tail);
+        } else {
+            value.encode_to(self.out);
+        }
+    }
+
+    fn finish(self) {
+        self.out.extend_from_slice(&self.tail);
+    }
+}
+
+/// Reads the members of a tuple, following offsets to dynamic members.
+struct TupleDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TupleDecoder<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn next<T: AbiType>(&mut self) -> Result<T> {
+        let head = self.data.get(self.pos..).ok_or(Error::UnexpectedEnd)?;
+        self.pos += T::HEAD_LEN;
+        if T::DYNAMIC {
+            let offset = decode_usize(head)?;
+            T::decode_from(self.data.get(offset..).ok_or(Error::InvalidOffset)?)
+        } else {
+            T::decode_from(head)
+        }
+    }
+}
+
+fn read_word(data: &[u8]) -> Result<&[u8; WORD]> {
+    data.get(..WORD)
+        .ok_or(Error::UnexpectedEnd)?
+        .try_into()
+        .map_err(|_| Error::UnexpectedEnd)
+}
+
+fn encode_usize(value: usize, out: &mut Vec<u8>) {
+    (value as u64).encode_to(out)
+}
+
+fn decode_usize(data: &[u8]) -> Result<usize> {
+    let value = u64::decode_from(data).map_err(|_| Error::InvalidOffset)?;
+    usize::try_from(value).map_err(|_| Error::InvalidOffset)
+}
+
+/// Appends `bytes` followed by zero padding up to a word boundary.
+fn encode_padded(bytes: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + (WORD - bytes.len() % WORD) % WORD, 0);
+}
+
+/// Reads a length-prefixed, padded byte string.
+fn decode_bytes(data: &[u8]) -> Result<&[u8]> {
+    let len = decode_usize(data)?;
+    let end = WORD.checked_add(len).ok_or(Error::InvalidOffset)?;
+    data.get(WORD..end).ok_or(Error::UnexpectedEnd)
+}
+
+impl AbiType for bool {
+    const DYNAMIC: bool = false;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        (*self as u8).encode_to(out)
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        match u8::decode_from(data)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidValue),
+        }
+    }
+}
+
+/// Returns the padding byte for an unsigned integer.
+fn zero_fill(_msb: u8) -> u8 {
+    0
+}
+
+/// Returns the padding byte that sign-extends an integer whose most
+/// significant byte is `msb`.
+fn sign_fill(msb: u8) -> u8 {
+    if msb & 0x80 != 0 {
+        0xff
+    } else {
+        0
+    }
+}
+
+macro_rules! impl_abi_int {
+    ($fill:ident; $($ty:ty),*) => {$(
+        impl AbiType for $ty {
+            const DYNAMIC: bool = false;
+            const HEAD_LEN: usize = WORD;
+
+            fn encode_to(&self, out: &mut Vec<u8>) {
+                let bytes = self.to_be_bytes();
+                out.resize(out.len() + WORD - bytes.len(), $fill(bytes[0]));
+                out.extend_from_slice(&bytes);
+            }
+
+            fn decode_from(data: &[u8]) -> Result<Self> {
+                const LEN: usize = core::mem::size_of::<$ty>();
+                let word = read_word(data)?;
+                let (pad, bytes) = word.split_at(WORD - LEN);
+                if pad.iter().any(|&b| b != $fill(bytes[0])) {
+                    return Err(Error::InvalidValue);
+                }
+                Ok(<$ty>::from_be_bytes(bytes.try_into().unwrap()))
+            }
+        }
+    )*};
+}
+
+impl_abi_int!(zero_fill; u8, u16, u32, u64, u128);
+impl_abi_int!(sign_fill; i8, i16, i32, i64, i128);
+
+/// A Solidity `uint256`, stored as big-endian bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(pub [u8; 32]);
+
+impl U256 {
+    /// Returns the big-endian bytes of this integer.
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Creates an integer from its big-endian bytes.
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[16..].copy_from_slice(&value.to_be_bytes());
+        Self(bytes)
+    }
+}
+
+impl AbiType for U256 {
+    const DYNAMIC: bool = false;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.0)
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        Ok(Self(*read_word(data)?))
+    }
+}
+
+/// A Solidity `address`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl AbiType for Address {
+    const DYNAMIC: bool = false;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&[0u8; 12]);
+        out.extend_from_slice(&self.0);
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        let word = read_word(data)?;
+        if word[..12].iter().any(|&b| b != 0) {
+            return Err(Error::InvalidValue);
+        }
+        Ok(Self(word[12..].try_into().unwrap()))
+    }
+}
+
+/// A Solidity `bytesN`, for `N` from 1 to 32.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+/// A Solidity `bytes32`.
+pub type Bytes32 = FixedBytes<32>;
+
+impl<const N: usize> AbiType for FixedBytes<N> {
+    const DYNAMIC: bool = false;
+    const HEAD_LEN: usize = {
+        assert!(N > 0 && N <= WORD, "bytesN must have 1 to 32 bytes");
+        WORD
+    };
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        encode_padded(&self.0, out)
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        let word = read_word(data)?;
+        if word[N..].iter().any(|&b| b != 0) {
+            return Err(Error::InvalidValue);
+        }
+        Ok(Self(word[..N].try_into().unwrap()))
+    }
+}
+
+impl AbiType for Digest {
+    const DYNAMIC: bool = false;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes())
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        Ok(Digest::from(*read_word(data)?))
+    }
+}
+
+/// A Solidity `bytes`.
+///
+/// A plain `Vec<u8>` is encoded as `uint8[]`, so byte strings must be wrapped
+/// in this type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl AbiType for Bytes {
+    const DYNAMIC: bool = true;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        encode_usize(self.0.len(), out);
+        encode_padded(&self.0, out);
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        Ok(Self(decode_bytes(data)?.to_vec()))
+    }
+}
+
+impl AbiType for String {
+    const DYNAMIC: bool = true;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        encode_usize(self.len(), out);
+        encode_padded(self.as_bytes(), out);
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        let bytes = decode_bytes(data)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+impl<T: AbiType> AbiType for Vec<T> {
+    const DYNAMIC: bool = true;
+    const HEAD_LEN: usize = WORD;
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        encode_usize(self.len(), out);
+        let mut encoder = TupleEncoder::new(out, self.len() * T::HEAD_LEN);
+        for item in self {
+            encoder.push(item);
+        }
+        encoder.finish();
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        let len = decode_usize(data)?;
+        // Every element takes at least one word, which bounds the allocation.
+        if len > data.len() / WORD {
+            return Err(Error::InvalidOffset);
+        }
+        let mut decoder = TupleDecoder::new(&data[WORD..]);
+        (0..len).map(|_| decoder.next()).collect()
+    }
+}
+
+impl<T: AbiType, const N: usize> AbiType for [T; N] {
+    const DYNAMIC: bool = T::DYNAMIC;
+    const HEAD_LEN: usize = if T::DYNAMIC { WORD } else { N * T::HEAD_LEN };
+
+    fn encode_to(&self, out: &mut Vec<u8>) {
+        let mut encoder = TupleEncoder::new(out, N * T::HEAD_LEN);
+        for item in self {
+            encoder.push(item);
+        }
+        encoder.finish();
+    }
+
+    fn decode_from(data: &[u8]) -> Result<Self> {
+        let mut decoder = TupleDecoder::new(data);
+        let items = (0..N).map(|_| decoder.next()).collect::<Result<Vec<T>>>()?;
+        Ok(items.try_into().ok().unwrap())
+    }
+}
+
+macro_rules! impl_abi_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: AbiType),+> AbiType for ($($name,)+) {
+            const DYNAMIC: bool = $($name::DYNAMIC)||+;
+            const HEAD_LEN: usize = if Self::DYNAMIC { WORD } else { 0 $(+ $name::HEAD_LEN)+ };
+
+            #[allow(non_snake_case)]
+            fn encode_to(&self, out: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                let mut encoder = TupleEncoder::new(out, 0 $(+ $name::HEAD_LEN)+);
+                $(encoder.push($name);)+
+                encoder.finish();
+            }
+
+            fn decode_from(data: &[u8]) -> Result<Self> {
+                let mut decoder = TupleDecoder::new(data);
+                Ok(($(decoder.next::<$name>()?,)+))
+            }
+
+            fn encode_params(&self, out: &mut Vec<u8>) {
+                self.encode_to(out)
+            }
+
+            fn decode_params(data: &[u8]) -> Result<Self> {
+                Self::decode_from(data)
+            }
+        }
+    };
+}
+
+impl_abi_tuple!(A);
+impl_abi_tuple!(A B);
+impl_abi_tuple!(A B C);
+impl_abi_tuple!(A B C D);
+impl_abi_tuple!(A B C D E);
+impl_abi_tuple!(A B C D E F);
+impl_abi_tuple!(A B C D E F G);
+impl_abi_tuple!(A B C D E F G H);
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+
+    use super::*;
+
+    fn words(words: &[&str]) -> Vec<u8> {
+        words.iter().flat_map(|w| hex::decode(w).unwrap()).collect()
+    }
+
+    fn round_trip<T: AbiType + PartialEq + fmt::Debug>(value: T, expected: &[u8]) {
+        let encoded = encode(&value);
+        assert_eq!(hex::encode(&encoded), hex::encode(expected));
+        assert_eq!(decode::<T>(&encoded).unwrap(), value);
+    }
+
+    // Example from the Solidity ABI specification:
+    // abi.encode(0x123, [0x456, 0x789], bytes10("1234567890"), bytes("Hello, world!"))
+    #[test]
+    fn spec_example_static_and_dynamic() {
+        let value = (
+            U256::from(0x123u128),
+            vec![0x456u32, 0x789],
+            FixedBytes(*b"1234567890"),
+            Bytes(b"Hello, world!".to_vec()),
+        );
+        let expected = words(&[
+            "0000000000000000000000000000000000000000000000000000000000000123",
+            "0000000000000000000000000000000000000000000000000000000000000080",
+            "3132333435363738393000000000000000000000000000000000000000000000",
+            "00000000000000000000000000000000000000000000000000000000000000e0",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000456",
+            "0000000000000000000000000000000000000000000000000000000000000789",
+            "000000000000000000000000000000000000000000000000000000000000000d",
+            "48656c6c6f2c20776f726c642100000000000000000000000000000000000000",
+        ]);
+        round_trip(value, &expected);
+    }
+
+    // Example from the Solidity ABI specification:
+    // abi.encode([[1, 2], [3]], ["one", "two", "three"])
+    #[test]
+    fn spec_example_nested_dynamic() {
+        let value = (
+            vec![vec![1u8, 2], vec![3]],
+            vec!["one".to_string(), "two".to_string(), "three".to_string()],
+        );
+        let expected = words(&[
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000140",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "0000000000000000000000000000000000000000000000000000000000000060",
+            "00000000000000000000000000000000000000000000000000000000000000a0",
+            "00000000000000000000000000000000000000000000000000000000000000e0",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "6f6e650000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "74776f0000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000005",
+            "7468726565000000000000000000000000000000000000000000000000000000",
+        ]);
+        round_trip(value, &expected);
+    }
+
+    #[test]
+    fn single_values() {
+        // A single dynamic value is encoded as a parameter list of one.
+        round_trip(
+            Bytes(b"abc".to_vec()),
+            &words(&[
+                "0000000000000000000000000000000000000000000000000000000000000020",
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "6162630000000000000000000000000000000000000000000000000000000000",
+            ]),
+        );
+        round_trip(
+            -1i8,
+            &words(&["ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"]),
+        );
+        round_trip(
+            true,
+            &words(&["0000000000000000000000000000000000000000000000000000000000000001"]),
+        );
+        round_trip(
+            Address([0x11; 20]),
+            &words(&["0000000000000000000000001111111111111111111111111111111111111111"]),
+        );
+        let digest = Digest::from([0xab; 32]);
+        round_trip(digest, &words(&[&hex::encode([0xab; 32])]));
+    }
+
+    #[test]
+    fn static_tuple_and_array() {
+        round_trip(
+            (Address([0x22; 20]), [1u64, 2], false),
+            &words(&[
+                "0000000000000000000000002222222222222222222222222222222222222222",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ]),
+        );
+    }
+
+    #[test]
+    fn invalid_data() {
+        let two = words(&["0000000000000000000000000000000000000000000000000000000000000002"]);
+        assert_eq!(decode::<bool>(&two), Err(Error::InvalidValue));
+        let big = words(&["0000000000000000000000000000000000000000000000000000000000000100"]);
+        assert_eq!(decode::<u8>(&big), Err(Error::InvalidValue));
+        assert_eq!(decode::<U256>(&two[..31]), Err(Error::UnexpectedEnd));
+        let far = words(&["0000000000000000000000000000000000000000000000000000000000001000"]);
+        assert_eq!(decode::<Bytes>(&far), Err(Error::InvalidOffset));
+        let huge_len = words(&[
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "00000000000000000000000000000000000000000000000000000000ffffffff",
+        ]);
+        assert_eq!(decode::<Vec<u8>>(&huge_len), Err(Error::InvalidOffset));
+    }
+}
@@ -49,6 +49,9 @@
 //! provided. See [read], [write][write()], [commit] (and their `_slice`
 //! variants) for more information.
 //!
+//! To call a typed function provided by the host, see [host_call]. Large inputs can be read
+//! without copying with [preloaded].
+//!
 //! In order to access default file descriptors directly, see [stdin], [stdout],
 //! [stderr] and [journal]. These file descriptors are either [FdReader] or
 //! [FdWriter] instances, which can be used to read from or write to the host.
@@ -68,26 +71,31 @@
 //! [proof composition]:https://www.risczero.com/blog/proof-composition
 //! [guest-optimization]: https://dev.risczero.com/api/zkvm/optimization#when-reading-data-as-raw-bytes-use-envread_slice
 
-use core::{cell::OnceCell, convert::Infallible, fmt};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{
+    cell::{OnceCell, RefCell},
+    convert::Infallible,
+    fmt,
+};
 
 use bytemuck::Pod;
 use risc0_zkvm_platform::{
-    align_up, fileno,
+    align_up, fileno, memory,
     syscall::{
         self, sys_alloc_words, sys_cycle_count, sys_halt, sys_input, sys_log, sys_pause, sys_read,
         sys_read_words, sys_verify_integrity, sys_write, syscall_2, SyscallName,
     },
     WORD_SIZE,
 };
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{
-    serde::{Deserializer, Serializer, WordRead, WordWrite},
+    serde::{Deserializer, Encoding, Lane, Serializer, WordRead, WordWrite},
     sha::{
         rust_crypto::{Digest as _, Sha256},
         Digest, Digestible,
     },
-    Assumption, Assumptions, MaybePruned, Output, PrunedValueError, ReceiptClaim,
+    Assumption, Assumptions, JournalTree, MaybePruned, Output, PrunedValueError, ReceiptClaim,
 };
 
 static mut HASHER: OnceCell<Sha256> = OnceCell::new();
@@ -102,6 +110,43 @@ static mut ASSUMPTIONS_DIGEST: MaybePruned<Assumptions> = MaybePruned::Pruned(Di
 /// information leakage through the post-state digest.
 static mut MEMORY_IMAGE_ENTROPY: [u32; 4] = [0u32; 4];
 
+/// Running hash of the bytes read from stdin, present once [bind_input] is called.
+static mut INPUT_HASHER: Option<Sha256> = None;
+
+/// Serde state of the streams read with [Read::read], by file descriptor.
+static mut READ_STREAMS: RefCell<BTreeMap<u32, Stream>> = RefCell::new(BTreeMap::new());
+
+/// Serde state of the streams written with [Write::write], by file descriptor.
+static mut WRITE_STREAMS: RefCell<BTreeMap<u32, Stream>> = RefCell::new(BTreeMap::new());
+
+/// Whether anything has been written to the journal.
+static mut JOURNAL_WRITTEN: bool = false;
+
+/// The [Encoding] of a stream, and the small values packed with [Encoding::V2]
+/// that are carried over from one value to the next, so that values read or
+/// written one at a time are encoded the same as a single tuple.
+#[derive(Clone, Copy, Default)]
+struct Stream {
+    encoding: Encoding,
+    lane: Lane,
+}
+
+fn read_stream(fd: u32) -> Stream {
+    unsafe { READ_STREAMS.borrow().get(&fd).copied().unwrap_or_default() }
+}
+
+fn set_read_stream(fd: u32, stream: Stream) {
+    unsafe { READ_STREAMS.borrow_mut().insert(fd, stream) };
+}
+
+fn write_stream(fd: u32) -> Stream {
+    unsafe { WRITE_STREAMS.borrow().get(&fd).copied().unwrap_or_default() }
+}
+
+fn set_write_stream(fd: u32, stream: Stream) {
+    unsafe { WRITE_STREAMS.borrow_mut().insert(fd, stream) };
+}
+
 pub(crate) fn init() {
     unsafe {
         HASHER.set(Sha256::new()).unwrap();
@@ -113,6 +158,19 @@ pub(crate) fn init() {
 }
 
 pub(crate) fn finalize(halt: bool, user_exit: u8) {
+    if halt {
+        verify_input();
+    }
+
+    // Write the small values still waiting to be packed with later ones.
+    let fds: Vec<u32> = unsafe { WRITE_STREAMS.borrow().keys().copied().collect() };
+    for fd in fds {
+        match fd {
+            fileno::JOURNAL => journal().flush_lane(),
+            _ => FdWriter::new(fd, |_| {}).flush_lane(),
+        }
+    }
+
     unsafe {
         let hasher = HASHER.take();
         let journal_digest: Digest = hasher.unwrap().finalize().as_slice().try_into().unwrap();
@@ -329,6 +387,74 @@ pub fn send_recv_slice<T: Pod, U: Pod>(syscall_name: SyscallName, to_host: &[T])
     &bytemuck::cast_slice(from_host_buf)[..nbytes as usize / core::mem::size_of::<U>()]
 }
 
+/// Calls a typed host function registered with `ExecutorEnvBuilder::host_fn`.
+///
+/// The request is serialized with [crate::serde] and sent to the host over the given channel,
+/// and the host's response is deserialized as `Resp`. Returns an error if the response cannot
+/// be decoded as `Resp`. If no handler is registered on the host for `channel`, execution fails
+/// with an unknown syscall error.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{declare_syscall, guest::env};
+///
+/// declare_syscall!(SYS_ADD);
+///
+/// let sum: u64 = env::host_call(SYS_ADD, &(1u32, 2u32)).unwrap();
+/// ```
+pub fn host_call<Req: Serialize, Resp: DeserializeOwned>(
+    channel: SyscallName,
+    req: &Req,
+) -> Result<Resp, crate::serde::Error> {
+    let to_host = crate::serde::to_vec(req)?;
+    let from_host = send_recv_slice::<u32, u32>(channel, &to_host);
+    crate::serde::from_slice(from_host)
+}
+
+/// Set the [Encoding] used by [read] to deserialize data from STDIN.
+///
+/// The default is [Encoding::V1]. Data written by the host with
+/// `ExecutorEnvBuilder::serde_encoding` set must be read with the same
+/// encoding. [read_borrowed] always uses [Encoding::V1].
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{guest::env, serde::Encoding};
+///
+/// env::set_input_serde_encoding(Encoding::V2);
+/// let input: Vec<u8> = env::read();
+/// ```
+pub fn set_input_serde_encoding(encoding: Encoding) {
+    stdin().set_serde_encoding(encoding);
+}
+
+/// Set the [Encoding] used by [commit] to serialize data to the journal.
+///
+/// The default is [Encoding::V1]. This must be called before anything is
+/// committed, so that the whole journal uses one encoding. The encoding is not
+/// recorded in the journal: on the host, decode a journal committed with
+/// [Encoding::V2] with `Journal::decode_with_encoding`.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{guest::env, serde::Encoding};
+///
+/// env::set_journal_serde_encoding(Encoding::V2);
+/// env::commit(&vec![1u8, 2, 3, 4]);
+/// ```
+pub fn set_journal_serde_encoding(encoding: Encoding) {
+    if write_stream(fileno::JOURNAL).encoding == encoding {
+        return;
+    }
+    if unsafe { JOURNAL_WRITTEN } {
+        panic!("the journal encoding must be set before anything is committed");
+    }
+    journal().set_serde_encoding(encoding);
+}
+
 /// Read private data from the STDIN of the zkVM and deserializes it.
 ///
 /// This function operates on every [`DeserializeOwned`] type, so you can
@@ -354,6 +480,70 @@ pub fn read<T: DeserializeOwned>() -> T {
     stdin().read()
 }
 
+/// Read all remaining data from the STDIN of the zkVM and deserialize it in place.
+///
+/// Unlike [read], this function reads the input once into a buffer that lives for the rest of
+/// the guest's execution and deserializes from that buffer, so strings and byte buffers in `T`
+/// can borrow from it instead of being copied. This halves memory use and saves cycles when
+/// parsing large inputs. All remaining STDIN data is consumed, so this should be the only read
+/// from STDIN.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let (name, data): (&str, Vec<u32>) = env::read_borrowed();
+/// ```
+pub fn read_borrowed<T: Deserialize<'static>>() -> T {
+    const CHUNK_WORDS: usize = 1024;
+
+    let mut words: Vec<u32> = Vec::new();
+    loop {
+        let len = words.len();
+        words.resize(len + CHUNK_WORDS, 0);
+        let nread_bytes =
+            unsafe { sys_read_words(fileno::STDIN, words[len..].as_mut_ptr(), CHUNK_WORDS) };
+        hash_input(
+            fileno::STDIN,
+            &bytemuck::cast_slice(&words[len..])[..nread_bytes],
+        );
+        words.truncate(len + align_up(nread_bytes, WORD_SIZE) / WORD_SIZE);
+        if nread_bytes < CHUNK_WORDS * WORD_SIZE {
+            break;
+        }
+    }
+
+    crate::serde::from_slice_borrowed(words.leak()).unwrap()
+}
+
+/// Returns the input data preloaded into guest memory with `ExecutorEnvBuilder::preload`.
+///
+/// Unlike reading from STDIN, this does not copy the data through syscalls. The data is part of
+/// the initial memory image, so the guest pays only for the pages it touches and the data is
+/// committed to by the image ID of the receipt. If the host preloaded nothing, the slice is empty.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// let data: &[u8] = env::preloaded();
+/// let checksum = data.iter().fold(0u8, |acc, b| acc ^ b);
+/// ```
+pub fn preloaded() -> &'static [u8] {
+    // SAFETY: The length is set by the host in the memory image, outside of the heap and stack.
+    let len = unsafe { (memory::PRELOAD_INPUT_LEN as *const u32).read_volatile() } as usize;
+    // SAFETY: The slice lies within the preload region, which the heap does not extend into when
+    // the length is non-zero, so the guest never writes to it.
+    unsafe {
+        core::slice::from_raw_parts(
+            memory::PRELOAD_INPUT.start() as *const u8,
+            len.min(memory::PRELOAD_INPUT.len_bytes()),
+        )
+    }
+}
+
 /// Read a slice from the STDIN of the zkVM.
 ///
 /// This function reads a slice of [plain old data][bytemuck::Pod], not
@@ -504,6 +694,52 @@ pub fn commit_slice<T: Pod>(slice: &[T]) {
     journal().write_slice(slice);
 }
 
+/// Commit a [JournalTree] to the journal as its Merkle root.
+///
+/// Only the 32-byte root is written to the journal. The fields are sent to the
+/// host privately, where they can be captured with
+/// `ExecutorEnvBuilder::journal_fields` and selectively disclosed with
+/// [JournalTree::disclose]. The root should be the only data committed to the
+/// journal, so this should be called once, instead of [commit] or
+/// [commit_slice].
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{guest::env, JournalTree};
+///
+/// let mut tree = JournalTree::new();
+/// tree.add("name", &"alice").unwrap();
+/// tree.add("score", &42u32).unwrap();
+/// env::commit_tree(&tree);
+/// ```
+pub fn commit_tree(tree: &JournalTree) {
+    let words = crate::serde::to_vec(tree).unwrap();
+    FdWriter::new(fileno::JOURNAL_FIELDS, |_| {}).write_slice(&words);
+    commit_slice(tree.root().as_bytes());
+}
+
+/// Commit public data to the journal using the Ethereum [ABI encoding](crate::abi).
+///
+/// The committed bytes are those of Solidity's `abi.encode`, so a contract can
+/// decode the journal with `abi.decode`. A tuple is committed as a parameter
+/// list. Calling this more than once appends the encodings one after another,
+/// which is generally not what a contract expects, so commit all values as a
+/// single tuple.
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::{abi::{Address, U256}, guest::env};
+///
+/// // Decoded on-chain with `abi.decode(journal, (address, uint256))`.
+/// env::commit_abi(&(Address([0u8; 20]), U256::from(100u128)));
+/// ```
+#[cfg(feature = "abi")]
+pub fn commit_abi<T: crate::abi::AbiType>(data: &T) {
+    commit_slice(&crate::abi::encode(data));
+}
+
 /// Return the number of processor cycles that have occurred since the guest
 /// began.
 ///
@@ -532,8 +768,9 @@ pub fn stderr() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
 
 /// Return a writer for the JOURNAL.
 pub fn journal() -> FdWriter<impl for<'a> Fn(&'a [u8])> {
-    FdWriter::new(fileno::JOURNAL, |bytes| {
-        unsafe { HASHER.get_mut().unwrap_unchecked().update(bytes) };
+    FdWriter::new(fileno::JOURNAL, |bytes| unsafe {
+        JOURNAL_WRITTEN = true;
+        HASHER.get_mut().unwrap_unchecked().update(bytes)
     })
 }
 
@@ -572,9 +809,41 @@ impl FdReader {
         FdReader { fd }
     }
 
+    /// Set the [Encoding] used by [Read::read] on this file descriptor.
+    ///
+    /// The encoding belongs to the file descriptor, so it also applies to
+    /// other readers of it, such as those returned by [stdin].
+    pub fn set_serde_encoding(&mut self, encoding: Encoding) {
+        if read_stream(self.fd).encoding != encoding {
+            set_read_stream(
+                self.fd,
+                Stream {
+                    encoding,
+                    lane: Lane::default(),
+                },
+            );
+        }
+    }
+
+    // Raw reads start at the next word, after any packed small values.
+    fn discard_lane(&mut self) {
+        let stream = read_stream(self.fd);
+        if stream.lane.len != 0 {
+            set_read_stream(
+                self.fd,
+                Stream {
+                    lane: Lane::default(),
+                    ..stream
+                },
+            );
+        }
+    }
+
     #[must_use = "read_bytes can potentially do a short read; this case should be handled."]
     fn read_bytes(&mut self, buf: &mut [u8]) -> usize {
-        unsafe { sys_read(self.fd, buf.as_mut_ptr(), buf.len()) }
+        let nread = unsafe { sys_read(self.fd, buf.as_mut_ptr(), buf.len()) };
+        hash_input(self.fd, &buf[..nread]);
+        nread
     }
 
     // Like read_bytes, but fills the buffer completely or until EOF occurs.
@@ -596,10 +865,16 @@ impl FdReader {
 
 impl Read for FdReader {
     fn read<T: DeserializeOwned>(&mut self) -> T {
-        T::deserialize(&mut Deserializer::new(self)).unwrap()
+        let stream = read_stream(self.fd);
+        let mut deserializer = Deserializer::resume(&mut *self, stream.encoding, stream.lane);
+        let value = T::deserialize(&mut deserializer).unwrap();
+        let lane = deserializer.suspend();
+        set_read_stream(self.fd, Stream { lane, ..stream });
+        value
     }
 
     fn read_slice<T: Pod>(&mut self, buf: &mut [T]) {
+        self.discard_lane();
         if let Ok(words) = bytemuck::try_cast_slice_mut(buf) {
             // Reading words performs significantly better if we're word aligned.
             self.read_words(words).unwrap();
@@ -615,6 +890,7 @@ impl Read for FdReader {
 impl WordRead for FdReader {
     fn read_words(&mut self, words: &mut [u32]) -> crate::serde::Result<()> {
         let nread_bytes = unsafe { sys_read_words(self.fd, words.as_mut_ptr(), words.len()) };
+        hash_input(self.fd, &bytemuck::cast_slice(words)[..nread_bytes]);
         if nread_bytes == words.len() * WORD_SIZE {
             Ok(())
         } else {
@@ -642,6 +918,7 @@ impl WordRead for FdReader {
 #[cfg(feature = "std")]
 impl std::io::Read for FdReader {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.discard_lane();
         Ok(self.read_bytes(buf))
     }
 }
@@ -677,6 +954,39 @@ impl<F: Fn(&[u8])> FdWriter<F> {
         FdWriter { fd, hook }
     }
 
+    /// Set the [Encoding] used by [Write::write] on this file descriptor.
+    ///
+    /// The encoding belongs to the file descriptor, so it also applies to
+    /// other writers of it, such as those returned by [journal].
+    pub fn set_serde_encoding(&mut self, encoding: Encoding) {
+        if write_stream(self.fd).encoding != encoding {
+            self.flush_lane();
+            set_write_stream(
+                self.fd,
+                Stream {
+                    encoding,
+                    lane: Lane::default(),
+                },
+            );
+        }
+    }
+
+    // Write any small values still waiting to be packed with later ones, so
+    // that raw writes start at the next word.
+    fn flush_lane(&mut self) {
+        let stream = write_stream(self.fd);
+        if stream.lane.len != 0 {
+            set_write_stream(
+                self.fd,
+                Stream {
+                    lane: Lane::default(),
+                    ..stream
+                },
+            );
+            self.write_bytes(&stream.lane.word.to_le_bytes());
+        }
+    }
+
     fn write_bytes(&mut self, bytes: &[u8]) {
         unsafe { sys_write(self.fd, bytes.as_ptr(), bytes.len()) }
         (self.hook)(bytes);
@@ -685,10 +995,16 @@ impl<F: Fn(&[u8])> FdWriter<F> {
 
 impl<F: Fn(&[u8])> Write for FdWriter<F> {
     fn write<T: Serialize>(&mut self, val: T) {
-        val.serialize(&mut Serializer::new(self)).unwrap();
+        let fd = self.fd;
+        let stream = write_stream(fd);
+        let mut serializer = Serializer::resume(&mut *self, stream.encoding, stream.lane);
+        val.serialize(&mut serializer).unwrap();
+        let lane = serializer.suspend();
+        set_write_stream(fd, Stream { lane, ..stream });
     }
 
     fn write_slice<T: Pod>(&mut self, buf: &[T]) {
+        self.flush_lane();
         self.write_bytes(bytemuck::cast_slice(buf));
     }
 }
@@ -713,6 +1029,7 @@ impl<F: Fn(&[u8])> WordWrite for FdWriter<F> {
 #[cfg(feature = "std")]
 impl<F: Fn(&[u8])> std::io::Write for FdWriter<F> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.flush_lane();
         self.write_bytes(buf);
         Ok(buf.len())
     }
@@ -722,6 +1039,64 @@ impl<F: Fn(&[u8])> std::io::Write for FdWriter<F> {
     }
 }
 
+/// Bind the bytes read from stdin to the [input digest][input_digest].
+///
+/// After this is called, every byte read from stdin through this module is
+/// hashed. When the guest halts, any unread input is consumed and the SHA-256
+/// digest of the whole of stdin is checked against [input_digest], panicking
+/// on a mismatch. The host sets the input digest to the hash of stdin with
+/// `ExecutorEnvBuilder::bind_input`, so the receipt claim commits to the input
+/// without the guest committing it to the journal.
+///
+/// This must be called before reading from stdin, and stdin must only be read
+/// using this module (e.g. [read], [read_slice] or [stdin]).
+///
+/// # Example
+///
+/// ```no_run
+/// use risc0_zkvm::guest::env;
+///
+/// env::bind_input();
+/// let input: u32 = env::read();
+/// ```
+pub fn bind_input() {
+    unsafe {
+        if INPUT_HASHER.is_none() {
+            INPUT_HASHER = Some(Sha256::new());
+        }
+    }
+}
+
+/// Adds the bytes read from `fd` to the running input hash, if enabled.
+fn hash_input(fd: u32, bytes: &[u8]) {
+    if fd != fileno::STDIN {
+        return;
+    }
+    if let Some(hasher) = unsafe { INPUT_HASHER.as_mut() } {
+        hasher.update(bytes);
+    }
+}
+
+/// Checks the hash of stdin against the input digest, if [bind_input] was called.
+fn verify_input() {
+    if unsafe { INPUT_HASHER.is_none() } {
+        return;
+    }
+
+    // Input the guest did not read is still part of the digest.
+    let mut reader = stdin();
+    let mut buf = [0u8; 1024];
+    while reader.read_bytes(&mut buf) != 0 {}
+
+    let hasher = unsafe { INPUT_HASHER.take() }.unwrap();
+    let digest: Digest = hasher.finalize().as_slice().try_into().unwrap();
+    assert_eq!(
+        digest,
+        input_digest(),
+        "the input read from stdin does not match the input digest"
+    );
+}
+
 /// Read the input digest from the input commitment.
 pub fn input_digest() -> Digest {
     Digest::new([
@@ -0,0 +1,174 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only access to files mounted by the host.
+//!
+//! The host makes files available to the guest with
+//! `ExecutorEnvBuilder::mount`. Reads are served through the same file
+//! descriptor mechanism as [env::stdin][crate::guest::env::stdin], so the
+//! contents of every file the guest reads are recorded in the session.
+//!
+//! When the `std` feature is enabled, [File] implements [std::io::Read] and
+//! [std::io::Seek].
+//!
+//! The `std::fs` module of the zkVM target is provided by the RISC Zero Rust
+//! toolchain rather than this crate, and only reaches mounted files when the
+//! toolchain's standard library is built against the `sys_open`, `sys_seek`,
+//! `sys_stat` and `sys_close` syscalls exported with the `export-syscalls`
+//! feature. Use this module to read mounted files with any toolchain.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use risc0_zkvm::guest::fs;
+//!
+//! let config = fs::read("/etc/config.toml").unwrap();
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use risc0_zkvm_platform::syscall::{seek, sys_close, sys_open, sys_read, sys_seek, sys_stat};
+
+/// The error returned when no file is mounted at the requested path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("no file is mounted at the given path")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotFound {}
+
+#[cfg(feature = "std")]
+impl From<NotFound> for std::io::Error {
+    fn from(err: NotFound) -> Self {
+        std::io::Error::new(std::io::ErrorKind::NotFound, err)
+    }
+}
+
+/// Enumeration of possible methods to seek within a [File].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeekFrom {
+    /// Sets the offset to the provided number of bytes.
+    Start(u64),
+
+    /// Sets the offset to the size of the file plus the specified number of
+    /// bytes.
+    End(i64),
+
+    /// Sets the offset to the current position plus the specified number of
+    /// bytes.
+    Current(i64),
+}
+
+/// A read-only file mounted by the host.
+///
+/// The file is closed when it is dropped.
+pub struct File {
+    fd: u32,
+}
+
+impl File {
+    /// Opens the file mounted at `path`.
+    pub fn open(path: &str) -> Result<File, NotFound> {
+        let fd = unsafe { sys_open(path.as_ptr(), path.len()) };
+        if fd == u32::MAX {
+            return Err(NotFound);
+        }
+        Ok(File { fd })
+    }
+
+    /// Reads bytes from the current position into `buf`, returning the
+    /// number of bytes read. Returns 0 at the end of the file.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        unsafe { sys_read(self.fd, buf.as_mut_ptr(), buf.len()) }
+    }
+
+    /// Reads all bytes from the current position until the end of the file,
+    /// appending them to `buf`. Returns the number of bytes read.
+    pub fn read_to_end(&mut self, buf: &mut Vec<u8>) -> usize {
+        let start = buf.len();
+        let mut chunk = [0u8; 1024];
+        loop {
+            let nread = self.read(&mut chunk);
+            if nread == 0 {
+                return buf.len() - start;
+            }
+            buf.extend_from_slice(&chunk[..nread]);
+        }
+    }
+
+    /// Moves the read position, returning the new position from the start of
+    /// the file, or `None` if the new position would be negative.
+    pub fn seek(&mut self, pos: SeekFrom) -> Option<u64> {
+        let (offset, whence) = match pos {
+            SeekFrom::Start(offset) => (offset as i64, seek::SET),
+            SeekFrom::End(offset) => (offset, seek::END),
+            SeekFrom::Current(offset) => (offset, seek::CUR),
+        };
+        let pos = sys_seek(self.fd, offset, whence);
+        (pos != u64::MAX).then_some(pos)
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        sys_close(self.fd);
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(File::read(self, buf))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Seek for File {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            std::io::SeekFrom::Start(offset) => SeekFrom::Start(offset),
+            std::io::SeekFrom::End(offset) => SeekFrom::End(offset),
+            std::io::SeekFrom::Current(offset) => SeekFrom::Current(offset),
+        };
+        File::seek(self, pos).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )
+        })
+    }
+}
+
+/// Reads the entire contents of the file mounted at `path`.
+pub fn read(path: &str) -> Result<Vec<u8>, NotFound> {
+    let mut file = File::open(path)?;
+    let mut contents = Vec::new();
+    file.read_to_end(&mut contents);
+    Ok(contents)
+}
+
+/// Returns the size, in bytes, of the file mounted at `path`.
+pub fn size(path: &str) -> Result<u64, NotFound> {
+    let size = unsafe { sys_stat(path.as_ptr(), path.len()) };
+    if size == u64::MAX {
+        return Err(NotFound);
+    }
+    Ok(size)
+}
@@ -64,10 +64,27 @@
 //! composite product publicly available. All input an output of your guest is private except for
 //! what is written to the journal with [env::commit].
 //!
+//! The same guest can be written with the typed [typed_entry] attribute, which reads each argument with
+//! [env::read] and commits the return value with [env::commit]. `risc0-build` then generates a
+//! typed `GuestMethod` for the host, so the host and guest cannot disagree on these types:
+//!
+//! ```ignore
+//! #![no_main]
+//! #![no_std]
+//!
+//! #[risc0_zkvm::guest::typed_entry]
+//! fn main(a: u64, b: u64) -> u64 {
+//!     if a == 1 || b == 1 {
+//!         panic!("Trivial factors")
+//!     }
+//!     a.checked_mul(b).expect("Integer overflow")
+//! }
+//! ```
+//!
 //! By default, the guest only has the Rust `core` libraries and not `std`. A partial
 //! implementation of the Rust standard libraries can be enabled with the `std` feature on this [crate].
 //! When this feature is not enabled, the lines including `#![no_std]` and `#![no_main]` are
-//! required, as well as the use of the [crate::guest::entry] macro. When `std` is enabled, these
+//! required, as well as the use of the [crate::guest::entry] macro or the [typed_entry] attribute. When `std` is enabled, these
 //! three lines can be omitted and many features of `std` can be used.
 //!
 //! If you encounter problems building zkVM guest code, you can see if we have a
@@ -80,6 +97,7 @@
 #![deny(missing_docs)]
 
 pub mod env;
+pub mod fs;
 pub use risc0_zkp::core::hash::sha;
 
 #[cfg(target_os = "zkvm")]
@@ -88,6 +106,7 @@ use core::arch::asm;
 use risc0_zkvm_platform::syscall::sys_panic;
 
 pub use crate::entry;
+pub use risc0_zkvm_macros::typed_entry;
 
 #[cfg(target_os = "zkvm")]
 core::arch::global_asm!(include_str!("memset.s"));
@@ -14,7 +14,7 @@
 
 use std::path::Path;
 
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use bytes::Bytes;
 use prost::Message;
 
@@ -24,7 +24,10 @@ use super::{
 };
 use crate::{
     get_version,
-    host::{api::SegmentInfo, client::prove::get_r0vm_path},
+    host::{
+        api::SegmentInfo,
+        client::{mount::MOUNT_CHANNEL, prove::get_r0vm_path},
+    },
     receipt::{AssumptionReceipt, SegmentReceipt, SuccinctReceipt},
     ExecutorEnv, Journal, ProveInfo, ProverOpts, Receipt, ReceiptClaim,
 };
@@ -433,15 +436,39 @@ impl Client {
         env: &ExecutorEnv<'_>,
         binary: pb::api::Asset,
     ) -> Result<pb::api::ExecutorEnv> {
+        ensure!(
+            env.journal_writer.is_none(),
+            "journal_writer is not supported by the external prover"
+        );
         Ok(pb::api::ExecutorEnv {
             binary: Some(binary),
             env_vars: env.env_vars.clone(),
             args: env.args.clone(),
-            slice_ios: env.slice_io.borrow().inner.keys().cloned().collect(),
-            read_fds: env.posix_io.borrow().read_fds.keys().cloned().collect(),
-            write_fds: env.posix_io.borrow().write_fds.keys().cloned().collect(),
+            slice_ios: env.slice_io.lock().unwrap().inner.keys().cloned().collect(),
+            read_fds: env
+                .posix_io
+                .lock()
+                .unwrap()
+                .read_fds
+                .keys()
+                .cloned()
+                .collect(),
+            write_fds: env
+                .posix_io
+                .lock()
+                .unwrap()
+                .write_fds
+                .keys()
+                .cloned()
+                .collect(),
             segment_limit_po2: env.segment_limit_po2,
             session_limit: env.session_limit,
+            stack_limit: env.stack_limit,
+            random_seed: env.random_seed,
+            preload: env.preload.clone(),
+            input_digest: env.input_digest.map(Into::into),
+            bind_input: env.bind_input,
+            mounts: env.mounts.paths().cloned().collect(),
             trace_events: (!env.trace.is_empty()).then_some(()),
             pprof_out: env
                 .pprof_out
@@ -450,7 +477,8 @@ impl Client {
                 .unwrap_or_default(),
             assumptions: env
                 .assumptions
-                .borrow()
+                .lock()
+                .unwrap()
                 .cached
                 .iter()
                 .map(|a| {
@@ -538,6 +566,7 @@ impl Client {
                                         .exit_code
                                         .ok_or(malformed_err())?
                                         .try_into()?,
+                                    memory: session.memory.map(TryInto::try_into).transpose()?,
                                 }),
                                 None => Err(malformed_err()),
                             }
@@ -612,41 +641,45 @@ impl Client {
     fn on_posix_read(&self, env: &ExecutorEnv<'_>, fd: u32, nread: usize) -> Result<Bytes> {
         tracing::debug!("on_posix_read: {fd}, {nread}");
         let mut from_host = vec![0; nread];
-        let posix_io = env.posix_io.borrow();
+        let posix_io = env.posix_io.lock().unwrap();
         let reader = posix_io
             .read_fds
             .get(&fd)
             .ok_or(anyhow!("Bad read file descriptor: {fd}"))?;
-        let nread = reader.borrow_mut().read(&mut from_host)?;
+        let nread = reader.lock().unwrap().read(&mut from_host)?;
         let slice = from_host[..nread].to_vec();
         Ok(slice.into())
     }
 
     fn on_posix_write(&self, env: &ExecutorEnv<'_>, fd: u32, from_guest: Bytes) -> Result<()> {
         tracing::debug!("on_posix_write: {fd}");
-        let posix_io = env.posix_io.borrow();
+        let posix_io = env.posix_io.lock().unwrap();
         let writer = posix_io
             .write_fds
             .get(&fd)
             .ok_or(anyhow!("Bad write file descriptor: {fd}"))?;
-        writer.borrow_mut().write_all(&from_guest)?;
+        writer.lock().unwrap().write_all(&from_guest)?;
         Ok(())
     }
 
     fn on_slice(&self, env: &ExecutorEnv<'_>, name: &str, from_guest: Bytes) -> Result<Bytes> {
-        let table = env.slice_io.borrow();
+        if name == MOUNT_CHANNEL {
+            return env.mounts.handle_remote(from_guest);
+        }
+        let table = env.slice_io.lock().unwrap();
         let slice_io = table
             .inner
             .get(name)
             .ok_or(anyhow!("Unknown I/O channel name: {name}"))?;
-        let result = slice_io.borrow_mut().handle_io(name, from_guest)?;
+        let result = slice_io.lock().unwrap().handle_io(name, from_guest)?;
         Ok(result)
     }
 
     fn on_trace(&self, env: &ExecutorEnv<'_>, event: pb::api::TraceEvent) -> Result<()> {
         for trace_callback in env.trace.iter() {
             trace_callback
-                .borrow_mut()
+                .lock()
+                .unwrap()
                 .trace_callback(event.clone().try_into()?)?;
         }
         Ok(())
@@ -34,11 +34,11 @@ use std::{
     time::Duration,
 };
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
 use bytes::{Buf, BufMut, Bytes};
 use prost::Message;
 
-use crate::{ExitCode, Journal};
+use crate::{host::encoding::malformed_err, ExitCode, Journal, MemoryStats};
 
 mod pb {
     pub(crate) mod api {
@@ -56,7 +56,7 @@ const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
 trait RootMessage: Message {}
 
-pub trait Connection {
+pub trait Connection: Send {
     fn stream(&self) -> &TcpStream;
     fn close(&mut self) -> Result<i32>;
     fn try_clone(&self) -> Result<Box<dyn Connection>>;
@@ -257,10 +257,6 @@ impl Connection for TcpConnection {
     }
 }
 
-fn malformed_err() -> anyhow::Error {
-    anyhow!("Malformed error")
-}
-
 impl pb::api::Asset {
     fn as_bytes(&self) -> Result<Bytes> {
         let bytes = match self.kind.as_ref().ok_or(malformed_err())? {
@@ -302,6 +298,9 @@ pub struct SessionInfo {
 
     /// The [ExitCode] of the session.
     pub exit_code: ExitCode,
+
+    /// Guest memory usage observed during execution, if the executor reported it.
+    pub memory: Option<MemoryStats>,
 }
 
 /// Provides information about a segment of execution.
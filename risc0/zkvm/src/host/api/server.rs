@@ -310,6 +310,7 @@ impl Server {
                                 segments: session.segments.len().try_into()?,
                                 journal: session.journal.unwrap_or_default().bytes,
                                 exit_code: Some(session.exit_code.into()),
+                                memory: Some(session.memory.into()),
                             }),
                         },
                     )),
@@ -646,6 +647,24 @@ fn build_env<'a>(
         env_builder.segment_limit_po2(segment_limit_po2);
     }
     env_builder.session_limit(request.session_limit);
+    if let Some(stack_limit) = request.stack_limit {
+        env_builder.stack_limit(stack_limit);
+    }
+    if let Some(random_seed) = request.random_seed {
+        env_builder.random_seed(random_seed);
+    }
+    if !request.preload.is_empty() {
+        env_builder.preload(request.preload.clone());
+    }
+    if let Some(input_digest) = request.input_digest.clone() {
+        env_builder.input_digest(input_digest.try_into()?);
+    }
+    if request.bind_input {
+        env_builder.bind_input();
+    }
+    if !request.mounts.is_empty() {
+        env_builder.remote_mounts(request.mounts.clone(), proxy.try_clone()?);
+    }
     if request.trace_events.is_some() {
         let proxy = TraceProxy::new(conn.try_clone()?);
         env_builder.trace_callback(proxy);
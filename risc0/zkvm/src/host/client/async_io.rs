@@ -0,0 +1,184 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    future::Future,
+    io::{Error as IoError, Read, Result as IoResult, Write},
+};
+
+use anyhow::{anyhow, Result};
+use bytes::Bytes;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    runtime::Handle,
+    sync::oneshot,
+};
+
+use super::slice_io::SliceIo;
+
+// The executor is synchronous and runs on its own thread, e.g. one started
+// with `tokio::task::spawn_blocking`. Each call into an async handler is
+// spawned as a task on the runtime the handler was registered from, and the
+// executor thread waits for its result on a channel. The futures are thus
+// polled by the runtime itself, with access to its I/O driver and timers, and
+// the executor never blocks a worker thread of the runtime.
+
+/// A host handler for the guest's simple I/O, like those added with
+/// [ExecutorEnvBuilder::slice_io][crate::ExecutorEnvBuilder::slice_io], that
+/// awaits on other work such as a database query.
+pub trait AsyncSliceIo {
+    /// Host side I/O handling
+    ///
+    /// Whatever data the guest sent is received by this function in
+    /// `from_guest`, and this function is to return the data the host is
+    /// sending to the guest.
+    fn handle_io(
+        &mut self,
+        syscall: &str,
+        from_guest: Bytes,
+    ) -> impl Future<Output = Result<Bytes>> + Send;
+}
+
+/// Runs `future` on the runtime of `handle` and waits for its output.
+fn run<F>(handle: &Handle, future: F) -> Result<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    handle.spawn(async move {
+        let _ = tx.send(future.await);
+    });
+    rx.blocking_recv()
+        .map_err(|_| anyhow!("async I/O handler was cancelled by its runtime"))
+}
+
+/// Adapts an [AsyncRead] into a [Read].
+pub(crate) struct BlockingReader<R> {
+    handle: Handle,
+    inner: Option<R>,
+}
+
+impl<R> BlockingReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            handle: Handle::current(),
+            inner: Some(inner),
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send + 'static> Read for BlockingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> IoResult<usize> {
+        let mut inner = self.inner.take().ok_or_else(cancelled)?;
+        let len = buf.len();
+        let (inner, result) = run(&self.handle, async move {
+            let mut data = vec![0; len];
+            let result = inner.read(&mut data).await.map(|n| {
+                data.truncate(n);
+                data
+            });
+            (inner, result)
+        })
+        .map_err(IoError::other)?;
+        self.inner = Some(inner);
+        let data = result?;
+        buf[..data.len()].copy_from_slice(&data);
+        Ok(data.len())
+    }
+}
+
+/// Adapts an [AsyncWrite] into a [Write].
+pub(crate) struct BlockingWriter<W> {
+    handle: Handle,
+    inner: Option<W>,
+}
+
+impl<W> BlockingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            handle: Handle::current(),
+            inner: Some(inner),
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin + Send + 'static> Write for BlockingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+        let mut inner = self.inner.take().ok_or_else(cancelled)?;
+        let data = buf.to_vec();
+        let (inner, result) = run(&self.handle, async move {
+            let result = inner.write(&data).await;
+            (inner, result)
+        })
+        .map_err(IoError::other)?;
+        self.inner = Some(inner);
+        result
+    }
+
+    fn flush(&mut self) -> IoResult<()> {
+        let mut inner = self.inner.take().ok_or_else(cancelled)?;
+        let (inner, result) = run(&self.handle, async move {
+            let result = inner.flush().await;
+            (inner, result)
+        })
+        .map_err(IoError::other)?;
+        self.inner = Some(inner);
+        result
+    }
+}
+
+// A previous call was cancelled along with the reader or writer it owned.
+fn cancelled() -> IoError {
+    IoError::other("async I/O handler was cancelled by its runtime")
+}
+
+/// Adapts an [AsyncSliceIo] into a [SliceIo].
+pub(crate) struct BlockingSliceIo<H> {
+    handle: Handle,
+    inner: Option<H>,
+}
+
+impl<H> BlockingSliceIo<H> {
+    pub(crate) fn new(inner: H) -> Self {
+        Self {
+            handle: Handle::current(),
+            inner: Some(inner),
+        }
+    }
+}
+
+impl<H: AsyncSliceIo + Send + 'static> SliceIo for BlockingSliceIo<H> {
+    fn handle_io(&mut self, syscall: &str, from_guest: Bytes) -> Result<Bytes> {
+        let mut inner = self.inner.take().ok_or_else(cancelled)?;
+        let syscall = syscall.to_string();
+        let (inner, result) = run(&self.handle, async move {
+            let result = inner.handle_io(&syscall, from_guest).await;
+            (inner, result)
+        })?;
+        self.inner = Some(inner);
+        result
+    }
+}
+
+/// Adapts an async callback into a synchronous one.
+pub(crate) fn blocking_fn<'a, Fut>(
+    callback: impl Fn(Bytes) -> Fut + 'a,
+) -> impl Fn(Bytes) -> Result<Bytes> + 'a
+where
+    Fut: Future<Output = Result<Bytes>> + Send + 'static,
+{
+    let handle = Handle::current();
+    move |from_guest| run(&handle, callback(from_guest))?
+}
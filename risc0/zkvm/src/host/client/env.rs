@@ -15,40 +15,71 @@
 //! This module defines the [ExecutorEnv] and [ExecutorEnvBuilder].
 
 use std::{
-    cell::RefCell,
     collections::HashMap,
     io::{BufRead, BufReader, Cursor, Read, Write},
+    marker::PhantomData,
     mem,
     path::{Path, PathBuf},
-    rc::Rc,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
-use anyhow::Result;
+use anyhow::{anyhow, ensure, Result};
 use bytemuck::Pod;
 use bytes::Bytes;
 use risc0_zkp::core::digest::Digest;
-use risc0_zkvm_platform::{self, fileno};
-use serde::Serialize;
+use risc0_zkvm_platform::{
+    self, fileno,
+    memory::{GUEST_MIN_MEM, STACK_TOP},
+};
+use serde::{de::DeserializeOwned, Serialize};
 use tempfile::TempDir;
 
+#[cfg(feature = "async")]
+use crate::host::client::async_io::{
+    blocking_fn, AsyncSliceIo, BlockingReader, BlockingSliceIo, BlockingWriter,
+};
 #[cfg(feature = "prove")]
 use crate::Assumption;
 use crate::{
     host::client::{
-        posix_io::PosixIo,
+        mount::{MountSource, MountTable},
+        posix_io::{BoundInput, PosixIo},
         slice_io::{slice_io_from_fn, SliceIo, SliceIoTable},
     },
-    serde::to_vec,
+    serde::{from_slice, to_vec, Encoding, Lane, Serializer},
     AssumptionReceipt, TraceCallback,
 };
 
 /// A builder pattern used to construct an [ExecutorEnv].
-#[derive(Default)]
-pub struct ExecutorEnvBuilder<'a> {
+///
+/// By default, the I/O handlers and callbacks added to the builder need not be
+/// [Send], so the [ExecutorEnv] it builds must be used on the thread that built
+/// it. Use [SendExecutorEnv::builder] to build an environment that can be moved
+/// to another thread.
+pub struct ExecutorEnvBuilder<'a, H = LocalHandlers> {
     inner: ExecutorEnv<'a>,
+    // Small input values packed with Encoding::V2, waiting for the next ones.
+    input_lane: Lane,
+    handlers: PhantomData<H>,
+}
+
+impl<'a, H> Default for ExecutorEnvBuilder<'a, H> {
+    fn default() -> Self {
+        Self {
+            inner: ExecutorEnv::default(),
+            input_lane: Lane::default(),
+            handlers: PhantomData,
+        }
+    }
 }
 
+/// Marks an [ExecutorEnvBuilder] that accepts any I/O handler or callback.
+pub enum LocalHandlers {}
+
+/// Marks an [ExecutorEnvBuilder] that only accepts I/O handlers and callbacks
+/// that are [Send].
+pub enum SendHandlers {}
+
 /// Container for assumptions in the executor environment.
 #[derive(Debug, Default)]
 pub(crate) struct AssumptionReceipts {
@@ -85,14 +116,22 @@ pub struct ExecutorEnv<'a> {
     pub(crate) args: Vec<String>,
     pub(crate) segment_limit_po2: Option<u32>,
     pub(crate) session_limit: Option<u64>,
-    pub(crate) posix_io: Rc<RefCell<PosixIo<'a>>>,
-    pub(crate) slice_io: Rc<RefCell<SliceIoTable<'a>>>,
+    pub(crate) stack_limit: Option<u32>,
+    pub(crate) random_seed: Option<u64>,
+    pub(crate) serde_encoding: Encoding,
+    pub(crate) posix_io: Arc<Mutex<PosixIo<'a>>>,
+    pub(crate) slice_io: Arc<Mutex<SliceIoTable<'a>>>,
+    pub(crate) mounts: MountTable,
+    pub(crate) journal_writer: Option<Arc<Mutex<dyn Write + 'a>>>,
     pub(crate) input: Vec<u8>,
-    pub(crate) trace: Vec<Rc<RefCell<dyn TraceCallback + 'a>>>,
-    pub(crate) assumptions: Rc<RefCell<AssumptionReceipts>>,
+    pub(crate) preload: Vec<u8>,
+    pub(crate) trace: Vec<Arc<Mutex<dyn TraceCallback + 'a>>>,
+    pub(crate) assumptions: Arc<Mutex<AssumptionReceipts>>,
     pub(crate) segment_path: Option<SegmentPath>,
     pub(crate) pprof_out: Option<PathBuf>,
     pub(crate) input_digest: Option<Digest>,
+    pub(crate) bind_input: bool,
+    pub(crate) bound_input: Option<Arc<Mutex<BoundInput<'a>>>>,
 }
 
 impl<'a> ExecutorEnv<'a> {
@@ -110,39 +149,58 @@ impl<'a> ExecutorEnv<'a> {
     }
 }
 
-impl<'a> ExecutorEnvBuilder<'a> {
-    /// Finalize this builder to construct an [ExecutorEnv].
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// use risc0_zkvm::ExecutorEnv;
-    ///
-    /// let env = ExecutorEnv::builder().build().unwrap();
-    /// ```
-    ///
-    /// After calling `build`, the [ExecutorEnvBuilder] will be reset to
-    /// default.
-    pub fn build(&mut self) -> Result<ExecutorEnv<'a>> {
-        let mut inner = mem::take(&mut self.inner);
-
-        if !inner.input.is_empty() {
-            let reader = Cursor::new(inner.input.clone());
-            inner
-                .posix_io
-                .borrow_mut()
-                .with_read_fd(fileno::STDIN, reader);
-        }
+/// An [ExecutorEnv] that can be sent to and shared with other threads.
+///
+/// A [SendExecutorEnv] is [Send] and [Sync], so it can be built on one thread
+/// and executed or proven on another, e.g. on a thread pool. Convert it into an
+/// [ExecutorEnv] on the thread that runs the executor.
+///
+/// # Example
+///
+/// ```
+/// use risc0_zkvm::{ExecutorEnv, SendExecutorEnv};
+///
+/// let env = SendExecutorEnv::builder()
+///     .write(&42u32)
+///     .unwrap()
+///     .build()
+///     .unwrap();
+/// std::thread::spawn(move || {
+///     let env: ExecutorEnv = env.into();
+///     // Run the executor with `env` here.
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+pub struct SendExecutorEnv<'a>(ExecutorEnv<'a>);
+
+// SAFETY: a SendExecutorEnv is only built by an ExecutorEnvBuilder<SendHandlers>,
+// which only accepts handlers and callbacks that are Send. The remaining state
+// of the ExecutorEnv is Send, and all of it is owned by the environment, so it
+// can be moved to another thread. No access is given through a shared
+// reference, so it is also Sync.
+unsafe impl Send for SendExecutorEnv<'_> {}
+unsafe impl Sync for SendExecutorEnv<'_> {}
+
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SendExecutorEnv<'static>>();
+};
 
-        if inner.pprof_out.is_none() {
-            if let Ok(env_var) = std::env::var("RISC0_PPROF_OUT") {
-                inner.pprof_out = Some(env_var.into());
-            }
-        }
+impl<'a> SendExecutorEnv<'a> {
+    /// Construct an [ExecutorEnvBuilder] that builds a [SendExecutorEnv].
+    pub fn builder() -> ExecutorEnvBuilder<'a, SendHandlers> {
+        ExecutorEnvBuilder::default()
+    }
+}
 
-        Ok(inner)
+impl<'a> From<SendExecutorEnv<'a>> for ExecutorEnv<'a> {
+    fn from(env: SendExecutorEnv<'a>) -> Self {
+        env.0
     }
+}
 
+impl<'a, H> ExecutorEnvBuilder<'a, H> {
     /// Set a segment limit, specified in powers of 2 cycles.
     ///
     /// Given value must be between [risc0_zkp::MIN_CYCLES_PO2] and
@@ -169,6 +227,95 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self
     }
 
+    /// Set a stack limit, specified in bytes.
+    ///
+    /// The guest stack grows down from [STACK_TOP]. If the stack pointer moves
+    /// more than `limit` bytes below it, execution fails with a stack overflow
+    /// error rather than continuing with corrupted memory.
+    ///
+    /// Stack overflow detection is opt-in. When no limit is set, the guard sits
+    /// at [GUEST_MIN_MEM], the bottom of guest memory, so the stack may use the
+    /// whole region below [STACK_TOP] and an overflow is only reported once the
+    /// stack pointer leaves guest memory entirely. Set a limit to catch deep
+    /// recursion well before that point.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .stack_limit(256 * 1024) // 256 KiB
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn stack_limit(&mut self, limit: u32) -> &mut Self {
+        self.inner.stack_limit = Some(limit);
+        self
+    }
+
+    /// Seed the randomness provided to the guest.
+    ///
+    /// By default, `SYS_RANDOM` returns entropy from the host operating
+    /// system, so guests using randomness produce a different journal and
+    /// cycle count on each run. With a seed set, the randomness is drawn from a
+    /// ChaCha generator seeded with `seed`, making execution reproducible. This
+    /// is intended for tests and benchmarks; the seeded values are predictable
+    /// and must not be relied on for privacy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .random_seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn random_seed(&mut self, seed: u64) -> &mut Self {
+        self.inner.random_seed = Some(seed);
+        self
+    }
+
+    /// Set the [Encoding] used by [ExecutorEnvBuilder::write].
+    ///
+    /// The default, [Encoding::V1], widens every `u8` to a full word.
+    /// [Encoding::V2] packs bytes and other small integers densely, which
+    /// makes inputs such as a `Vec<u8>` four times smaller and cheaper to read.
+    /// The guest must read the data with the same encoding, which it selects
+    /// with `env::set_input_serde_encoding`. Values written one at a time are
+    /// encoded the same as a single tuple of them, so the guest may read them
+    /// either way.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::{serde::Encoding, ExecutorEnv};
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .serde_encoding(Encoding::V2)
+    ///     .write(&vec![1u8, 2, 3, 4])
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn serde_encoding(&mut self, encoding: Encoding) -> &mut Self {
+        if self.inner.serde_encoding != encoding {
+            self.flush_input_lane();
+            self.inner.serde_encoding = encoding;
+        }
+        self
+    }
+
+    // Write any small input values still waiting to be packed with later ones.
+    fn flush_input_lane(&mut self) {
+        let lane = mem::take(&mut self.input_lane);
+        if lane.len != 0 {
+            self.inner.input.extend_from_slice(&lane.word.to_le_bytes());
+        }
+    }
+
     /// Add environment variables to the guest environment.
     ///
     /// # Example
@@ -253,7 +400,16 @@ impl<'a> ExecutorEnvBuilder<'a> {
     ///     .unwrap();
     /// ```
     pub fn write<T: Serialize>(&mut self, data: &T) -> Result<&mut Self> {
-        Ok(self.write_slice(&to_vec(data)?))
+        let mut words: Vec<u32> = Vec::new();
+        let mut serializer =
+            Serializer::resume(&mut words, self.inner.serde_encoding, self.input_lane);
+        data.serialize(&mut serializer)?;
+        self.input_lane = serializer.suspend();
+        drop(serializer);
+        self.inner
+            .input
+            .extend_from_slice(bytemuck::cast_slice(&words));
+        Ok(self)
     }
 
     /// Write input data to the zkVM guest stdin.
@@ -276,59 +432,123 @@ impl<'a> ExecutorEnvBuilder<'a> {
     ///     .unwrap();
     /// ```
     pub fn write_slice<T: Pod>(&mut self, slice: &[T]) -> &mut Self {
+        self.flush_input_lane();
         self.inner
             .input
             .extend_from_slice(bytemuck::cast_slice(slice));
         self
     }
 
-    /// Add a posix-style standard input.
-    pub fn stdin(&mut self, reader: impl Read + 'a) -> &mut Self {
-        self.read_fd(fileno::STDIN, BufReader::new(reader))
+    /// Preload `data` into guest memory, for the guest to read with `env::preloaded`.
+    ///
+    /// The data is placed in the initial memory image instead of being sent over stdin, so the
+    /// guest reads it without syscalls and pages in only the parts it touches. This makes it
+    /// cheaper than [ExecutorEnvBuilder::write_slice] for large inputs that are read sparsely.
+    /// At most 32 MiB can be preloaded, and doing so reduces the memory available to the heap
+    /// by the same amount.
+    ///
+    /// Since the data is part of the memory image, the receipt is verified against the image ID
+    /// computed with [compute_image_id_with_preload][crate::compute_image_id_with_preload]
+    /// rather than the image ID of the ELF alone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .preload(vec![0u8; 1024 * 1024])
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn preload(&mut self, data: impl Into<Vec<u8>>) -> &mut Self {
+        self.inner.preload = data.into();
+        self
     }
 
-    /// Add a posix-style standard output.
-    pub fn stdout(&mut self, writer: impl Write + 'a) -> &mut Self {
-        self.write_fd(fileno::STDOUT, writer)
+    /// Add a posix-style standard input that is read asynchronously.
+    ///
+    /// See [ExecutorEnvBuilder::io_callback_async] for how async handlers are driven.
+    #[cfg(feature = "async")]
+    pub fn stdin_async(
+        &mut self,
+        reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
+    ) -> &mut Self {
+        self.add_read_fd(fileno::STDIN, BufReader::new(BlockingReader::new(reader)))
     }
 
-    /// Add a posix-style standard error.
-    pub fn stderr(&mut self, writer: impl Write + 'a) -> &mut Self {
-        self.write_fd(fileno::STDERR, writer)
+    /// Add a posix-style file descriptor for reading asynchronously.
+    ///
+    /// See [ExecutorEnvBuilder::io_callback_async] for how async handlers are driven.
+    #[cfg(feature = "async")]
+    pub fn read_fd_async(
+        &mut self,
+        fd: u32,
+        reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
+    ) -> &mut Self {
+        self.add_read_fd(fd, BufReader::new(BlockingReader::new(reader)))
     }
 
-    /// Add a posix-style file descriptor for reading.
-    pub fn read_fd(&mut self, fd: u32, reader: impl BufRead + 'a) -> &mut Self {
-        self.inner.posix_io.borrow_mut().with_read_fd(fd, reader);
-        self
+    /// Add a posix-style file descriptor for writing asynchronously.
+    ///
+    /// See [ExecutorEnvBuilder::io_callback_async] for how async handlers are driven.
+    #[cfg(feature = "async")]
+    pub fn write_fd_async(
+        &mut self,
+        fd: u32,
+        writer: impl tokio::io::AsyncWrite + Unpin + Send + 'static,
+    ) -> &mut Self {
+        self.add_write_fd(fd, BlockingWriter::new(writer))
     }
 
-    /// Add a posix-style file descriptor for writing.
-    pub fn write_fd(&mut self, fd: u32, writer: impl Write + 'a) -> &mut Self {
-        self.inner.posix_io.borrow_mut().with_write_fd(fd, writer);
+    /// Mount a read-only file or directory into the guest at `path`.
+    ///
+    /// The guest can open, read, seek and stat files under `path`. When the
+    /// source is a directory, files below it are read from the host as the
+    /// guest opens them. All data read by the guest is recorded in the
+    /// session, so proving does not need access to the mounted files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .mount("/etc/config.toml", b"debug = true".as_slice())
+    ///     .mount("/data", Path::new("./data"))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn mount(&mut self, path: &str, source: impl Into<MountSource>) -> &mut Self {
+        self.inner.mounts.with_mount(path, source.into());
         self
     }
 
-    /// Add a handler for simple I/O handling.
-    pub fn slice_io(&mut self, channel: &str, handler: impl SliceIo + 'a) -> &mut Self {
-        self.inner
-            .slice_io
-            .borrow_mut()
-            .with_handler(channel, handler);
+    /// Forward accesses to files below the given mount points to the client
+    /// of an external executor.
+    #[cfg(feature = "prove")]
+    pub(crate) fn remote_mounts(
+        &mut self,
+        paths: Vec<String>,
+        handler: impl SliceIo + Send + 'static,
+    ) -> &mut Self {
+        self.inner.mounts.with_remote(paths, handler);
         self
     }
 
-    /// Add a handler for simple I/O handling.
-    pub fn io_callback<C: AsRef<str>>(
+    /// Add an async handler for simple I/O handling.
+    ///
+    /// This is the async variant of [ExecutorEnvBuilder::slice_io]. See
+    /// [ExecutorEnvBuilder::io_callback_async] for how async handlers are driven.
+    #[cfg(feature = "async")]
+    pub fn slice_io_async(
         &mut self,
-        channel: C,
-        callback: impl Fn(Bytes) -> Result<Bytes> + 'a,
+        channel: &str,
+        handler: impl AsyncSliceIo + Send + 'static,
     ) -> &mut Self {
-        self.inner
-            .slice_io
-            .borrow_mut()
-            .with_handler(channel.as_ref(), slice_io_from_fn(callback));
-        self
+        self.add_slice_io(channel, BlockingSliceIo::new(handler))
     }
 
     /// Add an [AssumptionReceipt] to the [ExecutorEnv], for use in [composition].
@@ -345,18 +565,13 @@ impl<'a> ExecutorEnvBuilder<'a> {
     pub fn add_assumption(&mut self, assumption: impl Into<AssumptionReceipt>) -> &mut Self {
         self.inner
             .assumptions
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .cached
             .push(assumption.into());
         self
     }
 
-    /// Add a callback handler for raw trace messages.
-    pub fn trace_callback(&mut self, callback: impl TraceCallback + 'a) -> &mut Self {
-        self.inner.trace.push(Rc::new(RefCell::new(callback)));
-        self
-    }
-
     /// Set the path where segments will be stored.
     pub fn segment_path<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
         self.inner.segment_path = Some(SegmentPath::Path(path.as_ref().to_path_buf()));
@@ -374,4 +589,414 @@ impl<'a> ExecutorEnvBuilder<'a> {
         self.inner.input_digest = Some(digest);
         self
     }
+
+    /// Bind the guest's standard input to the receipt claim.
+    ///
+    /// The SHA-256 digest of all of stdin, whether provided with
+    /// [ExecutorEnvBuilder::write], [ExecutorEnvBuilder::write_slice] or
+    /// [ExecutorEnvBuilder::stdin], is used as the input digest of the receipt
+    /// claim. The executor hashes stdin as the guest reads it, by any means, and
+    /// only buffers the input the guest has not yet read when the digest is first
+    /// needed, at the end of the first segment. A guest that calls
+    /// `env::bind_input` checks at exit that the bytes on its stdin match this
+    /// digest, so a verifier can check which input a proof was computed over by
+    /// comparing `ReceiptClaim::input` with the digest of the expected input.
+    ///
+    /// This cannot be combined with [ExecutorEnvBuilder::input_digest].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .write_slice(&[1u8, 2, 3, 4])
+    ///     .bind_input()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn bind_input(&mut self) -> &mut Self {
+        self.inner.bind_input = true;
+        self
+    }
+
+    fn build_env(&mut self) -> Result<ExecutorEnv<'a>> {
+        self.flush_input_lane();
+        let mut inner = mem::take(&mut self.inner);
+
+        if let Some(limit) = inner.stack_limit {
+            ensure!(
+                limit <= STACK_TOP - GUEST_MIN_MEM as u32,
+                "stack limit of {limit} bytes exceeds the available stack region"
+            );
+        }
+
+        if !inner.input.is_empty() {
+            let reader = Cursor::new(inner.input.clone());
+            inner
+                .posix_io
+                .lock()
+                .unwrap()
+                .with_read_fd(fileno::STDIN, reader);
+        }
+
+        if inner.bind_input {
+            ensure!(
+                inner.input_digest.is_none(),
+                "input_digest cannot be set when the input is bound with bind_input"
+            );
+            let mut posix_io = inner.posix_io.lock().unwrap();
+            inner.bound_input = Some(BoundInput::bind(&mut posix_io, fileno::STDIN));
+        }
+
+        if inner.pprof_out.is_none() {
+            if let Ok(env_var) = std::env::var("RISC0_PPROF_OUT") {
+                inner.pprof_out = Some(env_var.into());
+            }
+        }
+
+        Ok(inner)
+    }
+
+    fn add_read_fd(&mut self, fd: u32, reader: impl BufRead + 'a) -> &mut Self {
+        self.inner.posix_io.lock().unwrap().with_read_fd(fd, reader);
+        self
+    }
+
+    fn add_write_fd(&mut self, fd: u32, writer: impl Write + 'a) -> &mut Self {
+        self.inner
+            .posix_io
+            .lock()
+            .unwrap()
+            .with_write_fd(fd, writer);
+        self
+    }
+
+    fn set_journal_writer(&mut self, writer: impl Write + 'a) -> &mut Self {
+        self.inner.journal_writer = Some(Arc::new(Mutex::new(writer)));
+        self
+    }
+
+    fn add_slice_io(&mut self, channel: &str, handler: impl SliceIo + 'a) -> &mut Self {
+        self.inner
+            .slice_io
+            .lock()
+            .unwrap()
+            .with_handler(channel, handler);
+        self
+    }
+
+    fn add_host_fn<Req, Resp>(
+        &mut self,
+        channel: &str,
+        handler: impl Fn(Req) -> Result<Resp> + 'a,
+    ) -> &mut Self
+    where
+        Req: DeserializeOwned,
+        Resp: Serialize,
+    {
+        let name = channel.to_string();
+        self.add_slice_io(
+            channel,
+            slice_io_from_fn(move |from_guest| {
+                let req: Req = from_slice(&from_guest).map_err(|err| {
+                    anyhow!("host_fn {name:?}: failed to decode request from guest: {err}")
+                })?;
+                let resp = handler(req)?;
+                let words = to_vec(&resp)
+                    .map_err(|err| anyhow!("host_fn {name:?}: failed to encode response: {err}"))?;
+                Ok(Bytes::copy_from_slice(bytemuck::cast_slice(&words)))
+            }),
+        )
+    }
+
+    fn add_trace_callback(&mut self, callback: impl TraceCallback + 'a) -> &mut Self {
+        self.inner.trace.push(Arc::new(Mutex::new(callback)));
+        self
+    }
+}
+
+impl<'a> ExecutorEnvBuilder<'a> {
+    /// Finalize this builder to construct an [ExecutorEnv].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder().build().unwrap();
+    /// ```
+    ///
+    /// After calling `build`, the [ExecutorEnvBuilder] will be reset to
+    /// default.
+    pub fn build(&mut self) -> Result<ExecutorEnv<'a>> {
+        self.build_env()
+    }
+
+    /// Add a posix-style standard input.
+    pub fn stdin(&mut self, reader: impl Read + 'a) -> &mut Self {
+        self.read_fd(fileno::STDIN, BufReader::new(reader))
+    }
+
+    /// Add a posix-style standard output.
+    pub fn stdout(&mut self, writer: impl Write + 'a) -> &mut Self {
+        self.write_fd(fileno::STDOUT, writer)
+    }
+
+    /// Add a posix-style standard error.
+    pub fn stderr(&mut self, writer: impl Write + 'a) -> &mut Self {
+        self.write_fd(fileno::STDERR, writer)
+    }
+
+    /// Stream the journal committed by the guest to `writer`.
+    ///
+    /// By default, the journal is buffered in memory and returned in the
+    /// [Session][crate::Session] and [Receipt][crate::Receipt]. With a writer
+    /// set, committed bytes are written to it as the guest produces them and
+    /// only their SHA-256 digest is kept, in `Session::external_journal_digest`
+    /// and [ReceiptMetadata::external_journal_digest][crate::ReceiptMetadata].
+    /// This is intended for guests whose output is too large to hold in host
+    /// memory. Local execution is required; the external prover does not
+    /// support this.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .journal_writer(File::create("journal.bin").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn journal_writer(&mut self, writer: impl Write + 'a) -> &mut Self {
+        self.set_journal_writer(writer)
+    }
+
+    /// Capture the [JournalTree][crate::JournalTree] committed by the guest.
+    ///
+    /// A guest that calls `env::commit_tree` commits only the Merkle root of
+    /// the tree to the journal, and sends its fields to `writer`. Decode them
+    /// with [JournalTree::decode][crate::JournalTree::decode] to produce a
+    /// [JournalDisclosure][crate::JournalDisclosure]. By default, the fields
+    /// are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let mut fields = Vec::new();
+    /// let env = ExecutorEnv::builder()
+    ///     .journal_fields(&mut fields)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn journal_fields(&mut self, writer: impl Write + 'a) -> &mut Self {
+        self.write_fd(fileno::JOURNAL_FIELDS, writer)
+    }
+
+    /// Add a posix-style file descriptor for reading.
+    pub fn read_fd(&mut self, fd: u32, reader: impl BufRead + 'a) -> &mut Self {
+        self.add_read_fd(fd, reader)
+    }
+
+    /// Add a posix-style file descriptor for writing.
+    pub fn write_fd(&mut self, fd: u32, writer: impl Write + 'a) -> &mut Self {
+        self.add_write_fd(fd, writer)
+    }
+
+    /// Add a handler for simple I/O handling.
+    pub fn slice_io(&mut self, channel: &str, handler: impl SliceIo + 'a) -> &mut Self {
+        self.add_slice_io(channel, handler)
+    }
+
+    /// Add a handler for simple I/O handling.
+    pub fn io_callback<C: AsRef<str>>(
+        &mut self,
+        channel: C,
+        callback: impl Fn(Bytes) -> Result<Bytes> + 'a,
+    ) -> &mut Self {
+        self.add_slice_io(channel.as_ref(), slice_io_from_fn(callback))
+    }
+
+    /// Add an async handler for simple I/O handling.
+    ///
+    /// This is the async variant of [ExecutorEnvBuilder::io_callback], for handlers that await
+    /// on other work, such as a database query. Each future is spawned as a task on the runtime
+    /// this method is called from, and the executor waits for its output on a channel. The
+    /// executor must therefore run on a thread of its own, e.g. with
+    /// `tokio::task::spawn_blocking`, and not on a worker thread of the runtime.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called outside of a tokio runtime, as do the other async handlers. Running
+    /// the executor on a worker thread of the runtime also panics, rather than deadlocking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// # let rt = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// # rt.block_on(async {
+    /// tokio::task::spawn_blocking(|| {
+    ///     let env = ExecutorEnv::builder()
+    ///         .io_callback_async("lookup", |key: Bytes| async move {
+    ///             // e.g. fetch the value for `key` from a database.
+    ///             Ok(key)
+    ///         })
+    ///         .build()
+    ///         .unwrap();
+    ///     // Run the executor with `env` here.
+    /// })
+    /// .await
+    /// .unwrap();
+    /// # });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn io_callback_async<C, Fut>(
+        &mut self,
+        channel: C,
+        callback: impl Fn(Bytes) -> Fut + 'a,
+    ) -> &mut Self
+    where
+        C: AsRef<str>,
+        Fut: std::future::Future<Output = Result<Bytes>> + Send + 'static,
+    {
+        self.io_callback(channel, blocking_fn(callback))
+    }
+
+    /// Add a typed host function that the guest can call with `env::host_call`.
+    ///
+    /// The request sent by the guest is decoded as `Req` and the value returned by the handler is
+    /// encoded as `Resp`, both using [crate::serde]. Execution fails if the request cannot be
+    /// decoded or if the handler returns an error.
+    ///
+    /// The `channel` must match the name of the `SyscallName` used by the guest, which is usually
+    /// declared with `declare_syscall!` in a crate shared by the host and guest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use risc0_zkvm::ExecutorEnv;
+    ///
+    /// let env = ExecutorEnv::builder()
+    ///     .host_fn("add", |(a, b): (u32, u32)| Ok(a as u64 + b as u64))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn host_fn<Req, Resp>(
+        &mut self,
+        channel: impl AsRef<str>,
+        handler: impl Fn(Req) -> Result<Resp> + 'a,
+    ) -> &mut Self
+    where
+        Req: DeserializeOwned,
+        Resp: Serialize,
+    {
+        self.add_host_fn(channel.as_ref(), handler)
+    }
+
+    /// Add a callback handler for raw trace messages.
+    pub fn trace_callback(&mut self, callback: impl TraceCallback + 'a) -> &mut Self {
+        self.add_trace_callback(callback)
+    }
+}
+
+/// The handler methods of a builder for a [SendExecutorEnv] match those of an
+/// [ExecutorEnvBuilder] for an [ExecutorEnv], but require the handlers to be
+/// [Send].
+impl<'a> ExecutorEnvBuilder<'a, SendHandlers> {
+    /// Finalize this builder to construct a [SendExecutorEnv].
+    ///
+    /// After calling `build`, the [ExecutorEnvBuilder] will be reset to
+    /// default.
+    pub fn build(&mut self) -> Result<SendExecutorEnv<'a>> {
+        self.build_env().map(SendExecutorEnv)
+    }
+
+    /// Add a posix-style standard input.
+    pub fn stdin(&mut self, reader: impl Read + Send + 'a) -> &mut Self {
+        self.read_fd(fileno::STDIN, BufReader::new(reader))
+    }
+
+    /// Add a posix-style standard output.
+    pub fn stdout(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.write_fd(fileno::STDOUT, writer)
+    }
+
+    /// Add a posix-style standard error.
+    pub fn stderr(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.write_fd(fileno::STDERR, writer)
+    }
+
+    /// Stream the journal committed by the guest to `writer`.
+    pub fn journal_writer(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.set_journal_writer(writer)
+    }
+
+    /// Capture the [JournalTree][crate::JournalTree] committed by the guest.
+    pub fn journal_fields(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.write_fd(fileno::JOURNAL_FIELDS, writer)
+    }
+
+    /// Add a posix-style file descriptor for reading.
+    pub fn read_fd(&mut self, fd: u32, reader: impl BufRead + Send + 'a) -> &mut Self {
+        self.add_read_fd(fd, reader)
+    }
+
+    /// Add a posix-style file descriptor for writing.
+    pub fn write_fd(&mut self, fd: u32, writer: impl Write + Send + 'a) -> &mut Self {
+        self.add_write_fd(fd, writer)
+    }
+
+    /// Add a handler for simple I/O handling.
+    pub fn slice_io(&mut self, channel: &str, handler: impl SliceIo + Send + 'a) -> &mut Self {
+        self.add_slice_io(channel, handler)
+    }
+
+    /// Add a handler for simple I/O handling.
+    pub fn io_callback<C: AsRef<str>>(
+        &mut self,
+        channel: C,
+        callback: impl Fn(Bytes) -> Result<Bytes> + Send + 'a,
+    ) -> &mut Self {
+        self.add_slice_io(channel.as_ref(), slice_io_from_fn(callback))
+    }
+
+    /// Add an async handler for simple I/O handling.
+    #[cfg(feature = "async")]
+    pub fn io_callback_async<C, Fut>(
+        &mut self,
+        channel: C,
+        callback: impl Fn(Bytes) -> Fut + Send + 'a,
+    ) -> &mut Self
+    where
+        C: AsRef<str>,
+        Fut: std::future::Future<Output = Result<Bytes>> + Send + 'static,
+    {
+        self.io_callback(channel, blocking_fn(callback))
+    }
+
+    /// Add a typed host function that the guest can call with `env::host_call`.
+    pub fn host_fn<Req, Resp>(
+        &mut self,
+        channel: impl AsRef<str>,
+        handler: impl Fn(Req) -> Result<Resp> + Send + 'a,
+    ) -> &mut Self
+    where
+        Req: DeserializeOwned,
+        Resp: Serialize,
+    {
+        self.add_host_fn(channel.as_ref(), handler)
+    }
+
+    /// Add a callback handler for raw trace messages.
+    pub fn trace_callback(&mut self, callback: impl TraceCallback + Send + 'a) -> &mut Self {
+        self.add_trace_callback(callback)
+    }
 }
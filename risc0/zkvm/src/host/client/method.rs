@@ -0,0 +1,110 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use risc0_zkp::core::digest::{Digest, DIGEST_WORDS};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{default_executor, default_prover, ExecutorEnv, Receipt};
+
+/// A guest method with a typed input and output.
+///
+/// `risc0-build` generates a [GuestMethod] constant for each guest whose
+/// entrypoint is marked with the `risc0_zkvm::guest::typed_entry` attribute. The
+/// input is the guest's argument, or a tuple of its arguments when it takes
+/// more than one, and the output is its return type.
+///
+/// # Example
+///
+/// ```ignore
+/// // methods/guest/src/bin/multiply.rs
+/// #[risc0_zkvm::guest::typed_entry]
+/// fn main(a: u64, b: u64) -> u64 {
+///     a.checked_mul(b).expect("Integer overflow")
+/// }
+///
+/// // host
+/// use methods::MULTIPLY;
+///
+/// let product: u64 = MULTIPLY.execute((17, 23)).unwrap();
+/// let (receipt, product) = MULTIPLY.prove((17, 23)).unwrap();
+/// ```
+pub struct GuestMethod<I, O> {
+    elf: &'static [u8],
+    image_id: [u32; DIGEST_WORDS],
+    phantom: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O> GuestMethod<I, O> {
+    /// Construct a [GuestMethod] from the ELF binary and image ID of a guest.
+    pub const fn new(elf: &'static [u8], image_id: [u32; DIGEST_WORDS]) -> Self {
+        Self {
+            elf,
+            image_id,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The ELF binary of the guest.
+    pub fn elf(&self) -> &'static [u8] {
+        self.elf
+    }
+
+    /// The image ID of the guest.
+    pub fn image_id(&self) -> Digest {
+        self.image_id.into()
+    }
+}
+
+impl<I: Serialize, O: DeserializeOwned> GuestMethod<I, O> {
+    /// Execute the guest with the given input, returning the value it
+    /// committed.
+    ///
+    /// This uses the [default_executor].
+    pub fn execute(&self, input: I) -> Result<O> {
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let session = default_executor().execute(env, self.elf)?;
+        Ok(session.journal.decode()?)
+    }
+
+    /// Prove the guest with the given input, returning the verified receipt
+    /// and the value the guest committed.
+    ///
+    /// This uses the [default_prover].
+    pub fn prove(&self, input: I) -> Result<(Receipt, O)> {
+        let env = ExecutorEnv::builder().write(&input)?.build()?;
+        let receipt = default_prover().prove(env, self.elf)?.receipt;
+        receipt.verify(self.image_id)?;
+        let output = receipt.journal.decode()?;
+        Ok((receipt, output))
+    }
+}
+
+impl<I, O> Clone for GuestMethod<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for GuestMethod<I, O> {}
+
+impl<I, O> core::fmt::Debug for GuestMethod<I, O> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GuestMethod")
+            .field("image_id", &self.image_id())
+            .finish_non_exhaustive()
+    }
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "async")]
+pub(crate) mod async_io;
 pub(crate) mod env;
+pub(crate) mod method;
+pub(crate) mod mount;
 pub(crate) mod posix_io;
 pub(crate) mod prove;
 pub(crate) mod slice_io;
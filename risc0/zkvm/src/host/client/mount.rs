@@ -0,0 +1,428 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, Cursor, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, bail, Result};
+use bytes::Bytes;
+
+use super::slice_io::SliceIo;
+
+/// The host-side contents of a path mounted into the guest with
+/// [ExecutorEnvBuilder::mount][crate::ExecutorEnvBuilder::mount].
+#[derive(Clone, Debug)]
+pub enum MountSource {
+    /// A single file with the given contents.
+    Bytes(Bytes),
+
+    /// A directory on the host. Files below it are read when the guest opens
+    /// them.
+    Dir(PathBuf),
+}
+
+impl From<Bytes> for MountSource {
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for MountSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+impl From<&[u8]> for MountSource {
+    fn from(bytes: &[u8]) -> Self {
+        Self::Bytes(Bytes::copy_from_slice(bytes))
+    }
+}
+
+impl From<PathBuf> for MountSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Dir(path)
+    }
+}
+
+impl From<&Path> for MountSource {
+    fn from(path: &Path) -> Self {
+        Self::Dir(path.to_path_buf())
+    }
+}
+
+/// Name of the I/O channel on which the client of an external executor serves
+/// its mounts.
+pub(crate) const MOUNT_CHANNEL: &str = "risc0_zkvm::mount";
+
+const OP_READ_AT: u8 = 0;
+const OP_SIZE: u8 = 1;
+
+/// The most bytes requested from the client of an external executor in a
+/// single read of a remote mount.
+const MAX_REMOTE_READ: usize = 64 * 1024;
+
+/// Read-only files and directories mounted into the guest.
+#[derive(Clone, Debug, Default)]
+pub struct MountTable {
+    pub(crate) inner: BTreeMap<String, MountSource>,
+    remote: Option<RemoteMounts>,
+}
+
+/// Mounts served on request by the client of an external executor.
+#[derive(Clone)]
+pub(crate) struct RemoteMounts {
+    paths: Vec<String>,
+    handler: Arc<Mutex<dyn SliceIo + Send>>,
+}
+
+impl fmt::Debug for RemoteMounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteMounts")
+            .field("paths", &self.paths)
+            .finish_non_exhaustive()
+    }
+}
+
+enum Entry {
+    Bytes(Bytes),
+    File(PathBuf),
+}
+
+/// A file opened with [MountTable::open].
+pub(crate) enum MountFile {
+    Bytes(Cursor<Bytes>),
+    Host(fs::File),
+    Remote {
+        remote: RemoteMounts,
+        path: String,
+        pos: u64,
+        size: u64,
+    },
+}
+
+impl Read for MountFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Bytes(cursor) => cursor.read(buf),
+            Self::Host(file) => file.read(buf),
+            Self::Remote {
+                remote,
+                path,
+                pos,
+                size,
+            } => {
+                if buf.is_empty() || *pos >= *size {
+                    return Ok(0);
+                }
+                let len = buf.len().min(MAX_REMOTE_READ) as u32;
+                let args = [pos.to_le_bytes().as_slice(), &len.to_le_bytes()].concat();
+                let reply = remote_request(remote, OP_READ_AT, &args, path)
+                    .map_err(io::Error::other)?
+                    .ok_or(io::ErrorKind::NotFound)?;
+                let nread = reply.len().min(buf.len());
+                buf[..nread].copy_from_slice(&reply[..nread]);
+                *pos += nread as u64;
+                Ok(nread)
+            }
+        }
+    }
+}
+
+impl Seek for MountFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        match self {
+            Self::Bytes(cursor) => cursor.seek(pos),
+            Self::Host(file) => file.seek(pos),
+            Self::Remote { pos: cur, size, .. } => {
+                let new = match pos {
+                    SeekFrom::Start(offset) => Some(offset),
+                    SeekFrom::End(offset) => size.checked_add_signed(offset),
+                    SeekFrom::Current(offset) => cur.checked_add_signed(offset),
+                };
+                *cur = new.ok_or(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                ))?;
+                Ok(*cur)
+            }
+        }
+    }
+}
+
+impl MountTable {
+    pub fn with_mount(&mut self, path: &str, source: MountSource) -> &mut Self {
+        self.inner
+            .insert(path.trim_end_matches('/').to_string(), source);
+        self
+    }
+
+    /// Forward accesses to files below the given mount points to `handler`,
+    /// which is served by [MountTable::handle_remote] on the client.
+    #[cfg(feature = "prove")]
+    pub(crate) fn with_remote(
+        &mut self,
+        paths: Vec<String>,
+        handler: impl SliceIo + Send + 'static,
+    ) -> &mut Self {
+        self.remote = Some(RemoteMounts {
+            paths,
+            handler: Arc::new(Mutex::new(handler)),
+        });
+        self
+    }
+
+    /// Returns the guest paths of all mount points.
+    pub(crate) fn paths(&self) -> impl Iterator<Item = &String> {
+        self.inner.keys()
+    }
+
+    /// Opens the file at the given guest path for reading, or returns `None`
+    /// if no file is mounted there. The contents are read on demand.
+    pub fn open(&self, path: &str) -> Result<Option<MountFile>> {
+        if let Some(remote) = self.remote_for(path) {
+            let Some(size) = self.size(path)? else {
+                return Ok(None);
+            };
+            return Ok(Some(MountFile::Remote {
+                remote: remote.clone(),
+                path: path.to_string(),
+                pos: 0,
+                size,
+            }));
+        }
+        Ok(match self.lookup(path)? {
+            Some(Entry::Bytes(bytes)) => Some(MountFile::Bytes(Cursor::new(bytes))),
+            Some(Entry::File(host_path)) => Some(MountFile::Host(fs::File::open(host_path)?)),
+            None => None,
+        })
+    }
+
+    /// Returns the size of the file at the given guest path, or `None` if no
+    /// file is mounted there.
+    pub fn size(&self, path: &str) -> Result<Option<u64>> {
+        if let Some(remote) = self.remote_for(path) {
+            return remote_request(remote, OP_SIZE, &[], path)?
+                .map(|reply| {
+                    let size = reply.as_ref().try_into()?;
+                    Ok(u64::from_le_bytes(size))
+                })
+                .transpose();
+        }
+        Ok(match self.lookup(path)? {
+            Some(Entry::Bytes(bytes)) => Some(bytes.len() as u64),
+            Some(Entry::File(host_path)) => Some(fs::metadata(host_path)?.len()),
+            None => None,
+        })
+    }
+
+    /// Serve a request made by an external executor for a file mounted in
+    /// this table.
+    pub(crate) fn handle_remote(&self, request: Bytes) -> Result<Bytes> {
+        let (op, args) = request
+            .split_first()
+            .ok_or(anyhow!("Malformed mount request"))?;
+        let found = match *op {
+            OP_READ_AT => {
+                let (offset, args) = args
+                    .split_first_chunk()
+                    .ok_or(anyhow!("Malformed mount request"))?;
+                let (len, path) = args
+                    .split_first_chunk()
+                    .ok_or(anyhow!("Malformed mount request"))?;
+                let len = u32::from_le_bytes(*len) as usize;
+                match self.open(std::str::from_utf8(path)?)? {
+                    Some(mut file) => {
+                        file.seek(SeekFrom::Start(u64::from_le_bytes(*offset)))?;
+                        let mut buf = Vec::new();
+                        file.take(len.min(MAX_REMOTE_READ) as u64)
+                            .read_to_end(&mut buf)?;
+                        Some(buf.into())
+                    }
+                    None => None,
+                }
+            }
+            OP_SIZE => self
+                .size(std::str::from_utf8(args)?)?
+                .map(|size| Bytes::copy_from_slice(&size.to_le_bytes())),
+            _ => bail!("Unknown mount request: {op}"),
+        };
+        Ok(match found {
+            Some(contents) => [&[1], contents.as_ref()].concat().into(),
+            None => Bytes::from_static(&[0]),
+        })
+    }
+
+    fn remote_for(&self, path: &str) -> Option<&RemoteMounts> {
+        self.remote.as_ref().filter(|remote| {
+            remote.paths.iter().any(|prefix| {
+                path.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+        })
+    }
+
+    fn lookup(&self, path: &str) -> Result<Option<Entry>> {
+        if let Some(MountSource::Bytes(bytes)) = self.inner.get(path) {
+            return Ok(Some(Entry::Bytes(bytes.clone())));
+        }
+
+        // Find the longest mounted directory containing this path.
+        let found = self.inner.iter().rev().find_map(|(prefix, source)| {
+            let rest = path.strip_prefix(prefix.as_str())?.strip_prefix('/')?;
+            match source {
+                MountSource::Dir(dir) => Some((dir, rest)),
+                MountSource::Bytes(_) => None,
+            }
+        });
+        let Some((dir, rest)) = found else {
+            return Ok(None);
+        };
+
+        let rest = Path::new(rest);
+        if !rest.components().all(|c| matches!(c, Component::Normal(_))) {
+            bail!("Mounted path {path:?} must not contain relative or absolute components");
+        }
+
+        // Resolve symlinks, and reject any that lead out of the mounted directory.
+        let root = dir.canonicalize()?;
+        let host_path = match root.join(rest).canonicalize() {
+            Ok(host_path) => host_path,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if !host_path.starts_with(&root) {
+            bail!("Mounted path {path:?} resolves outside of its mounted directory");
+        }
+        if !host_path.is_file() {
+            return Ok(None);
+        }
+        Ok(Some(Entry::File(host_path)))
+    }
+}
+
+fn remote_request(remote: &RemoteMounts, op: u8, args: &[u8], path: &str) -> Result<Option<Bytes>> {
+    let request = [&[op], args, path.as_bytes()].concat();
+    let reply = remote
+        .handler
+        .lock()
+        .unwrap()
+        .handle_io(MOUNT_CHANNEL, request.into())?;
+    match reply.first() {
+        Some(0) => Ok(None),
+        Some(1) => Ok(Some(reply.slice(1..))),
+        _ => bail!("Malformed reply to mount request"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Seek, SeekFrom};
+
+    use bytes::Bytes;
+
+    use super::{MountSource, MountTable, OP_READ_AT, OP_SIZE};
+
+    fn request(op: u8, args: &[u8], path: &str) -> Bytes {
+        [&[op], args, path.as_bytes()].concat().into()
+    }
+
+    fn read_at(offset: u64, len: u32) -> Vec<u8> {
+        [offset.to_le_bytes().as_slice(), &len.to_le_bytes()].concat()
+    }
+
+    fn read(table: &MountTable, path: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        table
+            .open(path)?
+            .map(|mut file| {
+                let mut contents = Vec::new();
+                file.read_to_end(&mut contents)?;
+                Ok(contents)
+            })
+            .transpose()
+    }
+
+    #[test]
+    fn handle_remote() {
+        let mut table = MountTable::default();
+        table.with_mount("/config", MountSource::from(b"debug".as_slice()));
+
+        let reply = table
+            .handle_remote(request(OP_READ_AT, &read_at(1, 3), "/config"))
+            .unwrap();
+        assert_eq!(reply.as_ref(), b"\x01ebu");
+        let reply = table
+            .handle_remote(request(OP_SIZE, &[], "/config"))
+            .unwrap();
+        assert_eq!(
+            reply.as_ref(),
+            [&[1], 5u64.to_le_bytes().as_slice()].concat()
+        );
+        let reply = table
+            .handle_remote(request(OP_READ_AT, &read_at(0, 5), "/missing"))
+            .unwrap();
+        assert_eq!(reply.as_ref(), [0]);
+    }
+
+    #[cfg(feature = "prove")]
+    struct Client(MountTable);
+
+    #[cfg(feature = "prove")]
+    impl crate::host::client::slice_io::SliceIo for Client {
+        fn handle_io(&mut self, _syscall: &str, from_guest: Bytes) -> anyhow::Result<Bytes> {
+            self.0.handle_remote(from_guest)
+        }
+    }
+
+    #[cfg(feature = "prove")]
+    #[test]
+    fn remote_file() {
+        let mut client = MountTable::default();
+        client.with_mount("/data", MountSource::from(b"0123456789".as_slice()));
+        let mut server = MountTable::default();
+        server.with_remote(vec!["/data".to_string()], Client(client));
+
+        let mut file = server.open("/data").unwrap().unwrap();
+        file.seek(SeekFrom::End(-4)).unwrap();
+        let mut buf = [0; 2];
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"67");
+        file.seek(SeekFrom::Start(1)).unwrap();
+        file.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"12");
+        assert!(server.open("/data/missing").unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reject_symlink_escape() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), b"secret").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("file"), b"file").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret"), dir.path().join("link")).unwrap();
+
+        let mut table = MountTable::default();
+        table.with_mount("/data", dir.path().to_path_buf().into());
+        assert_eq!(read(&table, "/data/file").unwrap().unwrap(), b"file");
+        assert_eq!(table.size("/data/file").unwrap(), Some(4));
+        assert!(read(&table, "/data/link").is_err());
+        assert!(read(&table, "/data/missing").unwrap().is_none());
+    }
+}
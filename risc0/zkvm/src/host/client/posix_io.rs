@@ -13,19 +13,21 @@
 // limitations under the License.
 
 use std::{
-    cell::RefCell,
     collections::BTreeMap,
-    io::{stderr, stdout, BufRead, Cursor, Write},
-    rc::Rc,
+    io::{sink, stderr, stdout, BufRead, BufReader, Cursor, Read, Write},
+    sync::{Arc, Mutex},
 };
 
+use risc0_zkp::core::digest::Digest;
 use risc0_zkvm_platform::fileno;
 
+use crate::sha::rust_crypto::{Digest as _, Sha256};
+
 /// Posix-style I/O
 #[derive(Clone)]
 pub struct PosixIo<'a> {
-    pub(crate) read_fds: BTreeMap<u32, Rc<RefCell<dyn BufRead + 'a>>>,
-    pub(crate) write_fds: BTreeMap<u32, Rc<RefCell<dyn Write + 'a>>>,
+    pub(crate) read_fds: BTreeMap<u32, Arc<Mutex<dyn BufRead + 'a>>>,
+    pub(crate) write_fds: BTreeMap<u32, Arc<Mutex<dyn Write + 'a>>>,
 }
 
 impl<'a> Default for PosixIo<'a> {
@@ -36,19 +38,83 @@ impl<'a> Default for PosixIo<'a> {
         };
         new.with_read_fd(fileno::STDIN, Cursor::new(vec![]))
             .with_write_fd(fileno::STDOUT, stdout())
-            .with_write_fd(fileno::STDERR, stderr());
+            .with_write_fd(fileno::STDERR, stderr())
+            .with_write_fd(fileno::JOURNAL_FIELDS, sink());
         new
     }
 }
 
 impl<'a> PosixIo<'a> {
     pub fn with_read_fd(&mut self, fd: u32, reader: impl BufRead + 'a) -> &mut Self {
-        self.read_fds.insert(fd, Rc::new(RefCell::new(reader)));
+        self.read_fds.insert(fd, Arc::new(Mutex::new(reader)));
         self
     }
 
     pub fn with_write_fd(&mut self, fd: u32, writer: impl Write + 'a) -> &mut Self {
-        self.write_fds.insert(fd, Rc::new(RefCell::new(writer)));
+        self.write_fds.insert(fd, Arc::new(Mutex::new(writer)));
         self
     }
 }
+
+/// Standard input bound to the receipt claim with
+/// [ExecutorEnvBuilder::bind_input][crate::ExecutorEnvBuilder::bind_input].
+///
+/// Every byte served to the guest by SYS_READ is hashed as it is read. When the
+/// input digest is first needed, the input not yet read is buffered and hashed,
+/// and the guest goes on to read it from the buffer.
+pub(crate) struct BoundInput<'a> {
+    reader: Arc<Mutex<dyn BufRead + 'a>>,
+    hasher: Sha256,
+    digest: Option<Digest>,
+    rest: Cursor<Vec<u8>>,
+}
+
+impl<'a> BoundInput<'a> {
+    /// Bind the reader of the given file descriptor, replacing it with one that
+    /// hashes the bytes read from it.
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub(crate) fn bind(posix_io: &mut PosixIo<'a>, fd: u32) -> Arc<Mutex<Self>> {
+        let input = Arc::new(Mutex::new(Self {
+            reader: posix_io.read_fds[&fd].clone(),
+            hasher: Sha256::new(),
+            digest: None,
+            rest: Cursor::new(Vec::new()),
+        }));
+        posix_io.with_read_fd(fd, BufReader::new(SharedReader(input.clone())));
+        input
+    }
+
+    /// The SHA-256 digest of the whole input.
+    #[cfg(feature = "prove")]
+    pub(crate) fn digest(&mut self) -> anyhow::Result<Digest> {
+        if let Some(digest) = self.digest {
+            return Ok(digest);
+        }
+        let mut rest = Vec::new();
+        self.reader.lock().unwrap().read_to_end(&mut rest)?;
+        self.hasher.update(&rest);
+        let digest = self.hasher.finalize_reset().as_slice().try_into()?;
+        self.rest = Cursor::new(rest);
+        self.digest = Some(digest);
+        Ok(digest)
+    }
+}
+
+impl<'a> Read for BoundInput<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.digest.is_some() {
+            return self.rest.read(buf);
+        }
+        let nread = self.reader.lock().unwrap().read(buf)?;
+        self.hasher.update(&buf[..nread]);
+        Ok(nread)
+    }
+}
+
+struct SharedReader<R>(Arc<Mutex<R>>);
+
+impl<R: Read> Read for SharedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().read(buf)
+    }
+}
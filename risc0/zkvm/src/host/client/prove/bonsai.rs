@@ -64,7 +64,7 @@ impl Prover for BonsaiProver {
 
         // upload receipts
         let mut receipts_ids: Vec<String> = vec![];
-        for assumption in &env.assumptions.borrow().cached {
+        for assumption in &env.assumptions.lock().unwrap().cached {
             let serialized_receipt = match assumption {
                 crate::AssumptionReceipt::Proven(receipt) => bincode::serialize(receipt)?,
                 crate::AssumptionReceipt::Unresolved(_) => {
@@ -119,6 +119,7 @@ impl Prover for BonsaiProver {
                         segments: stats.segments,
                         total_cycles: stats.total_cycles,
                         user_cycles: stats.cycles,
+                        memory: None,
                     },
                 };
             } else {
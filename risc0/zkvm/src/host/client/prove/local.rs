@@ -70,6 +70,7 @@ impl Executor for LocalProver {
             segments,
             journal: session.journal.unwrap_or_default(),
             exit_code: session.exit_code,
+            memory: Some(session.memory),
         })
     }
 }
@@ -17,7 +17,7 @@ pub(crate) mod external;
 #[cfg(feature = "prove")]
 pub(crate) mod local;
 
-use std::{path::PathBuf, rc::Rc};
+use std::{path::PathBuf, rc::Rc, sync::Arc};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -303,13 +303,26 @@ impl ProverOpts {
 /// * LocalProver if the `prove` feature flag is enabled.
 /// * [ExternalProver] otherwise.
 pub fn default_prover() -> Rc<dyn Prover> {
+    Rc::<dyn Prover + Send + Sync>::from(select_prover())
+}
+
+/// Return a default [Prover] that can be shared across threads.
+///
+/// The [Prover] is selected as in [default_prover], and can be used to prove
+/// several [SendExecutorEnv][crate::SendExecutorEnv]s concurrently, e.g. on a
+/// thread pool.
+pub fn default_shared_prover() -> Arc<dyn Prover + Send + Sync> {
+    Arc::from(select_prover())
+}
+
+fn select_prover() -> Box<dyn Prover + Send + Sync> {
     let explicit = std::env::var("RISC0_PROVER").unwrap_or_default();
     if !explicit.is_empty() {
         return match explicit.to_lowercase().as_str() {
-            "bonsai" => Rc::new(BonsaiProver::new("bonsai")),
-            "ipc" => Rc::new(ExternalProver::new("ipc", get_r0vm_path())),
+            "bonsai" => Box::new(BonsaiProver::new("bonsai")),
+            "ipc" => Box::new(ExternalProver::new("ipc", get_r0vm_path())),
             #[cfg(feature = "prove")]
-            "local" => Rc::new(self::local::LocalProver::new("local")),
+            "local" => Box::new(self::local::LocalProver::new("local")),
             _ => unimplemented!("Unsupported prover: {explicit}"),
         };
     }
@@ -318,15 +331,15 @@ pub fn default_prover() -> Rc<dyn Prover> {
         && std::env::var("BONSAI_API_URL").is_ok()
         && std::env::var("BONSAI_API_KEY").is_ok()
     {
-        return Rc::new(BonsaiProver::new("bonsai"));
+        return Box::new(BonsaiProver::new("bonsai"));
     }
 
     if cfg!(feature = "prove") {
         #[cfg(feature = "prove")]
-        return Rc::new(self::local::LocalProver::new("local"));
+        return Box::new(self::local::LocalProver::new("local"));
     }
 
-    Rc::new(ExternalProver::new("ipc", get_r0vm_path()))
+    Box::new(ExternalProver::new("ipc", get_r0vm_path()))
 }
 
 /// Return a default [Executor] based on environment variables and feature
@@ -345,22 +358,33 @@ pub fn default_prover() -> Rc<dyn Prover> {
 /// * LocalProver if the `prove` feature flag is enabled.
 /// * [ExternalProver] otherwise.
 pub fn default_executor() -> Rc<dyn Executor> {
+    Rc::<dyn Executor + Send + Sync>::from(select_executor())
+}
+
+/// Return a default [Executor] that can be shared across threads.
+///
+/// The [Executor] is selected as in [default_executor].
+pub fn default_shared_executor() -> Arc<dyn Executor + Send + Sync> {
+    Arc::from(select_executor())
+}
+
+fn select_executor() -> Box<dyn Executor + Send + Sync> {
     let explicit = std::env::var("RISC0_EXECUTOR").unwrap_or_default();
     if !explicit.is_empty() {
         return match explicit.to_lowercase().as_str() {
-            "ipc" => Rc::new(ExternalProver::new("ipc", get_r0vm_path())),
+            "ipc" => Box::new(ExternalProver::new("ipc", get_r0vm_path())),
             #[cfg(feature = "prove")]
-            "local" => Rc::new(self::local::LocalProver::new("local")),
+            "local" => Box::new(self::local::LocalProver::new("local")),
             _ => unimplemented!("Unsupported executor: {explicit}"),
         };
     }
 
     if cfg!(feature = "prove") {
         #[cfg(feature = "prove")]
-        return Rc::new(self::local::LocalProver::new("local"));
+        return Box::new(self::local::LocalProver::new("local"));
     }
 
-    Rc::new(ExternalProver::new("ipc", get_r0vm_path()))
+    Box::new(ExternalProver::new("ipc", get_r0vm_path()))
 }
 
 pub(crate) fn get_r0vm_path() -> PathBuf {
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::Result;
 use bytes::Bytes;
@@ -36,39 +39,41 @@ pub trait SliceIo {
 
 #[derive(Clone, Default)]
 pub struct SliceIoTable<'a> {
-    pub(crate) inner: BTreeMap<String, Rc<RefCell<dyn SliceIo + 'a>>>,
+    pub(crate) inner: BTreeMap<String, Arc<Mutex<dyn SliceIo + 'a>>>,
 }
 
-type Callback<'a> = Rc<RefCell<dyn Fn(Bytes) -> Result<Bytes> + 'a>>;
+type Callback<'a> = Box<dyn Fn(Bytes) -> Result<Bytes> + 'a>;
 
 struct FnWrapper<'a> {
     callback: Callback<'a>,
 }
 
+// The handlers are shared through an Arc so that a SendExecutorEnv, whose
+// handlers are all Send, can be sent to another thread.
+#[allow(clippy::arc_with_non_send_sync)]
 pub fn slice_io_from_fn<'a>(
     callback: impl Fn(Bytes) -> Result<Bytes> + 'a,
-) -> Rc<RefCell<dyn SliceIo + 'a>> {
-    let callback = Rc::new(RefCell::new(callback));
-    Rc::new(RefCell::new(FnWrapper { callback }))
+) -> Arc<Mutex<dyn SliceIo + 'a>> {
+    let callback = Box::new(callback);
+    Arc::new(Mutex::new(FnWrapper { callback }))
 }
 
 impl<'a> SliceIo for FnWrapper<'a> {
     fn handle_io(&mut self, _syscall: &str, from_guest: Bytes) -> Result<Bytes> {
-        let callback = self.callback.borrow_mut();
-        callback(from_guest)
+        (self.callback)(from_guest)
     }
 }
 
 impl<'a> SliceIoTable<'a> {
     pub fn with_handler(&mut self, channel: &str, handler: impl SliceIo + 'a) -> &mut Self {
         self.inner
-            .insert(channel.to_string(), Rc::new(RefCell::new(handler)));
+            .insert(channel.to_string(), Arc::new(Mutex::new(handler)));
         self
     }
 }
 
-impl<'a> SliceIo for Rc<RefCell<dyn SliceIo + 'a>> {
+impl<'a> SliceIo for Arc<Mutex<dyn SliceIo + 'a>> {
     fn handle_io(&mut self, syscall: &str, from_guest: Bytes) -> Result<Bytes> {
-        self.borrow_mut().handle_io(syscall, from_guest)
+        self.lock().unwrap().handle_io(syscall, from_guest)
     }
 }
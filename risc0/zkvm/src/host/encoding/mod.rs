@@ -0,0 +1,261 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use prost::Message;
+use risc0_zkp::verify::VerificationError;
+use serde::{Deserialize, Serialize};
+
+use crate::{host::protos as pb, receipt::check_limit, Receipt, VerifierLimits};
+
+mod convert;
+
+#[derive(Serialize)]
+struct JsonReceiptRef<'a> {
+    format: &'a str,
+    version: u32,
+    receipt: &'a Receipt,
+}
+
+#[derive(Deserialize)]
+struct JsonReceipt {
+    format: String,
+    version: u32,
+    receipt: serde_json::Value,
+}
+
+impl Receipt {
+    /// Magic bytes at the start of the canonical binary encoding of a [Receipt].
+    pub const MAGIC: [u8; 4] = *b"R0RC";
+
+    /// Value of the `format` field in the JSON encoding of a [Receipt].
+    pub const JSON_FORMAT: &'static str = "risc0-receipt";
+
+    /// Version of the binary and JSON receipt encodings produced by this release.
+    pub const ENCODING_VERSION: u32 = 1;
+
+    /// Encode this receipt in the canonical binary encoding.
+    ///
+    /// The encoding is the [Receipt::MAGIC] bytes, followed by [Receipt::ENCODING_VERSION] as a
+    /// little-endian `u32`, followed by the `risc0.protos.core.Receipt` protobuf message. The
+    /// protobuf message covers every [InnerReceipt][crate::InnerReceipt] variant, and carries its
+    /// own compatibility versions so that receipts from newer releases are rejected rather than
+    /// misread. Unlike `bincode`, this encoding is versioned and remains decodable by later
+    /// releases.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(Self::MAGIC);
+        bytes.extend_from_slice(&Self::ENCODING_VERSION.to_le_bytes());
+        pb::core::Receipt::from(self.clone())
+            .encode(&mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    /// Decode a receipt from the canonical binary encoding produced by [Receipt::encode].
+    ///
+    /// Returns an error if the bytes are not a receipt in this encoding, or were produced by a
+    /// release using an encoding version unknown to this one.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        decode_message(bytes)?.try_into()
+    }
+
+    /// Decode a receipt as with [Receipt::decode], and check it against the given limits.
+    ///
+    /// The size of `bytes` is checked against [VerifierLimits::max_encoded_bytes] before anything
+    /// is decoded, and the sizes of the decoded message are checked against the other limits
+    /// before it is converted into a [Receipt]. This should be used for receipts from untrusted
+    /// sources, along with a [VerifierContext][crate::VerifierContext] with the same limits, so
+    /// that oversized receipts are rejected before they are verified.
+    pub fn decode_with_limits(bytes: &[u8], limits: &VerifierLimits) -> Result<Self> {
+        check_limit("encoded size", bytes.len(), limits.max_encoded_bytes)
+            .map_err(|err| anyhow!(err))?;
+        let message = decode_message(bytes)?;
+        check_message(&message, limits).map_err(|err| anyhow!(err))?;
+        message.try_into()
+    }
+
+    /// Encode this receipt as JSON, for debugging and interoperability.
+    ///
+    /// The encoding is an object with the fields `format` (always [Receipt::JSON_FORMAT]),
+    /// `version` (the [Receipt::ENCODING_VERSION]) and `receipt`, the serde representation of the
+    /// receipt. It is not as compact as the binary encoding of [Receipt::encode].
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&JsonReceiptRef {
+            format: Self::JSON_FORMAT,
+            version: Self::ENCODING_VERSION,
+            receipt: self,
+        })?)
+    }
+
+    /// Decode a receipt from the JSON encoding produced by [Receipt::to_json].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let json: JsonReceipt = serde_json::from_str(json).context("malformed receipt JSON")?;
+        ensure!(
+            json.format == Self::JSON_FORMAT,
+            "not a receipt: unexpected format {:?}",
+            json.format
+        );
+        check_version(json.version)?;
+        serde_json::from_value(json.receipt).context("malformed receipt JSON")
+    }
+}
+
+fn decode_message(bytes: &[u8]) -> Result<pb::core::Receipt> {
+    let Some(rest) = bytes.strip_prefix(&Receipt::MAGIC) else {
+        bail!("not an encoded receipt: missing magic bytes");
+    };
+    let Some((version, message)) = rest.split_first_chunk::<4>() else {
+        bail!("not an encoded receipt: missing encoding version");
+    };
+    check_version(u32::from_le_bytes(*version))?;
+    pb::core::Receipt::decode(message).context("malformed receipt")
+}
+
+/// Check the sizes of a decoded receipt message against the given limits, as
+/// [VerifierLimits::check] does for a [Receipt].
+fn check_message(
+    message: &pb::core::Receipt,
+    limits: &VerifierLimits,
+) -> Result<(), VerificationError> {
+    limits.check_journal(message.journal.len())?;
+    match message.inner.as_ref() {
+        Some(inner) => check_inner_message(inner, limits),
+        None => Ok(()),
+    }
+}
+
+fn check_inner_message(
+    inner: &pb::core::InnerReceipt,
+    limits: &VerifierLimits,
+) -> Result<(), VerificationError> {
+    use pb::core::inner_receipt::Kind;
+
+    match inner.kind.as_ref() {
+        Some(Kind::Composite(inner)) => {
+            limits.check_counts(inner.segments.len(), inner.assumption_receipts.len())?;
+            for segment in inner.segments.iter() {
+                limits.check_seal(segment.seal.len())?;
+            }
+            for assumption in inner.assumption_receipts.iter() {
+                check_inner_message(assumption, limits)?;
+            }
+            Ok(())
+        }
+        Some(Kind::Succinct(inner)) => limits.check_seal(inner.seal.len()),
+        Some(Kind::Groth16(inner)) => limits.check_seal(inner.seal.len()),
+        Some(Kind::Fake(_)) | None => Ok(()),
+    }
+}
+
+pub(crate) fn malformed_err() -> anyhow::Error {
+    anyhow!("Malformed error")
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version != Receipt::ENCODING_VERSION {
+        bail!(
+            "unsupported receipt encoding version {version}; this release supports version {}",
+            Receipt::ENCODING_VERSION
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        sha::{Digest, Digestible},
+        FakeReceipt, Groth16Receipt, Groth16ReceiptVerifierParameters, InnerReceipt, MaybePruned,
+        Receipt, ReceiptClaim, VerifierLimits,
+    };
+
+    fn receipts() -> Vec<Receipt> {
+        let journal = b"journal".to_vec();
+        vec![
+            Receipt::new(
+                InnerReceipt::Fake(FakeReceipt {
+                    claim: MaybePruned::Pruned(Digest::ZERO),
+                }),
+                journal.clone(),
+            ),
+            Receipt::new(
+                InnerReceipt::Groth16(Groth16Receipt {
+                    seal: vec![1u8; 256],
+                    claim: ReceiptClaim::ok(Digest::ZERO, journal.clone()).into(),
+                    verifier_parameters: Groth16ReceiptVerifierParameters::default().digest(),
+                }),
+                journal,
+            ),
+        ]
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        for receipt in receipts() {
+            let bytes = receipt.encode();
+            assert_eq!(bytes[..4], Receipt::MAGIC);
+            assert_eq!(Receipt::decode(&bytes).unwrap(), receipt);
+        }
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for receipt in receipts() {
+            let json = receipt.to_json().unwrap();
+            assert_eq!(Receipt::from_json(&json).unwrap(), receipt);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_encodings() {
+        let receipt = &receipts()[0];
+
+        let err = Receipt::decode(&bincode::serialize(receipt).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("magic"), "{err}");
+
+        let mut bytes = receipt.encode();
+        bytes[4] = 2;
+        let err = Receipt::decode(&bytes).unwrap_err();
+        assert!(err.to_string().contains("version 2"), "{err}");
+
+        let json = receipt
+            .to_json()
+            .unwrap()
+            .replacen("\"version\": 1", "\"version\": 2", 1);
+        let err = Receipt::from_json(&json).unwrap_err();
+        assert!(err.to_string().contains("version 2"), "{err}");
+    }
+
+    #[test]
+    fn decode_checks_limits() {
+        let bytes = receipts()[1].encode();
+
+        let limits = VerifierLimits::default().with_max_seal_bytes(256);
+        assert!(Receipt::decode_with_limits(&bytes, &limits).is_ok());
+
+        let limits = VerifierLimits::default().with_max_seal_bytes(255);
+        let err = Receipt::decode_with_limits(&bytes, &limits).unwrap_err();
+        assert!(err.to_string().contains("seal size of 256"), "{err}");
+
+        let limits = VerifierLimits::default().with_max_journal_bytes(4);
+        assert!(Receipt::decode_with_limits(&bytes, &limits).is_err());
+
+        let limits = VerifierLimits::default().with_max_encoded_bytes(bytes.len());
+        assert!(Receipt::decode_with_limits(&bytes, &limits).is_ok());
+
+        let limits = VerifierLimits::default().with_max_encoded_bytes(bytes.len() - 1);
+        let err = Receipt::decode_with_limits(&bytes, &limits).unwrap_err();
+        assert!(err.to_string().contains("encoded size"), "{err}");
+    }
+}
@@ -16,7 +16,9 @@
 pub(crate) mod api;
 #[cfg(feature = "client")]
 pub(crate) mod client;
-#[cfg(any(feature = "client", feature = "prove"))]
+#[cfg(feature = "encoding")]
+mod encoding;
+#[cfg(feature = "encoding")]
 mod protos;
 pub(crate) mod prove_info;
 pub mod recursion;
@@ -300,6 +300,19 @@ pub struct ExecutorEnv {
     pub assumptions: ::prost::alloc::vec::Vec<AssumptionReceipt>,
     #[prost(string, tag = "12")]
     pub segment_path: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag = "13")]
+    pub stack_limit: ::core::option::Option<u32>,
+    /// Mount points served by the client on request.
+    #[prost(string, repeated, tag = "14")]
+    pub mounts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, optional, tag = "15")]
+    pub random_seed: ::core::option::Option<u64>,
+    #[prost(message, optional, tag = "16")]
+    pub input_digest: ::core::option::Option<super::base::Digest>,
+    #[prost(bytes = "vec", tag = "17")]
+    pub preload: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "18")]
+    pub bind_input: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -341,6 +354,8 @@ pub struct SessionInfo {
     pub journal: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "3")]
     pub exit_code: ::core::option::Option<super::base::ExitCode>,
+    #[prost(message, optional, tag = "4")]
+    pub memory: ::core::option::Option<super::base::MemoryStats>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -46,3 +46,13 @@ pub mod exit_code {
         SessionLimit(()),
     }
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemoryStats {
+    #[prost(uint32, tag = "1")]
+    pub heap_high_water: u32,
+    #[prost(uint32, tag = "2")]
+    pub stack_depth: u32,
+    #[prost(uint64, tag = "3")]
+    pub touched_pages: u64,
+}
@@ -16,6 +16,8 @@ pub struct SessionStats {
     pub total_cycles: u64,
     #[prost(uint64, tag = "3")]
     pub user_cycles: u64,
+    #[prost(message, optional, tag = "4")]
+    pub memory: ::core::option::Option<super::base::MemoryStats>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -28,6 +30,9 @@ pub struct Receipt {
     pub journal: ::prost::alloc::vec::Vec<u8>,
     #[prost(message, optional, tag = "4")]
     pub metadata: ::core::option::Option<ReceiptMetadata>,
+    /// set if the journal is external
+    #[prost(message, optional, tag = "5")]
+    pub journal_digest: ::core::option::Option<super::base::Digest>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "client")]
 pub(crate) mod api;
 pub(crate) mod base;
 pub(crate) mod core;
@@ -33,4 +33,17 @@ pub struct SessionStats {
     pub total_cycles: u64,
     /// User cycles run within guest
     pub user_cycles: u64,
+    /// Guest memory usage observed during execution, if the prover reported it
+    pub memory: Option<MemoryStats>,
+}
+
+/// Struct containing information about how much guest memory an execution used
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Highest guest address written within the heap, tracking the heap allocator position
+    pub heap_high_water: u32,
+    /// Maximum stack depth in bytes, measured down from `STACK_TOP`
+    pub stack_depth: u32,
+    /// Count of distinct guest memory pages touched across all segments
+    pub touched_pages: usize,
 }
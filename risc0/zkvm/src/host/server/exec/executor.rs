@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cell::RefCell, io::Write, mem, rc::Rc, sync::Arc, time::Instant};
+use std::{
+    cell::RefCell,
+    io::Write,
+    mem,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use anyhow::{Context as _, Result};
 use risc0_binfmt::{MemoryImage, Program};
@@ -24,12 +31,17 @@ use risc0_circuit_rv32im::prove::emu::{
     },
 };
 use risc0_zkp::core::digest::Digest;
-use risc0_zkvm_platform::{fileno, memory::GUEST_MAX_MEM, PAGE_SIZE};
+use risc0_zkvm_platform::{
+    fileno,
+    memory::{GUEST_MAX_MEM, GUEST_MIN_MEM, PRELOAD_INPUT, STACK_TOP, TEXT_START},
+    PAGE_SIZE, WORD_SIZE,
+};
+use sha2::{Digest as _, Sha256};
 use tempfile::tempdir;
 
 use crate::{
-    host::client::env::SegmentPath, Assumptions, ExecutorEnv, FileSegmentRef, Output, Segment,
-    SegmentRef, Session,
+    host::client::env::SegmentPath, Assumptions, ExecutorEnv, FileSegmentRef, MaybePruned,
+    MemoryStats, Output, Segment, SegmentRef, Session, TraceCallback, TraceEvent,
 };
 
 use super::{
@@ -43,6 +55,7 @@ use super::{
 pub struct ExecutorImpl<'a> {
     env: ExecutorEnv<'a>,
     image: MemoryImage,
+    heap_start: u32,
     pub(crate) syscall_table: SyscallTable<'a>,
     profiler: Option<Rc<RefCell<Profiler>>>,
 }
@@ -56,7 +69,7 @@ impl<'a> ExecutorImpl<'a> {
     /// the guest program is executed to determine how its proof should be
     /// divided into subparts.
     pub fn new(env: ExecutorEnv<'a>, image: MemoryImage) -> Result<Self> {
-        Self::with_details(env, image, None)
+        Self::with_details(env, image, TEXT_START, None)
     }
 
     /// Construct a new [ExecutorImpl] from the ELF binary of the guest program
@@ -75,30 +88,39 @@ impl<'a> ExecutorImpl<'a> {
     ///     .unwrap();
     /// let mut exec = ExecutorImpl::from_elf(env, BENCH_ELF).unwrap();
     /// ```
-    pub fn from_elf(mut env: ExecutorEnv<'a>, elf: &[u8]) -> Result<Self> {
+    pub fn from_elf(env: ExecutorEnv<'a>, elf: &[u8]) -> Result<Self> {
         let program = Program::load_elf(elf, GUEST_MAX_MEM as u32)?;
         let image = MemoryImage::new(&program, PAGE_SIZE as u32)?;
+        // The guest allocator starts at the end of the loaded program.
+        let heap_start = program
+            .image
+            .keys()
+            .next_back()
+            .map_or(TEXT_START, |addr| addr + WORD_SIZE as u32);
 
         let profiler = if env.pprof_out.is_some() {
-            let profiler = Rc::new(RefCell::new(Profiler::new(elf, None)?));
-            env.trace.push(profiler.clone());
-            Some(profiler)
+            Some(Rc::new(RefCell::new(Profiler::new(elf, None)?)))
         } else {
             None
         };
 
-        Self::with_details(env, image, profiler)
+        Self::with_details(env, image, heap_start, profiler)
     }
 
     fn with_details(
         env: ExecutorEnv<'a>,
-        image: MemoryImage,
+        mut image: MemoryImage,
+        heap_start: u32,
         profiler: Option<Rc<RefCell<Profiler>>>,
     ) -> Result<Self> {
+        if !env.preload.is_empty() {
+            image.preload(&env.preload)?;
+        }
         let syscall_table = SyscallTable::new(&env);
         Ok(Self {
             env,
             image,
+            heap_start,
             syscall_table,
             profiler,
         })
@@ -123,10 +145,14 @@ impl<'a> ExecutorImpl<'a> {
     {
         nvtx::range_push!("execute");
 
-        let journal = Journal::default();
+        let journal = match &self.env.journal_writer {
+            Some(writer) => Journal::stream(writer.clone()),
+            None => Journal::default(),
+        };
         self.env
             .posix_io
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .with_write_fd(fileno::JOURNAL, journal.clone());
 
         let segment_limit_po2 = self
@@ -134,13 +160,34 @@ impl<'a> ExecutorImpl<'a> {
             .segment_limit_po2
             .unwrap_or(DEFAULT_SEGMENT_LIMIT_PO2 as u32) as usize;
 
+        let stack_guard = self
+            .env
+            .stack_limit
+            .map_or(GUEST_MIN_MEM as u32, |limit| STACK_TOP - limit);
+
+        // The heap stops short of the preloaded input when there is any.
+        let heap_end = if self.env.preload.is_empty() {
+            GUEST_MAX_MEM as u32
+        } else {
+            PRELOAD_INPUT.start() as u32
+        };
+
+        let mut trace: Vec<Rc<RefCell<dyn TraceCallback + 'a>>> = self
+            .env
+            .trace
+            .iter()
+            .map(|callback| -> Rc<RefCell<dyn TraceCallback + 'a>> {
+                Rc::new(RefCell::new(SharedTraceCallback(callback.clone())))
+            })
+            .collect();
+        if let Some(profiler) = &self.profiler {
+            trace.push(profiler.clone());
+        }
+
         let mut refs = Vec::new();
-        let mut exec = Executor::new(
-            self.image.clone(),
-            self,
-            self.env.input_digest,
-            self.env.trace.clone(),
-        );
+        let mut exec = Executor::new(self.image.clone(), self, self.env.input_digest, trace)
+            .with_stack_guard(stack_guard)
+            .with_heap_region(self.heap_start..heap_end);
 
         let start_time = Instant::now();
         let result = exec.run(segment_limit_po2, self.env.session_limit, |inner| {
@@ -150,16 +197,15 @@ impl<'a> ExecutorImpl<'a> {
                 .then(|| -> Option<Result<_>> {
                     inner
                         .output_digest
-                        .and_then(|digest| {
-                            (digest != Digest::ZERO).then(|| journal.buf.borrow().clone())
-                        })
+                        .and_then(|digest| (digest != Digest::ZERO).then(|| journal.snapshot()))
                         .map(|journal| {
                             Ok(Output {
-                                journal: journal.into(),
+                                journal,
                                 assumptions: Assumptions(
                                     self.env
                                         .assumptions
-                                        .borrow()
+                                        .lock()
+                                        .unwrap()
                                         .accessed
                                         .iter()
                                         .map(|(a, _)| a.clone().into())
@@ -186,18 +232,17 @@ impl<'a> ExecutorImpl<'a> {
         // Set the session_journal to the committed data iff the guest set a non-zero output.
         let session_journal = result
             .output_digest
-            .and_then(|digest| (digest != Digest::ZERO).then(|| journal.buf.take()));
+            .and_then(|digest| (digest != Digest::ZERO).then(|| journal.take()));
         if !result.exit_code.expects_output() && session_journal.is_some() {
             tracing::debug!(
-                "dropping non-empty journal due to exit code {:?}: 0x{}",
+                "dropping non-empty journal due to exit code {:?}",
                 result.exit_code,
-                hex::encode(journal.buf.borrow().as_slice())
             );
         };
 
         // Take (clear out) the list of accessed assumptions.
         // Leave the assumptions cache so it can be used if execution is resumed from pause.
-        let assumptions = mem::take(&mut self.env.assumptions.borrow_mut().accessed);
+        let assumptions = mem::take(&mut self.env.assumptions.lock().unwrap().accessed);
 
         if let Some(profiler) = self.profiler.take() {
             let report = profiler.borrow_mut().finalize_to_vec();
@@ -206,10 +251,15 @@ impl<'a> ExecutorImpl<'a> {
 
         self.image = result.post_image.clone();
 
-        let session = Session::new(
+        let memory = MemoryStats {
+            heap_high_water: result.memory.heap_high_water,
+            stack_depth: STACK_TOP - result.memory.stack_low_water,
+            touched_pages: result.memory.touched_pages,
+        };
+        let mut session = Session::new(
             refs,
-            self.env.input_digest.unwrap_or_default(),
-            session_journal,
+            NewSyscall::input_digest(self)?,
+            None,
             result.exit_code,
             result.post_image,
             assumptions,
@@ -217,7 +267,12 @@ impl<'a> ExecutorImpl<'a> {
             result.total_cycles,
             result.pre_state,
             result.post_state,
+            memory,
         );
+        if session_journal.is_some() {
+            session.external_journal_digest = journal.external_digest();
+        }
+        session.journal = session_journal;
 
         tracing::info_span!("executor").in_scope(|| {
             tracing::info!("execution time: {elapsed:?}");
@@ -257,24 +312,101 @@ impl<'a> NewSyscall for ExecutorImpl<'a> {
         let mut ctx = ContextAdapter { ctx };
         self.syscall_table
             .get_syscall(syscall)
-            .context(format!("Unknown syscall: {syscall:?}"))?
+            .with_context(|| {
+                format!("Unknown syscall: {syscall:?}, no handler is registered for this channel")
+            })?
             .borrow_mut()
             .syscall(syscall, &mut ctx, into_guest)
     }
+
+    fn input_digest(&self) -> Result<Digest> {
+        match &self.env.bound_input {
+            Some(input) => input.lock().unwrap().digest(),
+            None => Ok(self.env.input_digest.unwrap_or_default()),
+        }
+    }
 }
 
-// Capture the journal output in a buffer that we can access afterwards.
-#[derive(Clone, Default)]
-struct Journal {
-    buf: Rc<RefCell<Vec<u8>>>,
+// Forwards trace events to a callback registered in the env, which is shared
+// so that the env can be sent to another thread.
+struct SharedTraceCallback<'a>(Arc<Mutex<dyn TraceCallback + 'a>>);
+
+impl<'a> TraceCallback for SharedTraceCallback<'a> {
+    fn trace_callback(&mut self, event: TraceEvent) -> Result<()> {
+        self.0.lock().unwrap().trace_callback(event)
+    }
 }
 
-impl Write for Journal {
+// Capture the journal output in a buffer that we can access afterwards, or
+// stream it to the journal writer of the env, keeping only a running digest.
+#[derive(Clone)]
+enum Journal<'a> {
+    Buffer(Arc<Mutex<Vec<u8>>>),
+    Stream {
+        writer: Arc<Mutex<dyn Write + 'a>>,
+        hasher: Arc<Mutex<Sha256>>,
+    },
+}
+
+impl<'a> Default for Journal<'a> {
+    fn default() -> Self {
+        Self::Buffer(Default::default())
+    }
+}
+
+impl<'a> Journal<'a> {
+    fn stream(writer: Arc<Mutex<dyn Write + 'a>>) -> Self {
+        Self::Stream {
+            writer,
+            hasher: Default::default(),
+        }
+    }
+
+    // The journal committed so far, for the output of a segment.
+    fn snapshot(&self) -> MaybePruned<Vec<u8>> {
+        match self {
+            Self::Buffer(buf) => MaybePruned::Value(buf.lock().unwrap().clone()),
+            Self::Stream { hasher, .. } => MaybePruned::Pruned(Self::digest(hasher)),
+        }
+    }
+
+    // The journal committed by the guest, which is empty if it was streamed.
+    fn take(&self) -> crate::Journal {
+        match self {
+            Self::Buffer(buf) => crate::Journal::new(mem::take(&mut *buf.lock().unwrap())),
+            Self::Stream { .. } => crate::Journal::default(),
+        }
+    }
+
+    // The digest of the streamed journal, which is not held by the session.
+    fn external_digest(&self) -> Option<Digest> {
+        match self {
+            Self::Buffer(_) => None,
+            Self::Stream { hasher, .. } => Some(Self::digest(hasher)),
+        }
+    }
+
+    fn digest(hasher: &Mutex<Sha256>) -> Digest {
+        Digest::try_from(hasher.lock().unwrap().clone().finalize().as_slice()).unwrap()
+    }
+}
+
+impl<'a> Write for Journal<'a> {
     fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
-        self.buf.borrow_mut().write(bytes)
+        match self {
+            Self::Buffer(buf) => buf.lock().unwrap().write(bytes),
+            Self::Stream { writer, hasher } => {
+                let n = writer.lock().unwrap().write(bytes)?;
+                hasher.lock().unwrap().update(&bytes[..n]);
+                Ok(n)
+            }
+        }
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
-        self.buf.borrow_mut().flush()
+        match self {
+            Self::Buffer(buf) => buf.lock().unwrap().flush(),
+            Self::Stream { writer, .. } => writer.lock().unwrap().flush(),
+        }
     }
 }
@@ -14,19 +14,29 @@
 
 //! Handlers for two-way private I/O between host and guest.
 
-use std::{cell::RefCell, cmp::min, collections::HashMap, rc::Rc, str::from_utf8};
+use std::{
+    cell::RefCell,
+    cmp::min,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    io::{BufReader, Seek, SeekFrom},
+    rc::Rc,
+    str::from_utf8,
+    sync::{Arc, Mutex},
+};
 
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use bytes::Bytes;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use risc0_zkvm_platform::{
     fileno,
     syscall::{
         nr::{
-            SYS_ARGC, SYS_ARGV, SYS_CYCLE_COUNT, SYS_GETENV, SYS_LOG, SYS_PANIC, SYS_RANDOM,
-            SYS_READ, SYS_VERIFY_INTEGRITY, SYS_WRITE,
+            SYS_ARGC, SYS_ARGV, SYS_CLOSE, SYS_CYCLE_COUNT, SYS_GETENV, SYS_LOG, SYS_OPEN,
+            SYS_PANIC, SYS_RANDOM, SYS_READ, SYS_SEEK, SYS_STAT, SYS_VERIFY_INTEGRITY, SYS_WRITE,
         },
-        reg_abi::{REG_A3, REG_A4, REG_A5},
-        SyscallName,
+        reg_abi::{REG_A3, REG_A4, REG_A5, REG_A6},
+        seek, SyscallName,
     },
     WORD_SIZE,
 };
@@ -34,6 +44,7 @@ use risc0_zkvm_platform::{
 use crate::{
     host::client::{
         env::{AssumptionReceipts, ExecutorEnv},
+        mount::{MountFile, MountTable},
         posix_io::PosixIo,
         slice_io::SliceIo,
     },
@@ -88,17 +99,25 @@ impl<'a> SyscallTable<'a> {
         let sys_verify = SysVerify::new(env.assumptions.clone());
 
         let posix_io = env.posix_io.clone();
+        let sys_fs = Rc::new(RefCell::new(SysFs::new(
+            env.mounts.clone(),
+            env.posix_io.clone(),
+        )));
         this.with_syscall(SYS_CYCLE_COUNT, SysCycleCount)
             .with_syscall(SYS_LOG, posix_io.clone())
             .with_syscall(SYS_PANIC, SysPanic)
-            .with_syscall(SYS_RANDOM, SysRandom)
+            .with_syscall(SYS_RANDOM, SysRandom::new(env.random_seed))
             .with_syscall(SYS_GETENV, SysGetenv(env.env_vars.clone()))
             .with_syscall(SYS_READ, posix_io.clone())
             .with_syscall(SYS_WRITE, posix_io)
             .with_syscall(SYS_VERIFY_INTEGRITY, sys_verify)
             .with_syscall(SYS_ARGC, Args(env.args.clone()))
-            .with_syscall(SYS_ARGV, Args(env.args.clone()));
-        for (syscall, handler) in env.slice_io.borrow().inner.iter() {
+            .with_syscall(SYS_ARGV, Args(env.args.clone()))
+            .with_syscall(SYS_OPEN, sys_fs.clone())
+            .with_syscall(SYS_SEEK, sys_fs.clone())
+            .with_syscall(SYS_STAT, sys_fs.clone())
+            .with_syscall(SYS_CLOSE, sys_fs);
+        for (syscall, handler) in env.slice_io.lock().unwrap().inner.iter() {
             let handler = SysSliceIo::new(handler.clone());
             this.inner
                 .insert(syscall.clone(), Rc::new(RefCell::new(handler)));
@@ -178,7 +197,20 @@ impl Syscall for SysPanic {
     }
 }
 
-pub(crate) struct SysRandom;
+/// Provides randomness to the guest, either from the host operating system or,
+/// when a seed is given, from a deterministic ChaCha generator.
+pub(crate) struct SysRandom {
+    rng: Option<ChaCha20Rng>,
+}
+
+impl SysRandom {
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        Self {
+            rng: seed.map(ChaCha20Rng::seed_from_u64),
+        }
+    }
+}
+
 impl Syscall for SysRandom {
     fn syscall(
         &mut self,
@@ -188,7 +220,10 @@ impl Syscall for SysRandom {
     ) -> Result<(u32, u32)> {
         tracing::debug!("SYS_RANDOM: {}", to_guest.len());
         let mut rand_buf = vec![0u8; to_guest.len() * WORD_SIZE];
-        getrandom::getrandom(rand_buf.as_mut_slice())?;
+        match self.rng.as_mut() {
+            Some(rng) => rng.fill_bytes(rand_buf.as_mut_slice()),
+            None => getrandom::getrandom(rand_buf.as_mut_slice())?,
+        }
         bytemuck::cast_slice_mut(to_guest).clone_from_slice(rand_buf.as_slice());
         Ok((0, 0))
     }
@@ -196,11 +231,11 @@ impl Syscall for SysRandom {
 
 #[derive(Clone)]
 pub(crate) struct SysVerify {
-    pub(crate) assumptions: Rc<RefCell<AssumptionReceipts>>,
+    pub(crate) assumptions: Arc<Mutex<AssumptionReceipts>>,
 }
 
 impl SysVerify {
-    pub(crate) fn new(assumptions: Rc<RefCell<AssumptionReceipts>>) -> Self {
+    pub(crate) fn new(assumptions: Arc<Mutex<AssumptionReceipts>>) -> Self {
         Self { assumptions }
     }
 
@@ -216,7 +251,7 @@ impl SysVerify {
 
         // Iterate over the list looking for a matching assumption.
         let mut assumption: Option<(Assumption, AssumptionReceipt)> = None;
-        for cached_assumption in self.assumptions.borrow().cached.iter() {
+        for cached_assumption in self.assumptions.lock().unwrap().cached.iter() {
             let cached_claim_digest = cached_assumption
                 .claim_digest()
                 .context("failed to access claim digest on cached assumption")?;
@@ -270,7 +305,11 @@ impl SysVerify {
         };
 
         // Mark the assumption as accessed, pushing it to the head of the list, and return the success code.
-        self.assumptions.borrow_mut().accessed.insert(0, assumption);
+        self.assumptions
+            .lock()
+            .unwrap()
+            .accessed
+            .insert(0, assumption);
         Ok((0, 0))
     }
 }
@@ -326,15 +365,144 @@ impl Syscall for Args {
     }
 }
 
+/// Read-only access to files mounted with `ExecutorEnvBuilder::mount`.
+///
+/// Opened files are registered as read file descriptors in [PosixIo], so the
+/// guest reads them with SYS_READ like any other file descriptor.
+pub(crate) struct SysFs<'a> {
+    mounts: MountTable,
+    posix_io: Arc<Mutex<PosixIo<'a>>>,
+    files: BTreeMap<u32, Arc<Mutex<BufReader<MountFile>>>>,
+    // Descriptors released by sys_close, reused lowest first.
+    free_fds: BTreeSet<u32>,
+    // The lowest descriptor that has never been allocated.
+    next_fd: u32,
+}
+
+impl<'a> SysFs<'a> {
+    pub(crate) fn new(mounts: MountTable, posix_io: Arc<Mutex<PosixIo<'a>>>) -> Self {
+        Self {
+            mounts,
+            posix_io,
+            files: BTreeMap::new(),
+            free_fds: BTreeSet::new(),
+            next_fd: fileno::JOURNAL_FIELDS + 1,
+        }
+    }
+
+    fn alloc_fd(&mut self, posix_io: &PosixIo) -> Result<u32> {
+        if let Some(fd) = self.free_fds.pop_first() {
+            return Ok(fd);
+        }
+        // Skip descriptors the host registered with the executor environment.
+        // u32::MAX is never allocated, as it tells the guest that the file was
+        // not found.
+        while posix_io.read_fds.contains_key(&self.next_fd)
+            || posix_io.write_fds.contains_key(&self.next_fd)
+        {
+            self.next_fd += 1;
+        }
+        ensure!(self.next_fd < u32::MAX, "Too many open files");
+        let fd = self.next_fd;
+        self.next_fd += 1;
+        Ok(fd)
+    }
+
+    fn load_path(ctx: &mut dyn SyscallContext) -> Result<String> {
+        let path_ptr = ctx.load_register(REG_A3);
+        let path_len = ctx.load_register(REG_A4);
+        let path = ctx.load_region(path_ptr, path_len)?;
+        Ok(String::from_utf8(path)?)
+    }
+
+    fn sys_open(&mut self, ctx: &mut dyn SyscallContext) -> Result<(u32, u32)> {
+        let path = Self::load_path(ctx)?;
+        tracing::trace!("sys_open(path: {path:?})");
+        let Some(file) = self.mounts.open(&path)? else {
+            return Ok((u32::MAX, 0));
+        };
+
+        let posix_io = self.posix_io.clone();
+        let mut posix_io = posix_io.lock().unwrap();
+        let fd = self.alloc_fd(&posix_io)?;
+        let file = Arc::new(Mutex::new(BufReader::new(file)));
+        posix_io.read_fds.insert(fd, file.clone());
+        self.files.insert(fd, file);
+        Ok((fd, 0))
+    }
+
+    fn sys_seek(&mut self, ctx: &mut dyn SyscallContext) -> Result<(u32, u32)> {
+        let fd = ctx.load_register(REG_A3);
+        let offset_lo = ctx.load_register(REG_A4);
+        let offset_hi = ctx.load_register(REG_A5);
+        let whence = ctx.load_register(REG_A6);
+        let offset = (((offset_hi as u64) << 32) | offset_lo as u64) as i64;
+        tracing::trace!("sys_seek(fd: {fd}, offset: {offset}, whence: {whence})");
+
+        let pos = match whence {
+            seek::SET => SeekFrom::Start(offset as u64),
+            seek::CUR => SeekFrom::Current(offset),
+            seek::END => SeekFrom::End(offset),
+            _ => bail!("Bad seek whence {whence}"),
+        };
+        let file = self
+            .files
+            .get(&fd)
+            .ok_or(anyhow!("Bad seek file descriptor {fd}"))?;
+        let pos = file.lock().unwrap().seek(pos).unwrap_or(u64::MAX);
+        Ok(((pos >> 32) as u32, pos as u32))
+    }
+
+    fn sys_stat(&mut self, ctx: &mut dyn SyscallContext) -> Result<(u32, u32)> {
+        let path = Self::load_path(ctx)?;
+        tracing::trace!("sys_stat(path: {path:?})");
+        let size = self.mounts.size(&path)?.unwrap_or(u64::MAX);
+        Ok(((size >> 32) as u32, size as u32))
+    }
+
+    fn sys_close(&mut self, ctx: &mut dyn SyscallContext) -> Result<(u32, u32)> {
+        let fd = ctx.load_register(REG_A3);
+        tracing::trace!("sys_close(fd: {fd})");
+        self.files
+            .remove(&fd)
+            .ok_or(anyhow!("Bad close file descriptor {fd}"))?;
+        self.posix_io.lock().unwrap().read_fds.remove(&fd);
+        self.free_fds.insert(fd);
+        Ok((0, 0))
+    }
+}
+
+impl<'a> Syscall for Rc<RefCell<SysFs<'a>>> {
+    fn syscall(
+        &mut self,
+        syscall: &str,
+        ctx: &mut dyn SyscallContext,
+        _to_guest: &mut [u32],
+    ) -> Result<(u32, u32)> {
+        let mut this = self.borrow_mut();
+        if syscall == SYS_OPEN.as_str() {
+            this.sys_open(ctx)
+        } else if syscall == SYS_SEEK.as_str() {
+            this.sys_seek(ctx)
+        } else if syscall == SYS_STAT.as_str() {
+            this.sys_stat(ctx)
+        } else if syscall == SYS_CLOSE.as_str() {
+            this.sys_close(ctx)
+        } else {
+            bail!("Unknown syscall {syscall}")
+        }
+    }
+}
+
 /// A wrapper around a SliceIo that exposes it as a Syscall handler.
 pub struct SysSliceIo<'a> {
-    handler: Rc<RefCell<dyn SliceIo + 'a>>,
+    handler: Arc<Mutex<dyn SliceIo + 'a>>,
     stored_result: RefCell<Option<Bytes>>,
 }
 
 impl<'a> SysSliceIo<'a> {
     /// Wraps the given [SliceIo] into a [SysSliceIo].
-    pub fn new(handler: Rc<RefCell<dyn SliceIo + 'a>>) -> Self {
+    pub fn new(handler: Arc<Mutex<dyn SliceIo + 'a>>) -> Self {
         Self {
             handler,
             stored_result: RefCell::new(None),
@@ -364,7 +532,7 @@ impl<'a> Syscall for SysSliceIo<'a> {
                 // First call of pair. Send the data from the guest to the SliceIo
                 // and save what it returns.
                 assert_eq!(to_guest.len(), 0);
-                let mut handler = self.handler.borrow_mut();
+                let mut handler = self.handler.lock().unwrap();
                 let result = handler.handle_io(syscall, from_guest.into())?;
                 let len = result.len() as u32;
                 *stored_result = Some(result);
@@ -404,14 +572,14 @@ impl<'a> Syscall for PosixIo<'a> {
     }
 }
 
-impl<'a> Syscall for Rc<RefCell<PosixIo<'a>>> {
+impl<'a> Syscall for Arc<Mutex<PosixIo<'a>>> {
     fn syscall(
         &mut self,
         syscall: &str,
         ctx: &mut dyn SyscallContext,
         to_guest: &mut [u32],
     ) -> Result<(u32, u32)> {
-        self.borrow_mut().syscall(syscall, ctx, to_guest)
+        self.lock().unwrap().syscall(syscall, ctx, to_guest)
     }
 }
 
@@ -444,7 +612,7 @@ impl<'a> PosixIo<'a> {
         let read_all = |mut buf: &mut [u8]| -> Result<usize> {
             let mut tot_nread = 0;
             while !buf.is_empty() {
-                let nread = reader.borrow_mut().read(buf)?;
+                let nread = reader.lock().unwrap().read(buf)?;
                 if nread == 0 {
                     break;
                 }
@@ -489,7 +657,10 @@ impl<'a> PosixIo<'a> {
 
         tracing::trace!("sys_write(fd: {fd}, bytes: {buf_len})");
 
-        writer.borrow_mut().write_all(from_guest_bytes.as_slice())?;
+        writer
+            .lock()
+            .unwrap()
+            .write_all(from_guest_bytes.as_slice())?;
         Ok((0, 0))
     }
 
@@ -507,7 +678,8 @@ impl<'a> PosixIo<'a> {
 
         let msg = format!("R0VM[{}] ", ctx.get_cycle());
         writer
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .write_all(&[msg.as_bytes(), &from_guest, b"\n"].concat())?;
         Ok((0, 0))
     }
@@ -542,6 +714,20 @@ impl<'a> PosixIo<'a> {
 //     let buf_len = ctx.load_register(REG_A4);
 //     let from_guest = ctx.load_region(buf_ptr, buf_len)?;
 
+// SysFs/sys_open, SysFs/sys_stat:
+//     let path_ptr = ctx.load_register(REG_A3);
+//     let path_len = ctx.load_register(REG_A4);
+//     let path = ctx.load_region(path_ptr, path_len)?;
+
+// SysFs/sys_seek:
+//     let fd = ctx.load_register(REG_A3);
+//     let offset_lo = ctx.load_register(REG_A4);
+//     let offset_hi = ctx.load_register(REG_A5);
+//     let whence = ctx.load_register(REG_A6);
+
+// SysFs/sys_close:
+//     let fd = ctx.load_register(REG_A3);
+
 // PosixIo/sys_read:
 //     let fd = ctx.load_register(REG_A3);
 //     let nbytes = ctx.load_register(REG_A4) as usize;
@@ -24,7 +24,8 @@ use bytes::Bytes;
 use risc0_binfmt::{MemoryImage, Program};
 use risc0_zkvm_methods::{
     multi_test::{MultiTestSpec, SYS_MULTI_TEST, SYS_MULTI_TEST_WORDS},
-    BLST_ELF, HELLO_COMMIT_ELF, MULTI_TEST_ELF, RAND_ELF, SLICE_IO_ELF, STANDARD_LIB_ELF,
+    BLST_ELF, BOUND_INPUT_ELF, HELLO_COMMIT_ELF, MULTI_TEST_ELF, RAND_ELF, SLICE_IO_ELF,
+    STANDARD_LIB_ELF, TYPED_ENTRY,
 };
 use risc0_zkvm_platform::{fileno, syscall::nr::SYS_RANDOM, PAGE_SIZE, WORD_SIZE};
 use sha2::{Digest as _, Sha256};
@@ -38,9 +39,9 @@ use crate::{
         },
         testutils,
     },
-    serde::to_vec,
-    sha::{Digest, Digestible},
-    ExecutorEnv, ExecutorImpl, ExitCode,
+    serde::{to_vec, Encoding},
+    sha::{self, Digest, Digestible, Sha256 as _},
+    ExecutorEnv, ExecutorImpl, ExitCode, JournalTree, SendExecutorEnv,
 };
 
 fn run_test(spec: MultiTestSpec) {
@@ -210,6 +211,325 @@ fn host_syscall_words() {
     assert_eq!(session.exit_code, ExitCode::Halted(0));
 }
 
+#[cfg(feature = "async")]
+#[test]
+fn async_io() {
+    struct Echo;
+
+    impl crate::AsyncSliceIo for Echo {
+        fn handle_io(
+            &mut self,
+            _syscall: &str,
+            from_guest: Bytes,
+        ) -> impl std::future::Future<Output = Result<Bytes>> + Send {
+            async move {
+                tokio::task::yield_now().await;
+                Ok(from_guest)
+            }
+        }
+    }
+
+    // Run each executor on a blocking thread, while the single thread of the
+    // runtime polls the handlers.
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let (exit_code, called) = rt.block_on(async {
+        tokio::task::spawn_blocking(|| {
+            let input = to_vec(&MultiTestSpec::SyscallWords).unwrap();
+            let stdin: Vec<u8> = bytemuck::cast_slice(&input).to_vec();
+            let called = Mutex::new(0);
+            let env = ExecutorEnv::builder()
+                .stdin_async(Cursor::new(stdin))
+                .io_callback_async(SYS_MULTI_TEST_WORDS, |buf| {
+                    *called.lock().unwrap() += 1;
+                    async move {
+                        tokio::task::yield_now().await;
+                        Ok(buf)
+                    }
+                })
+                .build()
+                .unwrap();
+            let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+                .unwrap()
+                .run()
+                .unwrap();
+            (session.exit_code, called.into_inner().unwrap())
+        })
+        .await
+        .unwrap()
+    });
+    assert_eq!(exit_code, ExitCode::Halted(0));
+    assert_eq!(called, 1);
+
+    let exit_code = rt.block_on(async {
+        tokio::task::spawn_blocking(|| {
+            let env = ExecutorEnv::builder()
+                .write(&MultiTestSpec::SyscallWords)
+                .unwrap()
+                .slice_io_async(SYS_MULTI_TEST_WORDS.as_str(), Echo)
+                .build()
+                .unwrap();
+            ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+                .unwrap()
+                .run()
+                .unwrap()
+                .exit_code
+        })
+        .await
+        .unwrap()
+    });
+    assert_eq!(exit_code, ExitCode::Halted(0));
+}
+
+#[test]
+fn host_fn() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HostCall { a: u32::MAX, b: 2 })
+        .unwrap()
+        .host_fn(SYS_MULTI_TEST, |(a, b): (u32, u32)| Ok(a as u64 + b as u64))
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    let sum: u64 = session.journal.unwrap().decode().unwrap();
+    assert_eq!(sum, u32::MAX as u64 + 2);
+}
+
+#[test]
+fn execute_on_thread() {
+    let calls = Mutex::new(0);
+    let env = SendExecutorEnv::builder()
+        .write(&MultiTestSpec::HostCall { a: 1, b: 2 })
+        .unwrap()
+        .host_fn(SYS_MULTI_TEST, |(a, b): (u32, u32)| {
+            *calls.lock().unwrap() += 1;
+            Ok(a as u64 + b as u64)
+        })
+        .build()
+        .unwrap();
+    let session = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                ExecutorImpl::from_elf(env.into(), MULTI_TEST_ELF)
+                    .unwrap()
+                    .run()
+                    .unwrap()
+            })
+            .join()
+            .unwrap()
+    });
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    assert_eq!(*calls.lock().unwrap(), 1);
+    let sum: u64 = session.journal.unwrap().decode().unwrap();
+    assert_eq!(sum, 3);
+}
+
+#[test]
+fn serde_encoding_v2() {
+    let input: (Vec<u8>, i16, bool) = ((0..=255).collect(), -3, true);
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::CompactEcho)
+        .unwrap()
+        .serde_encoding(Encoding::V2)
+        .write(&input)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    let journal = session.journal.unwrap();
+    // The length word, 64 words of bytes, and the i16 and bool sharing one
+    // word, even though the guest committed them separately.
+    assert_eq!(journal.bytes.len(), 66 * WORD_SIZE);
+    let output: (Vec<u8>, i16, bool) = journal.decode_with_encoding(Encoding::V2).unwrap();
+    assert_eq!(output, input);
+}
+
+#[test]
+fn journal_tree() {
+    let mut fields = Vec::new();
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::JournalTree)
+        .unwrap()
+        .journal_fields(&mut fields)
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    let journal = session.journal.unwrap();
+    assert_eq!(journal.bytes.len(), 32);
+
+    let tree = JournalTree::decode(&fields).unwrap();
+    assert_eq!(journal.bytes, tree.root().as_bytes());
+    let disclosure = tree.disclose(&["score"]).unwrap();
+    disclosure.verify(&journal).unwrap();
+    let score: u32 = disclosure.get("score").unwrap().decode().unwrap();
+    assert_eq!(score, 42);
+    assert!(disclosure.get("name").is_none());
+}
+
+#[test]
+fn bind_input() {
+    let env = ExecutorEnv::builder()
+        .write(&7u32)
+        .unwrap()
+        .write(&"unread")
+        .unwrap()
+        .bind_input()
+        .build()
+        .unwrap();
+    let input = [to_vec(&7u32).unwrap(), to_vec(&"unread").unwrap()].concat();
+    let session = ExecutorImpl::from_elf(env, BOUND_INPUT_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+    let first: u32 = session.journal.as_ref().unwrap().decode().unwrap();
+    assert_eq!(first, 7);
+    assert_eq!(
+        session.claim().unwrap().input.digest(),
+        *sha::Impl::hash_words(&input)
+    );
+}
+
+#[test]
+fn bind_input_without_host_digest() {
+    let env = ExecutorEnv::builder()
+        .write(&7u32)
+        .unwrap()
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, BOUND_INPUT_ELF)
+        .unwrap()
+        .run()
+        .err()
+        .unwrap();
+    assert!(
+        format!("{err:?}").contains("does not match the input digest"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn bind_input_conflicts_with_input_digest() {
+    let err = ExecutorEnv::builder()
+        .input_digest(Digest::ZERO)
+        .bind_input()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("bind_input"));
+}
+
+#[test]
+fn typed_entry() {
+    let product: u64 = TYPED_ENTRY.execute((u32::MAX, 3)).unwrap();
+    assert_eq!(product, u32::MAX as u64 * 3);
+}
+
+#[test]
+fn preload() {
+    let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let offsets = vec![0, 1023, 1024, data.len() as u32 - 1];
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::Preloaded {
+            offsets: offsets.clone(),
+        })
+        .unwrap()
+        .preload(data.clone())
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+
+    let (len, bytes): (u32, Vec<u8>) = session.journal.as_ref().unwrap().decode().unwrap();
+    assert_eq!(len as usize, data.len());
+    let expected: Vec<u8> = offsets.iter().map(|&i| data[i as usize]).collect();
+    assert_eq!(bytes, expected);
+
+    // Only the touched pages of the preloaded data are paged in.
+    assert!(session.memory.touched_pages < data.len() / PAGE_SIZE);
+
+    let image_id = crate::compute_image_id_with_preload(MULTI_TEST_ELF, &data).unwrap();
+    assert_eq!(session.claim().unwrap().pre.digest(), image_id);
+}
+
+#[test]
+fn journal_writer() {
+    let buffered = ExecutorImpl::from_elf(ExecutorEnv::default(), HELLO_COMMIT_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    let mut streamed_bytes = Vec::new();
+    let env = ExecutorEnv::builder()
+        .journal_writer(&mut streamed_bytes)
+        .build()
+        .unwrap();
+    let streamed = ExecutorImpl::from_elf(env, HELLO_COMMIT_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+
+    assert!(streamed.journal.as_ref().unwrap().bytes.is_empty());
+    assert_eq!(
+        streamed.external_journal_digest,
+        Some(buffered.journal.as_ref().unwrap().digest())
+    );
+    assert_eq!(
+        streamed.claim().unwrap().digest(),
+        buffered.claim().unwrap().digest()
+    );
+    assert_eq!(streamed_bytes, buffered.journal.unwrap().bytes);
+}
+
+#[test]
+fn host_fn_decode_error() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HostCall { a: 100, b: 2 })
+        .unwrap()
+        .host_fn(SYS_MULTI_TEST, |req: Vec<u64>| Ok(req.len() as u64))
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .err()
+        .unwrap();
+    assert!(
+        format!("{err:?}").contains("failed to decode request from guest"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn host_fn_unknown_channel() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HostCall { a: 1, b: 2 })
+        .unwrap()
+        .build()
+        .unwrap();
+    let err = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .err()
+        .unwrap();
+    assert!(format!("{err:?}").contains("Unknown syscall"), "{err:?}");
+}
+
 // Make sure panics in the callback get propagated correctly.
 #[test]
 #[should_panic(expected = "I am panicking from here!")]
@@ -229,6 +549,58 @@ fn host_syscall_callback_panic() {
     assert_eq!(session.exit_code, ExitCode::Halted(0));
 }
 
+type MountedFileResult = (Option<Vec<u8>>, Option<u64>, Option<Vec<u8>>);
+
+fn run_mounted_file(path: &str, env: &mut crate::ExecutorEnvBuilder) -> MountedFileResult {
+    let env = env
+        .write(&MultiTestSpec::MountedFile { path: path.into() })
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    session.journal.unwrap().decode().unwrap()
+}
+
+#[test]
+fn mount_bytes() {
+    let (contents, size, tail) = run_mounted_file(
+        "/etc/config.toml",
+        ExecutorEnv::builder().mount("/etc/config.toml", b"debug = true".as_slice()),
+    );
+    assert_eq!(contents.unwrap(), b"debug = true");
+    assert_eq!(size, Some(12));
+    assert_eq!(tail.unwrap(), b"ebug = true");
+}
+
+#[test]
+fn mount_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(dir.path().join("sub/input.txt"), b"hello").unwrap();
+
+    let (contents, size, tail) = run_mounted_file(
+        "/data/sub/input.txt",
+        ExecutorEnv::builder().mount("/data", dir.path()),
+    );
+    assert_eq!(contents.unwrap(), b"hello");
+    assert_eq!(size, Some(5));
+    assert_eq!(tail.unwrap(), b"ello");
+}
+
+#[test]
+fn mount_not_found() {
+    let (contents, size, tail) = run_mounted_file(
+        "/data/missing.txt",
+        ExecutorEnv::builder().mount("/etc/config.toml", b"debug = true".as_slice()),
+    );
+    assert_eq!(contents, None);
+    assert_eq!(size, None);
+    assert_eq!(tail, None);
+}
+
 #[test]
 fn sha_accel() {
     run_test(MultiTestSpec::ShaConforms);
@@ -1004,6 +1376,35 @@ fn profiler() {
     assert!(check(&fr, addr), "{fr:#?} {addr}");
 }
 
+#[test]
+fn heap_past_preload() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HeapPastPreload)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = ExecutorImpl::from_elf(env, MULTI_TEST_ELF)
+        .unwrap()
+        .run()
+        .unwrap();
+    assert_eq!(session.exit_code, ExitCode::Halted(0));
+}
+
+#[test]
+fn heap_stops_at_preload() {
+    // With preloaded input, the same allocation runs out of memory instead of
+    // overwriting the preloaded data.
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::HeapPastPreload)
+        .unwrap()
+        .preload(vec![1u8; 16])
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let err = exec.run().err().unwrap();
+    assert!(err.to_string().contains("Out of memory"), "{err:?}");
+}
+
 #[test]
 fn oom() {
     let env = ExecutorEnv::builder()
@@ -1016,6 +1417,29 @@ fn oom() {
     assert!(err.to_string().contains("Out of memory"), "{err:?}");
 }
 
+#[test]
+fn stack_overflow() {
+    let env = ExecutorEnv::builder()
+        .write(&MultiTestSpec::DoNothing)
+        .unwrap()
+        .stack_limit(64)
+        .build()
+        .unwrap();
+    let mut exec = ExecutorImpl::from_elf(env, MULTI_TEST_ELF).unwrap();
+    let err = exec.run().err().unwrap();
+    assert!(err.to_string().contains("stack overflow at pc"), "{err:?}");
+}
+
+#[test]
+fn stack_limit_too_large() {
+    let err = ExecutorEnv::builder()
+        .stack_limit(u32::MAX)
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("stack limit"), "{err:?}");
+}
+
 #[test]
 fn memory_access() {
     fn access_memory(addr: u32) -> Result<ExitCode> {
@@ -1110,6 +1534,29 @@ fn post_state_digest_randomization() {
     assert_eq!(post_state_digests.len(), 1);
 }
 
+#[test]
+fn random_seed() {
+    let post_state_digest = |seed: u64| {
+        let env = ExecutorEnv::builder().random_seed(seed).build().unwrap();
+        ExecutorImpl::from_elf(env, HELLO_COMMIT_ELF)
+            .unwrap()
+            .run()
+            .unwrap()
+            .segments
+            .last()
+            .unwrap()
+            .resolve()
+            .unwrap()
+            .inner
+            .post_state
+            .digest()
+    };
+
+    // The same seed produces the same memory image entropy, and so the same post state.
+    assert_eq!(post_state_digest(42), post_state_digest(42));
+    assert_ne!(post_state_digest(42), post_state_digest(43));
+}
+
 #[test]
 fn aligned_alloc() {
     run_test(MultiTestSpec::AlignedAlloc);
@@ -57,12 +57,9 @@ impl ProverServer for DevModeProver {
         }
 
         let claim = session.claim()?;
-        let receipt = Receipt::new(
-            InnerReceipt::Fake(FakeReceipt {
-                claim: claim.into(),
-            }),
-            session.journal.clone().unwrap_or_default().bytes,
-        );
+        let receipt = session.receipt(InnerReceipt::Fake(FakeReceipt {
+            claim: claim.into(),
+        }));
 
         Ok(ProveInfo {
             receipt,
@@ -114,11 +111,8 @@ impl ProverServer for DevModeProver {
     }
 
     fn compress(&self, _opts: &ProverOpts, receipt: &Receipt) -> Result<Receipt> {
-        Ok(Receipt::new(
-            InnerReceipt::Fake(FakeReceipt {
-                claim: receipt.claim()?,
-            }),
-            receipt.journal.bytes.clone(),
-        ))
+        Ok(receipt.with_inner(InnerReceipt::Fake(FakeReceipt {
+            claim: receipt.claim()?,
+        })))
     }
 }
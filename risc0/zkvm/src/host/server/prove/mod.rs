@@ -166,28 +166,19 @@ pub trait ProverServer {
                 ReceiptKind::Composite => Ok(receipt.clone()),
                 ReceiptKind::Succinct => {
                     let succinct_receipt = self.composite_to_succinct(inner)?;
-                    Ok(Receipt::new(
-                        InnerReceipt::Succinct(succinct_receipt),
-                        receipt.journal.bytes.clone(),
-                    ))
+                    Ok(receipt.with_inner(InnerReceipt::Succinct(succinct_receipt)))
                 }
                 ReceiptKind::Groth16 => {
                     let succinct_receipt = self.composite_to_succinct(inner)?;
                     let groth16_receipt = self.succinct_to_groth16(&succinct_receipt)?;
-                    Ok(Receipt::new(
-                        InnerReceipt::Groth16(groth16_receipt),
-                        receipt.journal.bytes.clone(),
-                    ))
+                    Ok(receipt.with_inner(InnerReceipt::Groth16(groth16_receipt)))
                 }
             },
             InnerReceipt::Succinct(inner) => match opts.receipt_kind {
                 ReceiptKind::Composite | ReceiptKind::Succinct => Ok(receipt.clone()),
                 ReceiptKind::Groth16 => {
                     let groth16_receipt = self.succinct_to_groth16(inner)?;
-                    Ok(Receipt::new(
-                        InnerReceipt::Groth16(groth16_receipt),
-                        receipt.journal.bytes.clone(),
-                    ))
+                    Ok(receipt.with_inner(InnerReceipt::Groth16(groth16_receipt)))
                 }
             },
             InnerReceipt::Groth16(_) => match opts.receipt_kind {
@@ -27,7 +27,7 @@ use crate::{
     },
     receipt_claim::{MaybePruned, Merge, Unknown},
     sha::Digestible,
-    CompositeReceipt, Output, ProverOpts, Receipt, ReceiptClaim, Segment, Session, VerifierContext,
+    CompositeReceipt, Output, ProverOpts, ReceiptClaim, Segment, Session, VerifierContext,
 };
 
 /// An implementation of a Prover that runs locally.
@@ -79,10 +79,9 @@ impl ProverServer for ProverImpl {
             .output
             .merge_with(
                 &session
-                    .journal
-                    .as_ref()
-                    .map(|journal| Output {
-                        journal: MaybePruned::Pruned(journal.digest()),
+                    .journal_digest()
+                    .map(|digest| Output {
+                        journal: MaybePruned::Pruned(digest),
                         assumptions: assumptions.into(),
                     })
                     .into(),
@@ -124,24 +123,15 @@ impl ProverServer for ProverImpl {
 
         // Compress the receipt to the requested level.
         let receipt = match self.opts.receipt_kind {
-            ReceiptKind::Composite => Receipt::new(
-                InnerReceipt::Composite(composite_receipt),
-                session.journal.clone().unwrap_or_default().bytes,
-            ),
+            ReceiptKind::Composite => session.receipt(InnerReceipt::Composite(composite_receipt)),
             ReceiptKind::Succinct => {
                 let succinct_receipt = self.composite_to_succinct(&composite_receipt)?;
-                Receipt::new(
-                    InnerReceipt::Succinct(succinct_receipt),
-                    session.journal.clone().unwrap_or_default().bytes,
-                )
+                session.receipt(InnerReceipt::Succinct(succinct_receipt))
             }
             ReceiptKind::Groth16 => {
                 let succinct_receipt = self.composite_to_succinct(&composite_receipt)?;
                 let groth16_receipt = self.succinct_to_groth16(&succinct_receipt)?;
-                Receipt::new(
-                    InnerReceipt::Groth16(groth16_receipt),
-                    session.journal.clone().unwrap_or_default().bytes,
-                )
+                session.receipt(InnerReceipt::Groth16(groth16_receipt))
             }
         };
 
@@ -481,9 +481,12 @@ mod docker {
     use crate::{
         get_prover_server,
         host::server::prove::{DevModeProver, ProverServer},
-        ExecutorEnv, ExecutorImpl, ExitCode, FakeReceipt, InnerReceipt, ProverOpts, Receipt,
-        ReceiptKind,
+        sha::Digestible,
+        ExecutorEnv, ExecutorImpl, ExitCode, FakeReceipt, Groth16Receipt,
+        Groth16ReceiptVerifierParameters, InnerReceipt, ProverOpts, Receipt, ReceiptKind,
+        VerifierContext,
     };
+    use risc0_groth16::{ProofJson, PublicInputsJson, Seal};
     use risc0_zkp::core::digest::Digest;
     use risc0_zkvm_methods::{
         multi_test::MultiTestSpec, MULTI_TEST_ELF, MULTI_TEST_ID, VERIFY_ELF,
@@ -622,6 +625,70 @@ mod docker {
         // fake receipts
         self::test_fake_compress(groth16_receipt);
     }
+
+    // Format a big-endian integer in decimal, as in the JSON files written by snarkJS.
+    fn decimal(be_bytes: &[u8]) -> String {
+        // Little-endian base 10 digits.
+        let mut digits = vec![0u8];
+        for byte in be_bytes {
+            let mut carry = *byte as u32;
+            for digit in digits.iter_mut() {
+                let value = *digit as u32 * 256 + carry;
+                *digit = (value % 10) as u8;
+                carry = value / 10;
+            }
+            while carry > 0 {
+                digits.push((carry % 10) as u8);
+                carry /= 10;
+            }
+        }
+        digits.iter().rev().map(|d| char::from(b'0' + d)).collect()
+    }
+
+    // The two halves of a digest as public inputs, as in `risc0_groth16::split_digest`.
+    fn split_digest(digest: Digest) -> [String; 2] {
+        let mut be_bytes = digest.as_bytes().to_vec();
+        be_bytes.reverse();
+        [decimal(&be_bytes[16..]), decimal(&be_bytes[..16])]
+    }
+
+    #[test]
+    fn groth16_receipt_from_json() {
+        let receipt = generate_receipt(ProverOpts::groth16());
+        let groth16 = receipt.inner.groth16().unwrap();
+        let claim = receipt.claim().unwrap();
+        let params = Groth16ReceiptVerifierParameters::default();
+
+        // Write the proof as snarkJS does in proof.json and public.json.
+        let seal = Seal::from_vec(&groth16.seal).unwrap();
+        let proof: ProofJson = serde_json::from_value(serde_json::json!({
+            "pi_a": [decimal(&seal.a[0]), decimal(&seal.a[1]), "1"],
+            "pi_b": [
+                [decimal(&seal.b[0][1]), decimal(&seal.b[0][0])],
+                [decimal(&seal.b[1][1]), decimal(&seal.b[1][0])],
+                ["1", "0"],
+            ],
+            "pi_c": [decimal(&seal.c[0]), decimal(&seal.c[1]), "1"],
+            "protocol": "groth16",
+            "curve": "bn128",
+        }))
+        .unwrap();
+        let mut bn254_control_id = params.bn254_control_id.as_bytes().to_vec();
+        bn254_control_id.reverse();
+        let public_inputs = PublicInputsJson {
+            values: [
+                split_digest(params.control_root).to_vec(),
+                split_digest(claim.digest()).to_vec(),
+                vec![decimal(&bn254_control_id)],
+            ]
+            .concat(),
+        };
+
+        let ctx = VerifierContext::default();
+        let imported = Groth16Receipt::from_json(proof, public_inputs, claim, &ctx).unwrap();
+        assert_eq!(imported.seal, groth16.seal);
+        imported.verify_integrity_with_context(&ctx).unwrap();
+    }
 }
 
 mod sys_verify {
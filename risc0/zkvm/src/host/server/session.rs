@@ -18,15 +18,18 @@
 use std::{collections::BTreeSet, fs, path::PathBuf};
 
 use anyhow::{ensure, Result};
-use risc0_binfmt::{MemoryImage, SystemState};
+use risc0_binfmt::{Digestible, MemoryImage, SystemState};
 use risc0_circuit_rv32im::prove::segment::Segment as CircuitSegment;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    host::{client::env::SegmentPath, prove_info::SessionStats},
-    sha::Digest,
-    Assumption, AssumptionReceipt, Assumptions, ExitCode, Journal, MaybePruned, Output,
-    ReceiptClaim,
+    host::{
+        client::env::SegmentPath,
+        prove_info::{MemoryStats, SessionStats},
+    },
+    sha::{self, Digest},
+    Assumption, AssumptionReceipt, Assumptions, ExitCode, InnerReceipt, Journal, MaybePruned,
+    Output, Receipt, ReceiptClaim,
 };
 
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
@@ -55,6 +58,11 @@ pub struct Session {
     /// The data publicly committed by the guest program.
     pub journal: Option<Journal>,
 
+    /// The SHA-256 digest of the journal, if it was streamed to
+    /// `ExecutorEnvBuilder::journal_writer`, in which case [Session::journal]
+    /// is empty.
+    pub external_journal_digest: Option<Digest>,
+
     /// The [ExitCode] of the session.
     pub exit_code: ExitCode,
 
@@ -80,6 +88,9 @@ pub struct Session {
 
     /// The system state of the final [MemoryImage] at the end of execution.
     pub post_state: SystemState,
+
+    /// Guest memory usage observed during execution.
+    pub memory: MemoryStats,
 }
 
 /// The execution trace of a portion of a program.
@@ -143,11 +154,13 @@ impl Session {
         total_cycles: u64,
         pre_state: SystemState,
         post_state: SystemState,
+        memory: MemoryStats,
     ) -> Self {
         Self {
             segments,
             input,
             journal: journal.map(Journal::new),
+            external_journal_digest: None,
             exit_code,
             post_image,
             assumptions,
@@ -156,6 +169,23 @@ impl Session {
             total_cycles,
             pre_state,
             post_state,
+            memory,
+        }
+    }
+
+    /// The digest of the journal committed by the guest, if any.
+    pub(crate) fn journal_digest(&self) -> Option<Digest> {
+        self.journal.as_ref().map(|journal| {
+            self.external_journal_digest
+                .unwrap_or_else(|| journal.digest::<sha::Impl>())
+        })
+    }
+
+    /// Construct a [Receipt] of this session's journal with the given [InnerReceipt].
+    pub(crate) fn receipt(&self, inner: InnerReceipt) -> Receipt {
+        match self.external_journal_digest {
+            Some(digest) => Receipt::new_external(inner, digest),
+            None => Receipt::new(inner, self.journal.clone().unwrap_or_default()),
         }
     }
 
@@ -176,7 +206,10 @@ impl Session {
                 .as_ref()
                 .map(|journal| -> Result<_> {
                     Ok(Output {
-                        journal: journal.bytes.clone().into(),
+                        journal: match self.external_journal_digest {
+                            Some(digest) => MaybePruned::Pruned(digest),
+                            None => journal.bytes.clone().into(),
+                        },
                         assumptions: Assumptions(
                             self.assumptions
                                 .iter()
@@ -223,6 +256,12 @@ impl Session {
         tracing::info!("total cycles: {}", self.total_cycles);
         tracing::info!("user cycles: {}", self.user_cycles);
         tracing::debug!("cycle efficiency: {}%", cycle_efficiency as u32);
+        tracing::info!(
+            "heap high-water mark: 0x{:08x}",
+            self.memory.heap_high_water
+        );
+        tracing::info!("stack depth: {} bytes", self.memory.stack_depth);
+        tracing::info!("touched pages: {}", self.memory.touched_pages);
     }
 
     /// Returns stats for the session
@@ -233,6 +272,7 @@ impl Session {
             segments: self.segments.len(),
             total_cycles: self.total_cycles,
             user_cycles: self.user_cycles,
+            memory: Some(self.memory),
         }
     }
 }
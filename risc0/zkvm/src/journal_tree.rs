@@ -0,0 +1,482 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! [JournalTree] and associated types for selective disclosure of guest outputs.
+//!
+//! A guest that commits a [JournalTree] with
+//! [env::commit_tree][crate::guest::env::commit_tree] writes only the 32-byte
+//! Merkle root of its named fields to the journal. The fields themselves are
+//! sent to the host privately, and the host can later produce a
+//! [JournalDisclosure] that opens a chosen subset of the fields. Undisclosed
+//! subtrees are [pruned][MaybePruned::Pruned] to their digest, so a verifier
+//! can check the disclosed fields against the journal of a verified receipt.
+//!
+//! Each field is hashed together with a random 128-bit salt chosen when it is
+//! added to the tree. The salt of a field is revealed only when the field is
+//! disclosed, so the digest of an undisclosed field cannot be checked against
+//! guesses of its value, even if the value has few possibilities. The names of
+//! the fields and the shape of the tree are not hidden: a verifier learns how
+//! many fields the tree has.
+//!
+//! # Example
+//!
+//! ```rust
+//! use risc0_zkvm::{Journal, JournalTree};
+//!
+//! let mut tree = JournalTree::new();
+//! tree.add("name", &"alice").unwrap();
+//! tree.add("score", &42u32).unwrap();
+//!
+//! // The guest commits the root as its journal.
+//! let journal = Journal::new(tree.root().as_bytes().to_vec());
+//!
+//! // The host discloses only the score.
+//! let disclosure = tree.disclose(&["score"]).unwrap();
+//! disclosure.verify(&journal).unwrap();
+//! let score: u32 = disclosure.get("score").unwrap().decode().unwrap();
+//! assert_eq!(score, 42);
+//! assert!(disclosure.get("name").is_none());
+//! ```
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use risc0_binfmt::{tagged_struct, Digestible};
+use risc0_zkp::core::digest::Digest;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    serde::{from_slice, to_vec},
+    sha::{Impl, Sha256},
+    Journal, MaybePruned,
+};
+
+/// Errors from building a [JournalTree] or checking a [JournalDisclosure].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JournalTreeError {
+    /// A field with this name was already added to the tree.
+    DuplicateField(String),
+
+    /// No field with this name exists in the tree.
+    FieldNotFound(String),
+
+    /// The value of a field could not be serialized.
+    Serialize(crate::serde::Error),
+
+    /// The root of the disclosure does not match the journal.
+    RootMismatch {
+        /// The root computed from the disclosure.
+        disclosed: Digest,
+        /// The bytes of the journal.
+        journal: Vec<u8>,
+    },
+}
+
+impl fmt::Display for JournalTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateField(name) => write!(f, "journal field {name:?} was already added"),
+            Self::FieldNotFound(name) => write!(f, "journal field {name:?} not found"),
+            Self::Serialize(err) => write!(f, "failed to serialize journal field: {err}"),
+            Self::RootMismatch { disclosed, journal } => write!(
+                f,
+                "disclosed journal root {disclosed} does not match journal 0x{}",
+                hex::encode(journal)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JournalTreeError {}
+
+impl From<crate::serde::Error> for JournalTreeError {
+    fn from(err: crate::serde::Error) -> Self {
+        Self::Serialize(err)
+    }
+}
+
+/// A named field of a [JournalTree].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JournalField {
+    /// The name of the field, unique within its tree.
+    pub name: String,
+
+    /// The value of the field, serialized with the [risc0 serializer](crate::serde).
+    pub value: Vec<u32>,
+
+    /// The random salt hashed with the field, which blinds the digest of an
+    /// undisclosed field.
+    pub salt: [u32; 4],
+}
+
+impl JournalField {
+    /// Decode the value of this field by using the [risc0 deserializer](crate::serde).
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, crate::serde::Error> {
+        from_slice(&self.value)
+    }
+}
+
+impl Digestible for JournalField {
+    /// Hash the [JournalField] to get a digest of the struct.
+    fn digest<S: Sha256>(&self) -> Digest {
+        tagged_struct::<S>(
+            "risc0.JournalField",
+            &[
+                *S::hash_bytes(self.name.as_bytes()),
+                *S::hash_words(&self.value),
+                *S::hash_words(&self.salt),
+            ],
+            &[],
+        )
+    }
+}
+
+/// A node of a journal Merkle tree.
+///
+/// Leaves are [JournalField]s, in the order they were added to the tree. An
+/// odd node at the end of a level is carried up to the next level unchanged.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum JournalNode {
+    /// A single field.
+    Field(JournalField),
+
+    /// An inner node with its left and right children.
+    Branch(Box<MaybePruned<JournalNode>>, Box<MaybePruned<JournalNode>>),
+}
+
+impl Digestible for JournalNode {
+    /// Hash the [JournalNode] to get a digest of the subtree.
+    fn digest<S: Sha256>(&self) -> Digest {
+        match self {
+            Self::Field(field) => field.digest::<S>(),
+            Self::Branch(left, right) => tagged_struct::<S>(
+                "risc0.JournalBranch",
+                &[left.digest::<S>(), right.digest::<S>()],
+                &[],
+            ),
+        }
+    }
+}
+
+/// A set of named fields committed to the journal as a Merkle root.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JournalTree {
+    fields: Vec<JournalField>,
+}
+
+impl JournalTree {
+    /// Construct an empty [JournalTree].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode a [JournalTree] sent by the guest with
+    /// [env::commit_tree][crate::guest::env::commit_tree].
+    pub fn decode(bytes: &[u8]) -> Result<Self, crate::serde::Error> {
+        from_slice(bytes)
+    }
+
+    /// Add a field, serializing `value` with the [risc0 serializer](crate::serde).
+    ///
+    /// The field is salted with random data, which in the guest is provided by
+    /// the host.
+    pub fn add<T: Serialize + ?Sized>(
+        &mut self,
+        name: &str,
+        value: &T,
+    ) -> Result<&mut Self, JournalTreeError> {
+        if self.get(name).is_some() {
+            return Err(JournalTreeError::DuplicateField(name.to_string()));
+        }
+        self.fields.push(JournalField {
+            name: name.to_string(),
+            value: to_vec(value)?,
+            salt: random_salt(),
+        });
+        Ok(self)
+    }
+
+    /// Returns the fields of this tree, in the order they were added.
+    pub fn fields(&self) -> &[JournalField] {
+        &self.fields
+    }
+
+    /// Returns the field with the given name.
+    pub fn get(&self, name: &str) -> Option<&JournalField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the Merkle root of this tree, which is [Digest::ZERO] if the
+    /// tree is empty.
+    pub fn root(&self) -> Digest {
+        self.build(|_| true).digest::<Impl>()
+    }
+
+    /// Produce a [JournalDisclosure] opening only the fields with the given
+    /// names.
+    pub fn disclose(&self, names: &[&str]) -> Result<JournalDisclosure, JournalTreeError> {
+        if let Some(name) = names.iter().find(|name| self.get(name).is_none()) {
+            return Err(JournalTreeError::FieldNotFound(name.to_string()));
+        }
+        Ok(JournalDisclosure {
+            root: self.build(|field| names.contains(&field.name.as_str())),
+        })
+    }
+
+    /// Builds the Merkle tree, pruning every subtree that contains no field
+    /// selected by `keep`.
+    fn build(&self, keep: impl Fn(&JournalField) -> bool) -> MaybePruned<JournalNode> {
+        let mut level: Vec<(MaybePruned<JournalNode>, bool)> = self
+            .fields
+            .iter()
+            .map(|field| {
+                let node = JournalNode::Field(field.clone());
+                (prune_unless(node, keep(field)), keep(field))
+            })
+            .collect();
+        if level.is_empty() {
+            return MaybePruned::Pruned(Digest::ZERO);
+        }
+
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            let mut nodes = level.into_iter();
+            while let Some((left, keep_left)) = nodes.next() {
+                match nodes.next() {
+                    Some((right, keep_right)) => {
+                        let node = JournalNode::Branch(Box::new(left), Box::new(right));
+                        let keep = keep_left || keep_right;
+                        next.push((prune_unless(node, keep), keep));
+                    }
+                    None => next.push((left, keep_left)),
+                }
+            }
+            level = next;
+        }
+        level.pop().unwrap().0
+    }
+}
+
+fn random_salt() -> [u32; 4] {
+    let mut salt = [0u32; 4];
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        risc0_zkvm_platform::syscall::sys_rand(salt.as_mut_ptr(), salt.len())
+    };
+    #[cfg(not(target_os = "zkvm"))]
+    getrandom::getrandom(bytemuck::cast_slice_mut(&mut salt)).unwrap();
+    salt
+}
+
+fn prune_unless(node: JournalNode, keep: bool) -> MaybePruned<JournalNode> {
+    if keep {
+        MaybePruned::Value(node)
+    } else {
+        MaybePruned::Pruned(node.digest::<Impl>())
+    }
+}
+
+/// A subset of the fields of a [JournalTree], with the digests needed to
+/// check them against the tree's root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct JournalDisclosure {
+    root: MaybePruned<JournalNode>,
+}
+
+impl JournalDisclosure {
+    /// Returns the Merkle root of the tree the fields were disclosed from.
+    pub fn root(&self) -> Digest {
+        self.root.digest::<Impl>()
+    }
+
+    /// Returns the disclosed fields, in tree order.
+    pub fn fields(&self) -> Vec<&JournalField> {
+        fn walk<'a>(node: &'a MaybePruned<JournalNode>, out: &mut Vec<&'a JournalField>) {
+            match node {
+                MaybePruned::Value(JournalNode::Field(field)) => out.push(field),
+                MaybePruned::Value(JournalNode::Branch(left, right)) => {
+                    walk(left, out);
+                    walk(right, out);
+                }
+                MaybePruned::Pruned(_) => {}
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(&self.root, &mut out);
+        out
+    }
+
+    /// Returns the disclosed field with the given name.
+    pub fn get(&self, name: &str) -> Option<&JournalField> {
+        self.fields().into_iter().find(|field| field.name == name)
+    }
+
+    /// Check that the disclosed fields belong to the tree committed by `journal`.
+    ///
+    /// The journal should be taken from a receipt that has been verified.
+    pub fn verify(&self, journal: &Journal) -> Result<(), JournalTreeError> {
+        let root = self.root();
+        if journal.bytes != root.as_bytes() {
+            return Err(JournalTreeError::RootMismatch {
+                disclosed: root,
+                journal: journal.bytes.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use super::*;
+
+    fn tree(len: u32) -> JournalTree {
+        let mut tree = JournalTree::new();
+        for i in 0..len {
+            tree.add(&format!("field{i}"), &(i * 10)).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn disclose_each_field() {
+        for len in 1..8 {
+            let tree = tree(len);
+            let journal = Journal::new(tree.root().as_bytes().to_vec());
+            for i in 0..len {
+                let name = format!("field{i}");
+                let disclosure = tree.disclose(&[&name]).unwrap();
+                disclosure.verify(&journal).unwrap();
+                assert_eq!(disclosure.fields().len(), 1);
+                assert_eq!(
+                    disclosure.get(&name).unwrap().decode::<u32>().unwrap(),
+                    i * 10
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn disclose_none_and_all() {
+        let tree = tree(5);
+        let none = tree.disclose(&[]).unwrap();
+        assert!(none.fields().is_empty());
+        assert_eq!(none.root(), tree.root());
+
+        let all = tree.disclose(&["field0", "field1", "field2", "field3", "field4"]);
+        assert_eq!(all.unwrap().fields().len(), 5);
+    }
+
+    #[test]
+    fn salted_fields() {
+        // Trees with the same fields have different roots, and the digest of
+        // an undisclosed field does not reveal its value.
+        let tree_a = tree(2);
+        let tree_b = tree(2);
+        assert_eq!(
+            tree_a.get("field0").unwrap().value,
+            tree_b.get("field0").unwrap().value
+        );
+        assert_ne!(tree_a.root(), tree_b.root());
+
+        let digest = |disclosure: JournalDisclosure| match disclosure.root {
+            MaybePruned::Value(JournalNode::Branch(left, _)) => left.digest::<Impl>(),
+            _ => unreachable!(),
+        };
+        let hidden_a = digest(tree_a.disclose(&["field1"]).unwrap());
+        let hidden_b = digest(tree_b.disclose(&["field1"]).unwrap());
+        assert_ne!(hidden_a, hidden_b);
+
+        // The salt of a disclosed field is revealed along with its value.
+        let disclosure = tree_a.disclose(&["field0"]).unwrap();
+        assert_eq!(
+            disclosure.get("field0").unwrap().salt,
+            tree_a.get("field0").unwrap().salt
+        );
+    }
+
+    #[test]
+    fn tampered_field() {
+        let tree = tree(4);
+        let journal = Journal::new(tree.root().as_bytes().to_vec());
+        let mut disclosure = tree.disclose(&["field2"]).unwrap();
+        fn find(node: &mut MaybePruned<JournalNode>) -> Option<&mut JournalField> {
+            match node {
+                MaybePruned::Value(JournalNode::Field(field)) => Some(field),
+                MaybePruned::Value(JournalNode::Branch(left, right)) => {
+                    find(left).or_else(|| find(right))
+                }
+                MaybePruned::Pruned(_) => None,
+            }
+        }
+        find(&mut disclosure.root).unwrap().value = to_vec(&99u32).unwrap();
+        assert!(matches!(
+            disclosure.verify(&journal),
+            Err(JournalTreeError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let mut tree = tree(2);
+        assert_eq!(
+            tree.add("field1", &0u32).err(),
+            Some(JournalTreeError::DuplicateField("field1".into()))
+        );
+        assert_eq!(
+            tree.disclose(&["missing"]).err(),
+            Some(JournalTreeError::FieldNotFound("missing".into()))
+        );
+        assert_eq!(JournalTree::new().root(), Digest::ZERO);
+
+        // The risc0 serializer does not support sequences of unknown length.
+        use serde::ser::SerializeSeq as _;
+        struct Unsized;
+        impl Serialize for Unsized {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_seq(None)?.end()
+            }
+        }
+        assert_eq!(
+            tree.add("unsized", &Unsized).err(),
+            Some(JournalTreeError::Serialize(
+                crate::serde::Error::NotSupported
+            ))
+        );
+        assert!(tree.get("unsized").is_none());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let tree = tree(3);
+        let bytes: Vec<u8> = bytemuck::cast_slice(&to_vec(&tree).unwrap()).to_vec();
+        assert_eq!(JournalTree::decode(&bytes).unwrap(), tree);
+
+        let disclosure = tree.disclose(&["field1"]).unwrap();
+        let words = to_vec(&disclosure).unwrap();
+        let decoded: JournalDisclosure = from_slice(&words).unwrap();
+        assert_eq!(decoded, disclosure);
+    }
+}
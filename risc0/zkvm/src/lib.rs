@@ -51,6 +51,7 @@
 //!
 //! | Feature          | Target(s)         | Implies    | Description                                                                                                                                                  |
 //! | ---------------- | ----------------- | ---------- | ------------------------------------------------------------------------------------------------------------------------------------------------------------ |
+//! | abi              | all               |            | Enables Ethereum ABI encoding of journals, with `env::commit_abi` in the guest and `Journal::decode_abi` on the host.                                        |
 //! | client           | all except rv32im | std        | Enables the client API.                                                                                                                                      |
 //! | cuda             |                   | prove, std | Enables CUDA GPU acceleration for the prover. Requires CUDA toolkit to be installed.                                                                         |
 //! | disable-dev-mode | all except rv32im |            | Disables dev mode so that proving and verifying may not be faked. Used to prevent a misplaced `RISC0_DEV_MODE` from breaking security in production systems. |
@@ -72,9 +73,12 @@
 
 extern crate alloc;
 
+#[cfg(feature = "abi")]
+pub mod abi;
 pub mod guest;
 #[cfg(not(target_os = "zkvm"))]
 mod host;
+mod journal_tree;
 mod receipt;
 mod receipt_claim;
 pub mod serde;
@@ -90,6 +94,11 @@ pub use bytes::Bytes;
 pub use risc0_binfmt::{ExitCode, InvalidExitCodeError, SystemState};
 pub use risc0_zkvm_platform::{align_up, declare_syscall, memory::GUEST_MAX_MEM, PAGE_SIZE};
 
+#[cfg(all(not(target_os = "zkvm"), feature = "async"))]
+pub use self::host::client::async_io::AsyncSliceIo;
+pub use self::journal_tree::{
+    JournalDisclosure, JournalField, JournalNode, JournalTree, JournalTreeError,
+};
 pub use self::receipt_claim::{
     Assumption, Assumptions, Input, MaybePruned, Output, PrunedValueError, ReceiptClaim,
 };
@@ -120,10 +129,13 @@ pub use {
             client::Client as ApiClient, Asset, AssetRequest, Connector, SegmentInfo, SessionInfo,
         },
         client::{
-            env::{ExecutorEnv, ExecutorEnvBuilder},
+            env::{ExecutorEnv, ExecutorEnvBuilder, LocalHandlers, SendExecutorEnv, SendHandlers},
+            method::GuestMethod,
+            mount::MountSource,
             prove::{
-                bonsai::BonsaiProver, default_executor, default_prover, external::ExternalProver,
-                Executor, Prover, ProverOpts, ReceiptKind,
+                bonsai::BonsaiProver, default_executor, default_prover, default_shared_executor,
+                default_shared_prover, external::ExternalProver, Executor, Prover, ProverOpts,
+                ReceiptKind,
             },
         },
     },
@@ -132,22 +144,24 @@ pub use {
 #[cfg(not(target_os = "zkvm"))]
 pub use {
     self::host::{
-        prove_info::{ProveInfo, SessionStats},
+        prove_info::{MemoryStats, ProveInfo, SessionStats},
         recursion::{ALLOWED_CONTROL_IDS, ALLOWED_CONTROL_ROOT},
     },
-    risc0_binfmt::compute_image_id,
+    risc0_binfmt::{compute_image_id, compute_image_id_with_preload},
     risc0_circuit_rv32im::control_id::POSEIDON2_CONTROL_IDS,
     risc0_groth16::Seal as Groth16Seal,
 };
 
 pub use receipt::{
-    AssumptionReceipt, CompositeReceipt, CompositeReceiptVerifierParameters, FakeReceipt,
-    InnerAssumptionReceipt, InnerReceipt, Journal, Receipt, ReceiptMetadata, SegmentReceipt,
+    AssumptionReceipt, BatchVerificationError, ClaimMismatch, CompositeReceipt,
+    CompositeReceiptVerifierParameters, FakeReceipt, InnerAssumptionReceipt, InnerReceipt, Journal,
+    MultiVerifierContext, Receipt, ReceiptMetadata, SegmentReceipt,
     SegmentReceiptVerifierParameters, SuccinctReceipt, SuccinctReceiptVerifierParameters,
-    VerifierContext,
+    VerificationReport, VerifierContext, VerifierLimits,
 };
 //#[cfg(any(not(target_os = "zkvm"), feature = "std"))]
-pub use receipt::{Groth16Receipt, Groth16ReceiptVerifierParameters};
+pub use receipt::{Groth16Receipt, Groth16ReceiptVerifierParameters, EVM_VERIFY_SELECTOR};
+pub use risc0_groth16::SealFormat as Groth16SealFormat;
 
 use semver::Version;
 
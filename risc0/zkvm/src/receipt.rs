@@ -15,15 +15,17 @@
 //! Manages the output and cryptographic data for a proven computation.
 
 pub(crate) mod composite;
+mod explain;
 pub(crate) mod groth16;
 pub(crate) mod merkle;
+mod release;
 pub(crate) mod segment;
 pub(crate) mod succinct;
 
 use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
-use core::fmt::Debug;
+use core::{fmt::Debug, mem::size_of_val};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use risc0_core::field::baby_bear::BabyBear;
 use risc0_zkp::{
     core::{
@@ -40,15 +42,16 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 // Make succinct receipt available through this `receipt` module.
 use crate::{
     receipt_claim::Unknown,
-    serde::{from_slice, Error},
+    serde::{from_slice, from_slice_with_encoding, Encoding, Error},
     sha::{Digestible, Sha256},
     Assumption, Assumptions, MaybePruned, Output, ReceiptClaim,
 };
 
-pub use self::groth16::{Groth16Receipt, Groth16ReceiptVerifierParameters};
+pub use self::groth16::{Groth16Receipt, Groth16ReceiptVerifierParameters, EVM_VERIFY_SELECTOR};
 
 pub use self::{
     composite::{CompositeReceipt, CompositeReceiptVerifierParameters},
+    explain::{ClaimMismatch, VerificationReport},
     segment::{SegmentReceipt, SegmentReceiptVerifierParameters},
     succinct::{SuccinctReceipt, SuccinctReceiptVerifierParameters},
 };
@@ -130,17 +133,49 @@ pub struct Receipt {
 
 impl Receipt {
     /// Construct a new Receipt
-    pub fn new(inner: InnerReceipt, journal: Vec<u8>) -> Self {
+    pub fn new(inner: InnerReceipt, journal: impl Into<Journal>) -> Self {
         let metadata = ReceiptMetadata {
             verifier_parameters: inner.verifier_parameters(),
+            external_journal_digest: None,
         };
         Self {
             inner,
-            journal: Journal::new(journal),
+            journal: journal.into(),
             metadata,
         }
     }
 
+    /// Construct a new [Receipt] whose journal is stored elsewhere, referenced only by the
+    /// SHA-256 digest of its bytes.
+    ///
+    /// This is the case for receipts of an execution whose journal was streamed to
+    /// `ExecutorEnvBuilder::journal_writer`. The receipt verifies as usual, but the caller is
+    /// responsible for checking that the stored bytes match [Receipt::journal_digest].
+    pub fn new_external(inner: InnerReceipt, journal_digest: Digest) -> Self {
+        let mut receipt = Self::new(inner, Journal::default());
+        receipt.metadata.external_journal_digest = Some(journal_digest);
+        receipt
+    }
+
+    /// The digest of the journal attested to by this receipt.
+    ///
+    /// This is [ReceiptMetadata::external_journal_digest] if the journal is stored elsewhere, and
+    /// the digest of [Receipt::journal] otherwise.
+    pub fn journal_digest(&self) -> Digest {
+        self.metadata
+            .external_journal_digest
+            .unwrap_or_else(|| self.journal.digest())
+    }
+
+    /// Construct a receipt of the same journal with a different [InnerReceipt], e.g. when
+    /// compressing it.
+    #[cfg(feature = "prove")]
+    pub(crate) fn with_inner(&self, inner: InnerReceipt) -> Self {
+        let mut receipt = Self::new(inner, self.journal.clone());
+        receipt.metadata.external_journal_digest = self.metadata.external_journal_digest;
+        receipt
+    }
+
     /// Verify that this receipt proves a successful execution of the zkVM from
     /// the given `image_id`.
     ///
@@ -152,6 +187,23 @@ impl Receipt {
         self.verify_with_context(&VerifierContext::default(), image_id)
     }
 
+    /// Verify that this receipt proves a successful execution of the zkVM from the given
+    /// `image_id`, using the context in `ctx` for the release that produced it.
+    ///
+    /// The context is selected by the [ReceiptMetadata::verifier_parameters] of this receipt, and
+    /// the receipt is then verified as with [Receipt::verify_with_context]. Returns
+    /// [VerificationError::VerifierParametersMissing] if no allowlisted context matches.
+    pub fn verify_with_multi_context(
+        &self,
+        ctx: &MultiVerifierContext,
+        image_id: impl Into<Digest>,
+    ) -> Result<(), VerificationError> {
+        let ctx = ctx
+            .context_for(&self.metadata.verifier_parameters)
+            .ok_or(VerificationError::VerifierParametersMissing)?;
+        self.verify_with_context(ctx, image_id)
+    }
+
     /// Verify that this receipt proves a successful execution of the zkVM from the given
     /// `image_id`.
     ///
@@ -164,6 +216,7 @@ impl Receipt {
         ctx: &VerifierContext,
         image_id: impl Into<Digest>,
     ) -> Result<(), VerificationError> {
+        ctx.limits.check_journal(self.journal.bytes.len())?;
         if self.inner.verifier_parameters() != self.metadata.verifier_parameters {
             return Err(VerificationError::VerifierParametersMismatch {
                 expected: self.inner.verifier_parameters(),
@@ -173,11 +226,108 @@ impl Receipt {
 
         tracing::debug!("Receipt::verify_with_context");
         self.inner.verify_integrity_with_context(ctx)?;
+        self.verify_claim(image_id)
+    }
 
+    /// Verify a batch of receipts, each against the `image_id` it is paired with.
+    ///
+    /// This performs the same checks as calling [Receipt::verify_with_context] on each receipt,
+    /// except that [Groth16Receipt] seals are verified together with
+    /// [Groth16Receipt::verify_integrity_batch], which is much faster than verifying them one at a
+    /// time. On failure, the returned error identifies the first receipt that did not verify.
+    pub fn verify_batch<'a>(
+        receipts: impl IntoIterator<Item = (&'a Receipt, Digest)>,
+        ctx: &VerifierContext,
+    ) -> Result<(), BatchVerificationError> {
+        tracing::debug!("Receipt::verify_batch");
+        Self::verify_batch_with(receipts, |_| Ok(ctx))
+    }
+
+    /// Verify a batch of receipts, each against the `image_id` it is paired with, using the context
+    /// in `ctx` for the release that produced each receipt.
+    ///
+    /// This performs the same checks as calling [Receipt::verify_with_multi_context] on each
+    /// receipt, with the [Groth16Receipt] seals of each release verified together as in
+    /// [Receipt::verify_batch]. On failure, the returned error identifies the first receipt that
+    /// did not verify.
+    pub fn verify_batch_with_multi_context<'a>(
+        receipts: impl IntoIterator<Item = (&'a Receipt, Digest)>,
+        ctx: &MultiVerifierContext,
+    ) -> Result<(), BatchVerificationError> {
+        tracing::debug!("Receipt::verify_batch_with_multi_context");
+        Self::verify_batch_with(receipts, |receipt| {
+            ctx.context_for(&receipt.metadata.verifier_parameters)
+                .ok_or(VerificationError::VerifierParametersMissing)
+        })
+    }
+
+    fn verify_batch_with<'a, 'c>(
+        receipts: impl IntoIterator<Item = (&'a Receipt, Digest)>,
+        context_for: impl Fn(&Receipt) -> Result<&'c VerifierContext, VerificationError>,
+    ) -> Result<(), BatchVerificationError> {
+        // Groth16 seals are deferred and checked in a batch per context. All other checks stop at
+        // the first failing receipt, so only the seals before it need checking to find the first
+        // receipt that did not verify.
+        let mut groth16_batches: Vec<(&VerifierContext, Vec<usize>, Vec<&Groth16Receipt<_>>)> =
+            Vec::new();
+        let mut failure = None;
+        for (index, (receipt, image_id)) in receipts.into_iter().enumerate() {
+            let result = (|| {
+                let ctx = context_for(receipt)?;
+                ctx.limits.check_journal(receipt.journal.bytes.len())?;
+                if receipt.inner.verifier_parameters() != receipt.metadata.verifier_parameters {
+                    return Err(VerificationError::VerifierParametersMismatch {
+                        expected: receipt.inner.verifier_parameters(),
+                        received: receipt.metadata.verifier_parameters,
+                    });
+                }
+                match &receipt.inner {
+                    InnerReceipt::Groth16(inner) => {
+                        match groth16_batches
+                            .iter_mut()
+                            .find(|(batch_ctx, ..)| core::ptr::eq(*batch_ctx, ctx))
+                        {
+                            Some((_, indices, batch)) => {
+                                indices.push(index);
+                                batch.push(inner);
+                            }
+                            None => groth16_batches.push((ctx, vec![index], vec![inner])),
+                        }
+                    }
+                    inner => inner.verify_integrity_with_context(ctx)?,
+                }
+                receipt.verify_claim(image_id)
+            })();
+            if let Err(error) = result {
+                failure = Some(BatchVerificationError { index, error });
+                break;
+            }
+        }
+
+        for (ctx, indices, batch) in groth16_batches {
+            if let Err(err) = Groth16Receipt::verify_integrity_batch(batch, ctx) {
+                let index = indices[err.index];
+                if failure
+                    .as_ref()
+                    .map_or(true, |failure| index < failure.index)
+                {
+                    failure = Some(BatchVerificationError {
+                        index,
+                        error: err.error,
+                    });
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+
+    /// Check that the claim on the verified receipt is a successful execution of `image_id` that
+    /// committed to the journal of this receipt.
+    fn verify_claim(&self, image_id: impl Into<Digest>) -> Result<(), VerificationError> {
         // Check that the claim on the verified receipt matches what was expected. Since we have
         // constrained all field in the ReceiptClaim, we can directly construct the expected digest
         // and do not need to open the claim digest on the inner receipt.
-        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(self.journal.digest()));
+        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(self.journal_digest()));
         if expected_claim.digest() != self.inner.claim()?.digest() {
             tracing::debug!(
                 "receipt claim does not match expected claim:\nreceipt: {:#?}\nexpected: {:#?}",
@@ -208,6 +358,7 @@ impl Receipt {
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
+        ctx.limits.check_journal(self.journal.bytes.len())?;
         if self.inner.verifier_parameters() != self.metadata.verifier_parameters {
             return Err(VerificationError::VerifierParametersMismatch {
                 expected: self.inner.verifier_parameters(),
@@ -226,7 +377,7 @@ impl Receipt {
             .map_err(|_| VerificationError::ReceiptFormatError)?;
 
         let expected_output = claim.exit_code.expects_output().then(|| Output {
-            journal: MaybePruned::Pruned(self.journal.digest()),
+            journal: MaybePruned::Pruned(self.journal_digest()),
             // TODO(#982): It would be reasonable for this method to allow integrity verification
             // for receipts that have a non-empty assumptions list, but it is not supported here
             // because we don't have a enough information to open the assumptions list unless we
@@ -235,7 +386,9 @@ impl Receipt {
         });
 
         if claim.output.digest() != expected_output.digest() {
-            let empty_output = claim.output.is_none() && self.journal.bytes.is_empty();
+            let empty_output = claim.output.is_none()
+                && self.journal.bytes.is_empty()
+                && self.metadata.external_journal_digest.is_none();
             if !empty_output {
                 tracing::debug!(
                     "journal: 0x{}, expected output digest: 0x{}, decoded output digest: 0x{}",
@@ -275,9 +428,26 @@ impl Journal {
     }
 
     /// Decode the journal bytes by using the [risc0 deserializer](crate::serde).
+    ///
+    /// This uses [Encoding::V1], the default. Use [Journal::decode_with_encoding] for a journal
+    /// the guest committed with another encoding set by `env::set_journal_serde_encoding`.
     pub fn decode<T: DeserializeOwned>(&self) -> Result<T, Error> {
         from_slice(&self.bytes)
     }
+
+    /// Decode the journal bytes, which the guest committed using the given [Encoding].
+    pub fn decode_with_encoding<T: DeserializeOwned>(
+        &self,
+        encoding: Encoding,
+    ) -> Result<T, Error> {
+        from_slice_with_encoding(&self.bytes, encoding)
+    }
+
+    /// Decode the journal bytes as in Solidity's `abi.decode`, using the [ABI encoding](crate::abi).
+    #[cfg(feature = "abi")]
+    pub fn decode_abi<T: crate::abi::AbiType>(&self) -> Result<T, crate::abi::Error> {
+        crate::abi::decode(&self.bytes)
+    }
 }
 
 impl risc0_binfmt::Digestible for Journal {
@@ -286,6 +456,12 @@ impl risc0_binfmt::Digestible for Journal {
     }
 }
 
+impl From<Vec<u8>> for Journal {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
 impl AsRef<[u8]> for Journal {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
@@ -445,6 +621,12 @@ pub struct ReceiptMetadata {
     /// corresponding to multiple versions of a proof system or circuit) and it is ambiguous which
     /// one should be used to attempt verification of a receipt.
     pub verifier_parameters: Digest,
+
+    /// The SHA-256 digest of the journal, if it is stored elsewhere and [Receipt::journal] is
+    /// empty.
+    ///
+    /// Unlike the other metadata, this is checked against the seal during verification.
+    pub external_journal_digest: Option<Digest>,
 }
 
 /// An assumption attached to a guest execution as a result of calling
@@ -649,6 +831,12 @@ impl From<InnerReceipt> for InnerAssumptionReceipt {
     }
 }
 
+/// Releases of the zkVM with a [VerifierContext] preset, as full version strings.
+///
+/// The parameters of each release are pinned as literals in the `release` module, so that receipts
+/// produced by a release keep verifying when the current circuits or Groth16 setup change.
+const RELEASES: &[&str] = &["1.0.0-rc.5"];
+
 /// Context available to the verification process.
 #[non_exhaustive]
 pub struct VerifierContext {
@@ -663,6 +851,9 @@ pub struct VerifierContext {
 
     /// Parameters for verification of [Groth16Receipt].
     pub groth16_verifier_parameters: Option<Groth16ReceiptVerifierParameters>,
+
+    /// Limits on the size of receipts accepted by the verification process.
+    pub limits: VerifierLimits,
 }
 
 impl VerifierContext {
@@ -673,6 +864,7 @@ impl VerifierContext {
             segment_verifier_parameters: None,
             succinct_verifier_parameters: None,
             groth16_verifier_parameters: None,
+            limits: VerifierLimits::default(),
         }
     }
 
@@ -718,6 +910,12 @@ impl VerifierContext {
         self
     }
 
+    /// Return [VerifierContext] with the given [VerifierLimits] set.
+    pub fn with_limits(mut self, limits: VerifierLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Parameters for verification of [CompositeReceipt].
     ///
     /// Made up of the verifier parameters for each other receipt type. Returns none if any of the
@@ -729,8 +927,231 @@ impl VerifierContext {
             groth16: self.groth16_verifier_parameters.as_ref()?.clone().into(),
         })
     }
+
+    /// Return the [VerifierContext] with the verifier parameters of the given release of the zkVM,
+    /// as its full version string (e.g. `"1.0.0-rc.5"`).
+    ///
+    /// Receipts produced by that release verify with the returned context. The supported releases
+    /// are listed by [VerifierContext::releases].
+    pub fn for_release(release: &str) -> Result<Self> {
+        match release {
+            "1.0.0-rc.5" => release::v1_0_0_rc_5::verifier_context(),
+            _ => bail!(
+                "no verifier parameters are known for release {release}; supported releases are {}",
+                RELEASES.join(", ")
+            ),
+        }
+    }
+
+    /// Releases for which [VerifierContext::for_release] has a preset.
+    pub fn releases() -> &'static [&'static str] {
+        RELEASES
+    }
+
+    /// Digests of the verifier parameters of the receipts this context can verify, as found in
+    /// [ReceiptMetadata::verifier_parameters].
+    pub fn verifier_parameters_digests(&self) -> Vec<Digest> {
+        [
+            self.composite_verifier_parameters().map(|x| x.digest()),
+            self.succinct_verifier_parameters
+                .as_ref()
+                .map(|x| x.digest()),
+            self.groth16_verifier_parameters
+                .as_ref()
+                .map(|x| x.digest()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Limits on the size of receipts accepted by the verifier.
+///
+/// Receipts from untrusted sources are checked against these limits before any expensive work is
+/// done to verify them, and are rejected with [VerificationError::ResourceLimitExceeded] if they
+/// exceed any of them. Each limit is `None` when unbounded, which is the default.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct VerifierLimits {
+    /// Maximum size in bytes of the seal of any receipt, including those of each segment and
+    /// assumption of a [CompositeReceipt].
+    pub max_seal_bytes: Option<usize>,
+
+    /// Maximum number of segments in a [CompositeReceipt].
+    pub max_segments: Option<usize>,
+
+    /// Maximum number of assumption receipts in a [CompositeReceipt].
+    pub max_assumptions: Option<usize>,
+
+    /// Maximum size in bytes of the [Journal] of a [Receipt].
+    pub max_journal_bytes: Option<usize>,
+
+    /// Maximum size in bytes of the canonical encoding of a [Receipt], checked by
+    /// `Receipt::decode_with_limits` before anything is decoded.
+    pub max_encoded_bytes: Option<usize>,
+}
+
+impl VerifierLimits {
+    /// Return [VerifierLimits] with the given maximum seal size, in bytes.
+    pub fn with_max_seal_bytes(mut self, max: usize) -> Self {
+        self.max_seal_bytes = Some(max);
+        self
+    }
+
+    /// Return [VerifierLimits] with the given maximum number of segments.
+    pub fn with_max_segments(mut self, max: usize) -> Self {
+        self.max_segments = Some(max);
+        self
+    }
+
+    /// Return [VerifierLimits] with the given maximum number of assumption receipts.
+    pub fn with_max_assumptions(mut self, max: usize) -> Self {
+        self.max_assumptions = Some(max);
+        self
+    }
+
+    /// Return [VerifierLimits] with the given maximum journal size, in bytes.
+    pub fn with_max_journal_bytes(mut self, max: usize) -> Self {
+        self.max_journal_bytes = Some(max);
+        self
+    }
+
+    /// Return [VerifierLimits] with the given maximum size of an encoded receipt, in bytes.
+    pub fn with_max_encoded_bytes(mut self, max: usize) -> Self {
+        self.max_encoded_bytes = Some(max);
+        self
+    }
+
+    /// Check the sizes of the given receipt against these limits, without verifying it.
+    pub fn check(&self, receipt: &Receipt) -> Result<(), VerificationError> {
+        self.check_journal(receipt.journal.bytes.len())?;
+        match &receipt.inner {
+            InnerReceipt::Composite(inner) => self.check_composite(inner),
+            InnerReceipt::Succinct(inner) => self.check_seal(size_of_val(inner.seal.as_slice())),
+            InnerReceipt::Groth16(inner) => self.check_seal(inner.seal.len()),
+            InnerReceipt::Fake(_) => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_journal(&self, journal_bytes: usize) -> Result<(), VerificationError> {
+        check_limit("journal size", journal_bytes, self.max_journal_bytes)
+    }
+
+    pub(crate) fn check_seal(&self, seal_bytes: usize) -> Result<(), VerificationError> {
+        check_limit("seal size", seal_bytes, self.max_seal_bytes)
+    }
+
+    pub(crate) fn check_counts(
+        &self,
+        segments: usize,
+        assumptions: usize,
+    ) -> Result<(), VerificationError> {
+        check_limit("segment count", segments, self.max_segments)?;
+        check_limit("assumption count", assumptions, self.max_assumptions)
+    }
+
+    pub(crate) fn check_composite(
+        &self,
+        receipt: &CompositeReceipt,
+    ) -> Result<(), VerificationError> {
+        self.check_counts(receipt.segments.len(), receipt.assumption_receipts.len())?;
+        for segment in receipt.segments.iter() {
+            self.check_seal(size_of_val(segment.seal.as_slice()))?;
+        }
+        for assumption in receipt.assumption_receipts.iter() {
+            match assumption {
+                InnerAssumptionReceipt::Composite(inner) => self.check_composite(inner)?,
+                InnerAssumptionReceipt::Succinct(inner) => {
+                    self.check_seal(size_of_val(inner.seal.as_slice()))?
+                }
+                InnerAssumptionReceipt::Groth16(inner) => self.check_seal(inner.seal.len())?,
+                InnerAssumptionReceipt::Fake(_) => {}
+            }
+        }
+        Ok(())
+    }
 }
 
+pub(crate) fn check_limit(
+    resource: &'static str,
+    size: usize,
+    limit: Option<usize>,
+) -> Result<(), VerificationError> {
+    match limit {
+        Some(limit) if size > limit => Err(VerificationError::ResourceLimitExceeded {
+            resource,
+            size,
+            limit,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// An allowlist of [VerifierContext]s, such as those of several releases of the zkVM.
+///
+/// Each receipt is verified with the context matching its [ReceiptMetadata::verifier_parameters],
+/// so that receipts produced by any of the allowlisted releases are accepted. See
+/// [Receipt::verify_with_multi_context].
+#[non_exhaustive]
+pub struct MultiVerifierContext {
+    /// The allowlisted contexts.
+    pub contexts: Vec<VerifierContext>,
+}
+
+impl MultiVerifierContext {
+    /// Create a [MultiVerifierContext] accepting receipts verifiable by any of the given contexts.
+    pub fn new(contexts: impl IntoIterator<Item = VerifierContext>) -> Self {
+        Self {
+            contexts: contexts.into_iter().collect(),
+        }
+    }
+
+    /// Create a [MultiVerifierContext] accepting receipts from any of the given releases. See
+    /// [VerifierContext::for_release].
+    pub fn for_releases<'a>(releases: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        Ok(Self::new(
+            releases
+                .into_iter()
+                .map(VerifierContext::for_release)
+                .collect::<Result<Vec<_>>>()?,
+        ))
+    }
+
+    /// Return the first allowlisted context able to verify receipts with the given verifier
+    /// parameters digest.
+    pub fn context_for(&self, verifier_parameters: &Digest) -> Option<&VerifierContext> {
+        self.contexts.iter().find(|ctx| {
+            ctx.verifier_parameters_digests()
+                .contains(verifier_parameters)
+        })
+    }
+}
+
+/// Error returned when verifying a batch of receipts, identifying the receipt that failed.
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub struct BatchVerificationError {
+    /// Index in the batch of the receipt that failed verification.
+    pub index: usize,
+
+    /// The reason the receipt failed verification.
+    pub error: VerificationError,
+}
+
+impl core::fmt::Display for BatchVerificationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "receipt {} failed verification: {}",
+            self.index, self.error
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BatchVerificationError {}
+
 impl Default for VerifierContext {
     fn default() -> Self {
         Self {
@@ -738,19 +1159,61 @@ impl Default for VerifierContext {
             segment_verifier_parameters: Some(Default::default()),
             succinct_verifier_parameters: Some(Default::default()),
             groth16_verifier_parameters: Some(Default::default()),
+            limits: VerifierLimits::default(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{FakeReceipt, InnerReceipt, Receipt};
+    use super::{
+        BatchVerificationError, CompositeReceipt, FakeReceipt, Groth16Receipt,
+        Groth16ReceiptVerifierParameters, InnerAssumptionReceipt, InnerReceipt, Journal,
+        MultiVerifierContext, Receipt, SegmentReceipt, VerifierContext, VerifierLimits,
+    };
     use crate::{
-        sha::{Digest, DIGEST_BYTES},
-        MaybePruned,
+        sha::{Digest, Digestible, DIGEST_BYTES},
+        MaybePruned, ReceiptClaim,
     };
+    use risc0_zkp::core::digest::digest;
     use risc0_zkp::verify::VerificationError;
 
+    fn fake_receipts(journal: &Journal) -> [Receipt; 2] {
+        let inner = InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+            Digest::ZERO,
+            journal.bytes.clone(),
+        )));
+        [
+            Receipt::new(inner.clone(), journal.clone()),
+            Receipt::new_external(inner, journal.digest()),
+        ]
+    }
+
+    #[test]
+    fn external_journal() {
+        let journal = Journal::new(b"hello".to_vec());
+        let [internal, external] = fake_receipts(&journal);
+        assert!(external.journal.bytes.is_empty());
+        assert_eq!(external.journal_digest(), internal.journal_digest());
+        external.verify_claim(Digest::ZERO).unwrap();
+
+        let mut mismatched = external.clone();
+        mismatched.metadata.external_journal_digest = Some(Digest::ZERO);
+        assert!(matches!(
+            mismatched.verify_claim(Digest::ZERO),
+            Err(VerificationError::ClaimDigestMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(any(feature = "client", feature = "prove"))]
+    fn bincode_round_trip() {
+        for receipt in fake_receipts(&Journal::new(b"hello".to_vec())) {
+            let bytes = bincode::serialize(&receipt).unwrap();
+            assert_eq!(bincode::deserialize::<Receipt>(&bytes).unwrap(), receipt);
+        }
+    }
+
     #[test]
     fn mangled_version_info_should_error() {
         let mut mangled_receipt = Receipt::new(
@@ -790,4 +1253,227 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn verify_batch_identifies_failing_receipt() {
+        let journal = b"journal".to_vec();
+        let groth16_receipt = Receipt::new(
+            InnerReceipt::Groth16(Groth16Receipt {
+                seal: vec![0u8; 256],
+                claim: ReceiptClaim::ok(Digest::ZERO, journal.clone()).into(),
+                verifier_parameters: Groth16ReceiptVerifierParameters::default().digest(),
+            }),
+            journal,
+        );
+        let mut mangled_receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Pruned(Digest::ZERO),
+            }),
+            vec![],
+        );
+        mangled_receipt.metadata.verifier_parameters = Digest::from([1u8; DIGEST_BYTES]);
+
+        let ctx = Default::default();
+        Receipt::verify_batch([], &ctx).unwrap();
+        // The invalid Groth16 seal is reported even though the batch check runs after the
+        // failure of the later receipt.
+        assert_eq!(
+            Receipt::verify_batch(
+                [
+                    (&groth16_receipt, Digest::ZERO),
+                    (&mangled_receipt, Digest::ZERO)
+                ],
+                &ctx
+            )
+            .err()
+            .unwrap()
+            .index,
+            0
+        );
+        assert_eq!(
+            Receipt::verify_batch(
+                [
+                    (&mangled_receipt, Digest::ZERO),
+                    (&groth16_receipt, Digest::ZERO)
+                ],
+                &ctx
+            )
+            .err()
+            .unwrap(),
+            BatchVerificationError {
+                index: 0,
+                error: VerificationError::VerifierParametersMismatch {
+                    expected: Digest::ZERO,
+                    received: Digest::from([1u8; DIGEST_BYTES]),
+                }
+            }
+        );
+        assert_eq!(
+            Receipt::verify_batch(
+                [
+                    (&groth16_receipt, Digest::ZERO),
+                    (&groth16_receipt, Digest::ZERO)
+                ],
+                &ctx
+            )
+            .err()
+            .unwrap()
+            .index,
+            0
+        );
+
+        assert_eq!(
+            Receipt::verify_batch_with_multi_context(
+                [(&groth16_receipt, Digest::ZERO)],
+                &MultiVerifierContext::new([])
+            )
+            .err()
+            .unwrap(),
+            BatchVerificationError {
+                index: 0,
+                error: VerificationError::VerifierParametersMissing,
+            }
+        );
+        let err = Receipt::verify_batch_with_multi_context(
+            [
+                (&groth16_receipt, Digest::ZERO),
+                (&groth16_receipt, Digest::ZERO),
+            ],
+            &MultiVerifierContext::new([VerifierContext::default()]),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(err.index, 0);
+        assert_ne!(err.error, VerificationError::VerifierParametersMissing);
+    }
+
+    // The verifier parameters of each release must never change, or receipts produced by that
+    // release will stop verifying.
+    #[test]
+    fn release_verifier_parameters_are_pinned() {
+        let ctx = VerifierContext::for_release("1.0.0-rc.5").unwrap();
+        assert_eq!(
+            ctx.segment_verifier_parameters.as_ref().unwrap().digest(),
+            digest!("929e6fe659097966a442d0919e56fc13a2efffa2ef9e88b2ed37bc7eb7686f03")
+        );
+        assert_eq!(
+            ctx.verifier_parameters_digests(),
+            vec![
+                digest!("ca2ffad49af55723cd58531dce9f9c06310b368c57857aa040e8f1468dd429b1"),
+                digest!("2b77ca0b15690e4ccf3264268ade0daece7eb811ce5259cbb8b52a9c2bba12e5"),
+                digest!("f7ca3f2cfe1cde247417d317852c696a0e4a42acaf51205e6af54a58fa6c64fe"),
+            ]
+        );
+        assert!(VerifierContext::for_release("0.1").is_err());
+        assert!(VerifierContext::for_release("1.0").is_err());
+    }
+
+    #[test]
+    fn multi_context_selects_by_verifier_parameters() {
+        let ctx = MultiVerifierContext::for_releases(VerifierContext::releases().iter().copied())
+            .unwrap();
+        let groth16_params = Groth16ReceiptVerifierParameters::default().digest();
+        assert!(ctx.context_for(&groth16_params).is_some());
+        assert!(ctx.context_for(&Digest::ZERO).is_none());
+
+        let mut mangled_receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt {
+                claim: MaybePruned::Pruned(Digest::ZERO),
+            }),
+            vec![],
+        );
+        mangled_receipt.metadata.verifier_parameters = Digest::from([1u8; DIGEST_BYTES]);
+        assert_eq!(
+            mangled_receipt
+                .verify_with_multi_context(&ctx, Digest::ZERO)
+                .err()
+                .unwrap(),
+            VerificationError::VerifierParametersMissing
+        );
+    }
+
+    #[test]
+    fn limits_reject_oversized_receipts() {
+        let journal = b"journal".to_vec();
+        let claim = ReceiptClaim::ok(Digest::ZERO, journal.clone());
+        let ctx = |limits| VerifierContext::default().with_limits(limits);
+        let limit_exceeded = |resource, size, limit| VerificationError::ResourceLimitExceeded {
+            resource,
+            size,
+            limit,
+        };
+
+        let groth16_receipt = Receipt::new(
+            InnerReceipt::Groth16(Groth16Receipt {
+                seal: vec![0u8; 256],
+                claim: claim.clone().into(),
+                verifier_parameters: Groth16ReceiptVerifierParameters::default().digest(),
+            }),
+            journal.clone(),
+        );
+        assert_eq!(
+            groth16_receipt
+                .verify_with_context(
+                    &ctx(VerifierLimits::default().with_max_seal_bytes(255)),
+                    Digest::ZERO
+                )
+                .err()
+                .unwrap(),
+            limit_exceeded("seal size", 256, 255)
+        );
+        assert_eq!(
+            groth16_receipt
+                .verify_integrity_with_context(&ctx(
+                    VerifierLimits::default().with_max_journal_bytes(journal.len() - 1)
+                ))
+                .err()
+                .unwrap(),
+            limit_exceeded("journal size", journal.len(), journal.len() - 1)
+        );
+
+        let segment = SegmentReceipt {
+            seal: vec![0u32; 64],
+            index: 0,
+            hashfn: "poseidon2".into(),
+            verifier_parameters: Digest::ZERO,
+            claim: claim.clone(),
+        };
+        let composite_receipt = Receipt::new(
+            InnerReceipt::Composite(CompositeReceipt {
+                segments: vec![segment; 3],
+                assumption_receipts: vec![
+                    InnerAssumptionReceipt::Fake(
+                        FakeReceipt::new(claim).into_unknown()
+                    );
+                    2
+                ],
+                verifier_parameters: Digest::ZERO,
+            }),
+            journal,
+        );
+        let composite_limits = [
+            (
+                VerifierLimits::default().with_max_segments(2),
+                limit_exceeded("segment count", 3, 2),
+            ),
+            (
+                VerifierLimits::default().with_max_assumptions(1),
+                limit_exceeded("assumption count", 2, 1),
+            ),
+            (
+                VerifierLimits::default().with_max_seal_bytes(255),
+                limit_exceeded("seal size", 256, 255),
+            ),
+        ];
+        for (limits, error) in composite_limits {
+            assert_eq!(limits.check(&composite_receipt).err().unwrap(), error);
+            assert_eq!(
+                composite_receipt
+                    .verify_with_context(&ctx(limits), Digest::ZERO)
+                    .err()
+                    .unwrap(),
+                error
+            );
+        }
+    }
 }
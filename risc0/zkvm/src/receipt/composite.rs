@@ -67,6 +67,7 @@ impl CompositeReceipt {
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
         tracing::debug!("CompositeReceipt::verify_integrity_with_context");
+        ctx.limits.check_composite(self)?;
         // Verify the continuation, by verifying every segment receipt in order.
         let (final_receipt, receipts) = self
             .segments
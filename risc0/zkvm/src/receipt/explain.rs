@@ -0,0 +1,377 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diagnostics explaining why a [Receipt] failed verification.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use risc0_binfmt::ExitCode;
+use risc0_zkp::{core::digest::Digest, verify::VerificationError};
+
+use super::{InnerReceipt, Receipt, VerifierContext};
+use crate::{sha::Digestible, MaybePruned, Output, ReceiptClaim};
+
+/// A field of the [ReceiptClaim] on a [Receipt] that differs from the claim expected by
+/// [Receipt::verify].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ClaimMismatch {
+    /// The claim is pruned, so its fields cannot be compared individually.
+    Claim {
+        /// Digest of the expected claim.
+        expected: Digest,
+        /// Digest of the claim on the receipt.
+        received: Digest,
+    },
+
+    /// The pre-state digest, which is the image ID, differs.
+    PreState {
+        /// The image ID given to [Receipt::explain_verify].
+        expected: Digest,
+        /// The pre-state digest on the receipt.
+        received: Digest,
+    },
+
+    /// The post-state differs, e.g. because execution ended in a paused state.
+    PostState {
+        /// Digest of the post-state of an execution that halted successfully.
+        expected: Digest,
+        /// Digest of the post-state on the receipt.
+        received: Digest,
+    },
+
+    /// The exit code is not `Halted(0)`.
+    ExitCode {
+        /// The exit code of a successful execution.
+        expected: ExitCode,
+        /// The exit code on the receipt.
+        received: ExitCode,
+    },
+
+    /// The input digest differs.
+    Input {
+        /// Digest of the expected input.
+        expected: Digest,
+        /// Digest of the input on the receipt.
+        received: Digest,
+    },
+
+    /// The output is missing or pruned, so the journal and assumptions cannot be compared.
+    Output {
+        /// Digest of the expected output.
+        expected: Digest,
+        /// Digest of the output on the receipt.
+        received: Digest,
+    },
+
+    /// The journal digest in the claim does not match [Receipt::journal_digest].
+    Journal {
+        /// The [Receipt::journal_digest].
+        expected: Digest,
+        /// Digest of the journal in the claim.
+        received: Digest,
+    },
+
+    /// The assumptions list is not empty, i.e. the receipt is conditional.
+    Assumptions {
+        /// Digest of the empty assumptions list.
+        expected: Digest,
+        /// Digest of the assumptions list in the claim.
+        received: Digest,
+    },
+}
+
+impl fmt::Display for ClaimMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (field, expected, received) = match self {
+            Self::Claim { expected, received } => ("claim", expected, received),
+            Self::PreState { expected, received } => ("pre-state (image ID)", expected, received),
+            Self::PostState { expected, received } => ("post-state", expected, received),
+            Self::ExitCode { expected, received } => {
+                return write!(f, "exit code: expected {expected:?}, received {received:?}")
+            }
+            Self::Input { expected, received } => ("input", expected, received),
+            Self::Output { expected, received } => ("output", expected, received),
+            Self::Journal { expected, received } => ("journal digest", expected, received),
+            Self::Assumptions { expected, received } => ("assumptions", expected, received),
+        };
+        write!(f, "{field}: expected {expected}, received {received}")
+    }
+}
+
+/// Report produced by [Receipt::explain_verify].
+///
+/// Describes the outcome of verification, the fields of the claim that differ from the claim
+/// expected by [Receipt::verify], and the verifier parameters the receipt was made for.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct VerificationReport {
+    /// The result of [Receipt::verify_with_context].
+    pub result: Result<(), VerificationError>,
+
+    /// The claim that [Receipt::verify] expects, reconstructed from the image ID and journal.
+    pub expected_claim: ReceiptClaim,
+
+    /// The claim on the receipt, or `None` if it could not be extracted.
+    pub claim: Option<MaybePruned<ReceiptClaim>>,
+
+    /// Fields of the claim on the receipt that differ from [VerificationReport::expected_claim].
+    pub mismatches: Vec<ClaimMismatch>,
+
+    /// Digest of the verifier parameters the receipt was made for.
+    pub verifier_parameters: Digest,
+
+    /// Whether the verifier parameters of the [VerifierContext] match those of the receipt.
+    ///
+    /// When this is `false`, the receipt was likely made by a different release than the
+    /// verifier, and the seal is not expected to verify.
+    pub verifier_parameters_match: bool,
+
+    /// The control root the receipt was made for, if it can be determined.
+    ///
+    /// This is derived from the control inclusion proof for a [SuccinctReceipt][super::SuccinctReceipt],
+    /// and from the [VerifierContext] for a [Groth16Receipt][super::Groth16Receipt] with matching
+    /// verifier parameters.
+    pub control_root: Option<Digest>,
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(()) => writeln!(f, "receipt verified")?,
+            Err(err) => writeln!(f, "receipt failed to verify: {err}")?,
+        }
+        write!(f, "verifier parameters: {}", self.verifier_parameters)?;
+        if !self.verifier_parameters_match {
+            write!(f, " (not supported by the verifier context)")?;
+        }
+        writeln!(f)?;
+        if let Some(control_root) = self.control_root {
+            writeln!(f, "control root: {control_root}")?;
+        }
+        for mismatch in self.mismatches.iter() {
+            writeln!(f, "mismatch in {mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Receipt {
+    /// Verify this receipt as with [Receipt::verify_with_context], and explain the outcome.
+    ///
+    /// When verification fails with an error such as [VerificationError::ClaimDigestMismatch],
+    /// the returned report reconstructs the expected [ReceiptClaim] and lists each field of the
+    /// claim on the receipt that differs from it, along with the verifier parameters and control
+    /// root the receipt was made for. This is intended for diagnostics, and is more expensive than
+    /// [Receipt::verify_with_context].
+    pub fn explain_verify(
+        &self,
+        image_id: impl Into<Digest>,
+        ctx: &VerifierContext,
+    ) -> VerificationReport {
+        let image_id = image_id.into();
+        let result = self.verify_with_context(ctx, image_id);
+        let expected_claim = ReceiptClaim::ok(image_id, MaybePruned::Pruned(self.journal_digest()));
+        let claim = self.inner.claim().ok();
+        let mismatches = claim
+            .as_ref()
+            .map(|claim| claim_mismatches(&expected_claim, claim))
+            .unwrap_or_default();
+        let verifier_parameters = self.inner.verifier_parameters();
+
+        VerificationReport {
+            result,
+            expected_claim,
+            claim,
+            mismatches,
+            verifier_parameters,
+            verifier_parameters_match: ctx
+                .verifier_parameters_digests()
+                .contains(&verifier_parameters),
+            control_root: control_root(&self.inner, ctx),
+        }
+    }
+}
+
+fn claim_mismatches(
+    expected: &ReceiptClaim,
+    claim: &MaybePruned<ReceiptClaim>,
+) -> Vec<ClaimMismatch> {
+    let mut mismatches = Vec::new();
+    let claim = match claim {
+        MaybePruned::Value(claim) => claim,
+        MaybePruned::Pruned(digest) => {
+            if *digest != expected.digest() {
+                mismatches.push(ClaimMismatch::Claim {
+                    expected: expected.digest(),
+                    received: *digest,
+                });
+            }
+            return mismatches;
+        }
+    };
+
+    if claim.pre.digest() != expected.pre.digest() {
+        mismatches.push(ClaimMismatch::PreState {
+            expected: expected.pre.digest(),
+            received: claim.pre.digest(),
+        });
+    }
+    if claim.post.digest() != expected.post.digest() {
+        mismatches.push(ClaimMismatch::PostState {
+            expected: expected.post.digest(),
+            received: claim.post.digest(),
+        });
+    }
+    if claim.exit_code != expected.exit_code {
+        mismatches.push(ClaimMismatch::ExitCode {
+            expected: expected.exit_code,
+            received: claim.exit_code,
+        });
+    }
+    if claim.input.digest() != expected.input.digest() {
+        mismatches.push(ClaimMismatch::Input {
+            expected: expected.input.digest(),
+            received: claim.input.digest(),
+        });
+    }
+    if claim.output.digest() != expected.output.digest() {
+        match (&claim.output, &expected.output) {
+            (MaybePruned::Value(Some(output)), MaybePruned::Value(Some(expected))) => {
+                mismatches.extend(output_mismatches(expected, output));
+            }
+            _ => mismatches.push(ClaimMismatch::Output {
+                expected: expected.output.digest(),
+                received: claim.output.digest(),
+            }),
+        }
+    }
+    mismatches
+}
+
+fn output_mismatches(expected: &Output, output: &Output) -> Vec<ClaimMismatch> {
+    let mut mismatches = Vec::new();
+    if output.journal.digest() != expected.journal.digest() {
+        mismatches.push(ClaimMismatch::Journal {
+            expected: expected.journal.digest(),
+            received: output.journal.digest(),
+        });
+    }
+    if output.assumptions.digest() != expected.assumptions.digest() {
+        mismatches.push(ClaimMismatch::Assumptions {
+            expected: expected.assumptions.digest(),
+            received: output.assumptions.digest(),
+        });
+    }
+    mismatches
+}
+
+fn control_root(inner: &InnerReceipt, ctx: &VerifierContext) -> Option<Digest> {
+    match inner {
+        InnerReceipt::Succinct(receipt) => {
+            let suite = ctx.suites.get(&receipt.hashfn)?;
+            Some(
+                receipt
+                    .control_inclusion_proof
+                    .root(&receipt.control_id, suite.hashfn.as_ref()),
+            )
+        }
+        InnerReceipt::Groth16(receipt) => ctx
+            .groth16_verifier_parameters
+            .as_ref()
+            .filter(|params| params.digest() == receipt.verifier_parameters)
+            .map(|params| params.control_root),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClaimMismatch;
+    use crate::{
+        sha::{Digest, Digestible},
+        ExitCode, FakeReceipt, InnerReceipt, Receipt, ReceiptClaim, VerifierContext,
+    };
+
+    #[test]
+    fn explain_verify_reports_mismatched_fields() {
+        let image_id = Digest::from([1u32; 8]);
+        let journal = b"journal".to_vec();
+        let claim = ReceiptClaim::paused(Digest::from([2u32; 8]), b"other".to_vec());
+        let receipt = Receipt::new(InnerReceipt::Fake(FakeReceipt::new(claim)), journal.clone());
+
+        let report = receipt.explain_verify(image_id, &VerifierContext::default());
+        assert!(report.result.is_err());
+        assert_eq!(
+            report.mismatches,
+            vec![
+                ClaimMismatch::PreState {
+                    expected: image_id,
+                    received: Digest::from([2u32; 8]),
+                },
+                ClaimMismatch::ExitCode {
+                    expected: ExitCode::Halted(0),
+                    received: ExitCode::Paused(0),
+                },
+                ClaimMismatch::Journal {
+                    expected: journal.digest(),
+                    received: b"other".to_vec().digest(),
+                },
+            ]
+        );
+
+        let receipt = Receipt::new(
+            InnerReceipt::Fake(FakeReceipt::new(ReceiptClaim::ok(
+                image_id,
+                journal.clone(),
+            ))),
+            journal,
+        );
+        let report = receipt.explain_verify(image_id, &VerifierContext::default());
+        assert!(report.mismatches.is_empty());
+        assert_eq!(
+            report.claim.map(|claim| claim.digest()),
+            Some(report.expected_claim.digest())
+        );
+        assert!(report.control_root.is_none());
+    }
+
+    #[test]
+    fn explain_verify_external_journal() {
+        let image_id = Digest::from([1u32; 8]);
+        let journal = b"journal".to_vec();
+        let claim = ReceiptClaim::ok(image_id, journal.clone());
+        let receipt = Receipt::new_external(
+            InnerReceipt::Fake(FakeReceipt::new(claim.clone())),
+            journal.digest(),
+        );
+        let report = receipt.explain_verify(image_id, &VerifierContext::default());
+        assert!(report.mismatches.is_empty());
+        assert_eq!(report.expected_claim.digest(), claim.digest());
+
+        let receipt = Receipt::new_external(
+            InnerReceipt::Fake(FakeReceipt::new(claim)),
+            b"other".to_vec().digest(),
+        );
+        let report = receipt.explain_verify(image_id, &VerifierContext::default());
+        assert_eq!(
+            report.mismatches,
+            vec![ClaimMismatch::Journal {
+                expected: b"other".to_vec().digest(),
+                received: journal.digest(),
+            }]
+        );
+    }
+}
@@ -15,21 +15,36 @@
 use alloc::vec::Vec;
 use core::fmt::Debug;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, ensure, Result};
 use risc0_binfmt::{tagged_struct, Digestible};
 use risc0_circuit_recursion::control_id::{ALLOWED_CONTROL_ROOT, BN254_IDENTITY_CONTROL_ID};
-use risc0_groth16::{fr_from_hex_string, split_digest, Seal, Verifier, VerifyingKey};
+use risc0_groth16::{
+    fr_from_hex_string, split_digest, Fr, ProofJson, PublicInputsJson, Seal, SealFormat, Verifier,
+    VerifyingKey,
+};
 use risc0_zkp::core::hash::sha::Sha256;
 use risc0_zkp::{core::digest::Digest, verify::VerificationError};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 
 // Make succinct receipt available through this `receipt` module.
 use crate::{
-    receipt::VerifierContext,
-    receipt_claim::{MaybePruned, Unknown},
+    receipt::{BatchVerificationError, VerifierContext},
+    receipt_claim::{MaybePruned, ReceiptClaim, Unknown},
     sha,
 };
 
+/// Function selector of `verify(bytes,bytes32,bytes32)` on the `IRiscZeroVerifier` interface.
+pub const EVM_VERIFY_SELECTOR: [u8; 4] = [0xab, 0x75, 0x0e, 0x75];
+
+/// Length of the selector prepended to the seal in the EVM encoding.
+const EVM_SEAL_SELECTOR_LEN: usize = 4;
+
+/// Length of the Groth16 seal, as encoded by [Seal::to_vec].
+const SEAL_LEN: usize = 256;
+
+/// Length of a word in the Solidity ABI encoding.
+const ABI_WORD_LEN: usize = 32;
+
 /// A receipt composed of a Groth16 over the BN_254 curve
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -39,6 +54,9 @@ where
     Claim: Digestible + Debug + Clone + Serialize,
 {
     /// A Groth16 proof of a zkVM execution with the associated claim.
+    ///
+    /// Encoded in one of the [SealFormat] encodings, which is checked on deserialization.
+    #[serde(deserialize_with = "deserialize_seal")]
     pub seal: Vec<u8>,
 
     /// [ReceiptClaim][crate::ReceiptClaim] containing information about the execution that this
@@ -57,6 +75,20 @@ impl<Claim> Groth16Receipt<Claim>
 where
     Claim: Digestible + Debug + Clone + Serialize,
 {
+    /// Create a new [Groth16Receipt] from the given seal, in one of the [SealFormat] encodings,
+    /// claim and verifier parameters digest.
+    pub fn new(
+        seal: Vec<u8>,
+        claim: impl Into<MaybePruned<Claim>>,
+        verifier_parameters: Digest,
+    ) -> Self {
+        Self {
+            seal,
+            claim: claim.into(),
+            verifier_parameters,
+        }
+    }
+
     /// Verify the integrity of this receipt, ensuring the claim is attested
     /// to by the seal.
     pub fn verify_integrity(&self) -> Result<(), VerificationError> {
@@ -69,32 +101,108 @@ where
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
+        ctx.limits.check_seal(self.seal.len())?;
         let params = ctx
             .groth16_verifier_parameters
             .as_ref()
             .ok_or(VerificationError::VerifierParametersMissing)?;
 
-        let (a0, a1) =
-            split_digest(params.control_root).map_err(|_| VerificationError::ReceiptFormatError)?;
-        let (c0, c1) = split_digest(self.claim.digest::<sha::Impl>())
-            .map_err(|_| VerificationError::ReceiptFormatError)?;
-        let mut id_bn554: Digest = params.bn254_control_id;
-        id_bn554.as_mut_bytes().reverse();
-        let id_bn254_fr = fr_from_hex_string(&hex::encode(id_bn554))
-            .map_err(|_| VerificationError::ReceiptFormatError)?;
-        Verifier::new(
-            &Seal::from_vec(&self.seal).map_err(|_| VerificationError::ReceiptFormatError)?,
-            &[a0, a1, c0, c1, id_bn254_fr],
-            &params.verifying_key,
-        )
-        .map_err(|_| VerificationError::ReceiptFormatError)?
-        .verify()
-        .map_err(|_| VerificationError::InvalidProof)?;
+        self.verifier(params)?
+            .verify()
+            .map_err(|_| VerificationError::InvalidProof)?;
 
         // Everything passed
         Ok(())
     }
 
+    /// Verify the integrity of a batch of receipts, ensuring the claim of each is attested to by
+    /// its seal.
+    ///
+    /// All receipts are checked against the verifier parameters in `ctx`, so the verifying key is
+    /// prepared once for the whole batch. The seals are checked together with a single
+    /// multi-pairing, using a random linear combination of the Groth16 verification equations. If
+    /// the batch check fails, each receipt is verified individually, and the returned error
+    /// identifies the first invalid receipt.
+    pub fn verify_integrity_batch<'a>(
+        receipts: impl IntoIterator<Item = &'a Self>,
+        ctx: &VerifierContext,
+    ) -> Result<(), BatchVerificationError>
+    where
+        Claim: 'a,
+    {
+        let mut receipts = receipts.into_iter().peekable();
+        if receipts.peek().is_none() {
+            return Ok(());
+        }
+        let params = ctx
+            .groth16_verifier_parameters
+            .as_ref()
+            .ok_or(BatchVerificationError {
+                index: 0,
+                error: VerificationError::VerifierParametersMissing,
+            })?;
+        let prepared_key = params.verifying_key.prepare();
+
+        let proofs = receipts
+            .enumerate()
+            .map(|(index, receipt)| {
+                ctx.limits
+                    .check_seal(receipt.seal.len())
+                    .and_then(|()| receipt.proof(params))
+                    .map_err(|error| BatchVerificationError { index, error })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if Verifier::verify_batch(&prepared_key, &proofs).is_ok() {
+            return Ok(());
+        }
+        for (index, proof) in proofs.iter().enumerate() {
+            Verifier::verify_batch(&prepared_key, core::slice::from_ref(proof)).map_err(|_| {
+                BatchVerificationError {
+                    index,
+                    error: VerificationError::InvalidProof,
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Construct the Groth16 [Verifier] for this receipt with the given parameters.
+    fn verifier(
+        &self,
+        params: &Groth16ReceiptVerifierParameters,
+    ) -> Result<Verifier, VerificationError> {
+        let (seal, public_inputs) = self.proof(params)?;
+        Verifier::new(&seal, &public_inputs, &params.verifying_key)
+            .map_err(|_| VerificationError::ReceiptFormatError)
+    }
+
+    /// Decode the seal of this receipt, along with the public inputs it is checked against under
+    /// the given parameters.
+    fn proof(
+        &self,
+        params: &Groth16ReceiptVerifierParameters,
+    ) -> Result<(Seal, Vec<Fr>), VerificationError> {
+        let public_inputs = public_inputs(params, self.claim.digest::<sha::Impl>())
+            .map_err(|_| VerificationError::ReceiptFormatError)?;
+        let seal = Seal::decode(&self.seal).map_err(|_| VerificationError::ReceiptFormatError)?;
+        Ok((seal, public_inputs))
+    }
+
+    /// Returns the [SealFormat] in which the seal of this receipt is encoded.
+    pub fn seal_format(&self) -> Result<SealFormat> {
+        SealFormat::of(&self.seal)
+    }
+
+    /// Re-encode the seal of this receipt in the given [SealFormat].
+    ///
+    /// The conversion is lossless, and the receipt verifies the same way in either format.
+    /// Use [SealFormat::Compressed] to roughly halve the size of the seal for storage.
+    pub fn with_seal_format(mut self, format: SealFormat) -> Result<Self> {
+        self.seal = Seal::decode(&self.seal)?.encode(format)?;
+        Ok(self)
+    }
+
     /// Prunes the claim, retaining its digest, and converts into a [Groth16Receipt] with an unknown
     /// claim type. Can be used to get receipts of a uniform type across heterogenous claims.
     pub fn into_unknown(self) -> Groth16Receipt<Unknown> {
@@ -106,6 +214,242 @@ where
     }
 }
 
+impl<Claim> Groth16Receipt<Claim>
+where
+    Claim: Digestible + Debug + Clone + Serialize,
+{
+    /// Selector identifying the verifier parameters of this receipt on-chain.
+    ///
+    /// The selector is the first four bytes of the [Groth16ReceiptVerifierParameters] digest, and
+    /// is used by on-chain routers to dispatch the seal to the verifier for that version.
+    pub fn evm_selector(&self) -> [u8; 4] {
+        evm_selector(&self.verifier_parameters)
+    }
+
+    /// Encode the seal in the format accepted by the on-chain verifier.
+    ///
+    /// The encoded seal is the [Groth16Receipt::evm_selector] followed by the 256 byte Groth16
+    /// seal, with the coordinates of the G2 point `b` in the order expected by the EVM pairing
+    /// precompile (i.e. imaginary part first). A compressed seal is decompressed, since the
+    /// on-chain verifier only accepts the uncompressed encoding.
+    pub fn evm_seal(&self) -> Result<Vec<u8>> {
+        let seal = Seal::decode(&self.seal)?.to_vec();
+        let mut encoded = Vec::with_capacity(EVM_SEAL_SELECTOR_LEN + seal.len());
+        encoded.extend_from_slice(&self.evm_selector());
+        encoded.extend_from_slice(&seal);
+        Ok(encoded)
+    }
+
+    /// Decode a seal produced by [Groth16Receipt::evm_seal], checking that its selector matches
+    /// the given verifier parameters digest.
+    ///
+    /// Returns the Groth16 seal, as stored in [Groth16Receipt::seal].
+    pub fn decode_evm_seal(encoded: &[u8], verifier_parameters: &Digest) -> Result<Vec<u8>> {
+        ensure!(
+            encoded.len() == EVM_SEAL_SELECTOR_LEN + SEAL_LEN,
+            "encoded seal has length {}, expected {}",
+            encoded.len(),
+            EVM_SEAL_SELECTOR_LEN + SEAL_LEN
+        );
+        let (selector, seal) = encoded.split_at(EVM_SEAL_SELECTOR_LEN);
+        let expected = evm_selector(verifier_parameters);
+        if selector != expected {
+            bail!(
+                "seal selector {} does not match verifier parameters selector {}",
+                hex::encode(selector),
+                hex::encode(expected)
+            );
+        }
+        Ok(Seal::from_vec(seal)?.to_vec())
+    }
+}
+
+impl Groth16Receipt<ReceiptClaim> {
+    /// Construct a [Groth16Receipt] from a Groth16 proof produced outside of the zkVM prover, such
+    /// as the `proof.json` and `public.json` output by snarkjs from a [SuccinctReceipt].
+    ///
+    /// The public inputs are checked against the control root and BN254 control ID of the
+    /// [Groth16ReceiptVerifierParameters] in the given context, and against the digest of the given
+    /// claim (e.g. the claim of the [SuccinctReceipt] that was compressed). The proof is then
+    /// verified, so the returned receipt is known to pass
+    /// [Groth16Receipt::verify_integrity_with_context] with the same context.
+    ///
+    /// The snarkjs `public.json` is a plain array of decimal strings, and can be loaded with
+    /// `PublicInputsJson { values: serde_json::from_str(public_json)? }`.
+    ///
+    /// [SuccinctReceipt]: crate::SuccinctReceipt
+    pub fn from_json(
+        proof: ProofJson,
+        public_inputs: PublicInputsJson,
+        claim: impl Into<MaybePruned<ReceiptClaim>>,
+        ctx: &VerifierContext,
+    ) -> Result<Self> {
+        let params = ctx
+            .groth16_verifier_parameters
+            .as_ref()
+            .ok_or_else(|| anyhow!("verifier context has no Groth16 verifier parameters"))?;
+        let claim = claim.into();
+
+        let expected = self::public_inputs(params, claim.digest::<sha::Impl>())?;
+        let actual = public_inputs.to_scalar()?;
+        ensure!(
+            actual.len() == expected.len(),
+            "expected {} public inputs, got {}",
+            expected.len(),
+            actual.len()
+        );
+        for (i, (actual, expected)) in actual.iter().zip(expected.iter()).enumerate() {
+            let name = match i {
+                0 | 1 => "control root",
+                2 | 3 => "claim digest",
+                _ => "BN254 control ID",
+            };
+            ensure!(
+                actual == expected,
+                "public input {i} does not match the {name} of the receipt"
+            );
+        }
+
+        let receipt = Self {
+            seal: Seal::try_from(proof)?.to_vec(),
+            claim,
+            verifier_parameters: params.digest::<sha::Impl>(),
+        };
+        receipt
+            .verify_integrity_with_context(ctx)
+            .map_err(|err| anyhow!(err))?;
+        Ok(receipt)
+    }
+
+    /// ABI-encode a call to `verify(bytes seal, bytes32 imageId, bytes32 journalDigest)` on the
+    /// `IRiscZeroVerifier` contract, including the function selector.
+    ///
+    /// The seal is encoded with [Groth16Receipt::evm_seal]. The on-chain verifier reconstructs the
+    /// claim with [ReceiptClaim::ok] from the image ID and journal digest, so this returns an error
+    /// if the claim of this receipt is not of that form (e.g. the guest did not exit with
+    /// `Halted(0)` or has unresolved assumptions), or if the claim or its output are pruned.
+    pub fn to_evm_calldata(&self) -> Result<Vec<u8>> {
+        let claim = self
+            .claim
+            .as_value()
+            .map_err(|_| anyhow!("receipt claim is pruned"))?;
+        let image_id = claim.pre.digest::<sha::Impl>();
+        let journal_digest = match claim.output.as_value() {
+            Ok(Some(output)) => output.journal.digest::<sha::Impl>(),
+            Ok(None) => bail!("receipt claim has no output"),
+            Err(_) => bail!("receipt claim output is pruned"),
+        };
+        let expected = ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal_digest));
+        ensure!(
+            self.claim.digest::<sha::Impl>() == expected.digest::<sha::Impl>(),
+            "receipt claim is not an unconditional successful execution and cannot be verified \
+             on-chain from its image ID and journal digest"
+        );
+
+        let seal = self.evm_seal()?;
+        let padded_len = seal.len().next_multiple_of(ABI_WORD_LEN);
+        let mut calldata =
+            Vec::with_capacity(EVM_VERIFY_SELECTOR.len() + 4 * ABI_WORD_LEN + padded_len);
+        calldata.extend_from_slice(&EVM_VERIFY_SELECTOR);
+        // Head: offset of the dynamic seal argument, followed by the two static arguments.
+        calldata.extend_from_slice(&abi_word(3 * ABI_WORD_LEN));
+        calldata.extend_from_slice(image_id.as_bytes());
+        calldata.extend_from_slice(journal_digest.as_bytes());
+        // Tail: length-prefixed seal, right-padded with zeros to a whole number of words.
+        calldata.extend_from_slice(&abi_word(seal.len()));
+        calldata.extend_from_slice(&seal);
+        calldata.resize(calldata.len() + padded_len - seal.len(), 0);
+        Ok(calldata)
+    }
+
+    /// Parse calldata produced by [Groth16Receipt::to_evm_calldata] back into a receipt.
+    ///
+    /// The selector of the seal must match the given verifier parameters digest. The journal is
+    /// not part of the calldata, so the claim of the returned receipt has a pruned journal.
+    pub fn from_evm_calldata(calldata: &[u8], verifier_parameters: Digest) -> Result<Self> {
+        let Some(args) = calldata.strip_prefix(&EVM_VERIFY_SELECTOR) else {
+            bail!("calldata is not a call to verify(bytes,bytes32,bytes32)");
+        };
+        ensure!(
+            args.len() >= 4 * ABI_WORD_LEN,
+            "calldata is too short to hold the verify arguments"
+        );
+        let word = |i: usize| -> [u8; ABI_WORD_LEN] {
+            args[i * ABI_WORD_LEN..(i + 1) * ABI_WORD_LEN]
+                .try_into()
+                .unwrap()
+        };
+
+        let seal_offset = abi_usize(&word(0))?;
+        let image_id = Digest::from(word(1));
+        let journal_digest = Digest::from(word(2));
+
+        let Some(seal_len_word) = args
+            .get(seal_offset..seal_offset.saturating_add(ABI_WORD_LEN))
+            .and_then(|word| <[u8; ABI_WORD_LEN]>::try_from(word).ok())
+        else {
+            bail!("seal offset {seal_offset} is out of bounds");
+        };
+        let seal_len = abi_usize(&seal_len_word)?;
+        let seal_start = seal_offset + ABI_WORD_LEN;
+        let Some(encoded_seal) = args.get(seal_start..seal_start.saturating_add(seal_len)) else {
+            bail!("seal of length {seal_len} is out of bounds");
+        };
+        let seal = Self::decode_evm_seal(encoded_seal, &verifier_parameters)?;
+
+        Ok(Groth16Receipt {
+            seal,
+            claim: ReceiptClaim::ok(image_id, MaybePruned::Pruned(journal_digest)).into(),
+            verifier_parameters,
+        })
+    }
+}
+
+/// Deserialize a seal, checking that it is in one of the [SealFormat] encodings.
+fn deserialize_seal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let seal = Vec::<u8>::deserialize(deserializer)?;
+    SealFormat::of(&seal).map_err(D::Error::custom)?;
+    Ok(seal)
+}
+
+/// Public inputs of the Groth16 proof for the given verifier parameters and claim digest.
+///
+/// These are the two halves of the control root, the two halves of the claim digest, and the
+/// BN254 control ID.
+fn public_inputs(
+    params: &Groth16ReceiptVerifierParameters,
+    claim_digest: Digest,
+) -> Result<Vec<Fr>> {
+    let (a0, a1) = split_digest(params.control_root)?;
+    let (c0, c1) = split_digest(claim_digest)?;
+    let mut id_bn254: Digest = params.bn254_control_id;
+    id_bn254.as_mut_bytes().reverse();
+    let id_bn254_fr = fr_from_hex_string(&hex::encode(id_bn254))?;
+    Ok(alloc::vec![a0, a1, c0, c1, id_bn254_fr])
+}
+
+/// Selector for the given verifier parameters digest. See [Groth16Receipt::evm_selector].
+fn evm_selector(verifier_parameters: &Digest) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&verifier_parameters.as_bytes()[..EVM_SEAL_SELECTOR_LEN]);
+    selector
+}
+
+/// Encode a [usize] as a big-endian ABI word.
+fn abi_word(value: usize) -> [u8; ABI_WORD_LEN] {
+    let mut word = [0u8; ABI_WORD_LEN];
+    word[ABI_WORD_LEN - 8..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+/// Decode a big-endian ABI word as a [usize], failing if it does not fit.
+fn abi_usize(word: &[u8; ABI_WORD_LEN]) -> Result<usize> {
+    let (high, low) = word.split_at(ABI_WORD_LEN - 8);
+    ensure!(high.iter().all(|b| *b == 0), "ABI word is out of range");
+    usize::try_from(u64::from_be_bytes(low.try_into().unwrap()))
+        .map_err(|_| anyhow!("ABI word is out of range"))
+}
+
 /// Verifier parameters used to verify a [Groth16Receipt].
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Groth16ReceiptVerifierParameters {
@@ -146,9 +490,13 @@ impl Default for Groth16ReceiptVerifierParameters {
 
 #[cfg(test)]
 mod tests {
-    use super::Groth16ReceiptVerifierParameters;
-    use crate::sha::Digestible;
-    use risc0_zkp::core::digest::digest;
+    use risc0_groth16::{ProofJson, PublicInputsJson};
+
+    use super::{
+        public_inputs, Groth16Receipt, Groth16ReceiptVerifierParameters, EVM_VERIFY_SELECTOR,
+    };
+    use crate::{receipt_claim::MaybePruned, sha::Digestible, ReceiptClaim, VerifierContext};
+    use risc0_zkp::core::digest::{digest, Digest};
 
     // Check that the verifier parameters has a stable digest (and therefore a stable value). This
     // struct encodes parameters used in verification, and so this value should be updated if and
@@ -161,4 +509,132 @@ mod tests {
             digest!("f7ca3f2cfe1cde247417d317852c696a0e4a42acaf51205e6af54a58fa6c64fe")
         );
     }
+
+    // The receipt verifier contract must check the same public inputs as Groth16Receipt.
+    #[test]
+    fn solidity_verifier_matches_parameters() {
+        let params = Groth16ReceiptVerifierParameters::default();
+        let source =
+            risc0_groth16::solidity::receipt_verifier(params.control_root, params.bn254_control_id)
+                .unwrap();
+        let constant = |name: &str| {
+            let prefix = format!("constant {name} = ");
+            let start = source.find(&prefix).unwrap() + prefix.len();
+            let end = start + source[start..].find(';').unwrap();
+            source[start..end].to_string()
+        };
+        let constants = PublicInputsJson {
+            values: ["CONTROL_ROOT_0", "CONTROL_ROOT_1", "BN254_CONTROL_ID"]
+                .map(constant)
+                .to_vec(),
+        }
+        .to_scalar()
+        .unwrap();
+
+        let inputs = public_inputs(&params, Digest::ZERO).unwrap();
+        assert_eq!(
+            constants,
+            [inputs[0].clone(), inputs[1].clone(), inputs[4].clone()]
+        );
+    }
+
+    fn groth16_receipt(claim: ReceiptClaim) -> Groth16Receipt<ReceiptClaim> {
+        Groth16Receipt {
+            seal: (0..=255).collect(),
+            claim: claim.into(),
+            verifier_parameters: Groth16ReceiptVerifierParameters::default().digest(),
+        }
+    }
+
+    #[test]
+    fn evm_calldata_round_trip() {
+        let image_id = digest!("0102030405060708091011121314151617181920212223242526272829303132");
+        let receipt = groth16_receipt(ReceiptClaim::ok(image_id, b"journal".to_vec()));
+
+        let calldata = receipt.to_evm_calldata().unwrap();
+        // Selector, three head words, seal length and the selector-prefixed seal padded to 288.
+        assert_eq!(calldata.len(), 4 + 4 * 32 + 288);
+        assert_eq!(calldata[..4], EVM_VERIFY_SELECTOR);
+        assert_eq!(calldata[4 + 32..4 + 64], *image_id.as_bytes());
+        assert_eq!(calldata[4 + 128..4 + 132], receipt.evm_selector());
+
+        let parsed =
+            Groth16Receipt::from_evm_calldata(&calldata, receipt.verifier_parameters).unwrap();
+        assert_eq!(parsed.seal, receipt.seal);
+        assert_eq!(parsed.claim.digest(), receipt.claim.digest());
+        assert_eq!(parsed.to_evm_calldata().unwrap(), calldata);
+    }
+
+    #[test]
+    fn evm_calldata_rejects_other_verifier_parameters() {
+        let receipt = groth16_receipt(ReceiptClaim::ok(Digest::ZERO, Vec::new()));
+        let calldata = receipt.to_evm_calldata().unwrap();
+        assert!(Groth16Receipt::from_evm_calldata(&calldata, Digest::ZERO).is_err());
+    }
+
+    #[test]
+    fn evm_calldata_rejects_unsupported_claims() {
+        let claim = ReceiptClaim::paused(Digest::ZERO, Vec::new());
+        assert!(groth16_receipt(claim).to_evm_calldata().is_err());
+
+        let mut claim = ReceiptClaim::ok(Digest::ZERO, Vec::new());
+        claim.pre = MaybePruned::Pruned(Digest::ZERO);
+        claim.output = MaybePruned::Pruned(Digest::ZERO);
+        assert!(groth16_receipt(claim).to_evm_calldata().is_err());
+    }
+
+    fn snarkjs_proof() -> ProofJson {
+        serde_json::from_str(
+            r#"{
+                "pi_a": ["1", "2", "1"],
+                "pi_b": [["1", "2"], ["3", "4"], ["1", "0"]],
+                "pi_c": ["1", "2", "1"],
+                "protocol": "groth16",
+                "curve": "bn128"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn from_json_checks_public_inputs() {
+        let ctx = VerifierContext::default();
+        let claim = ReceiptClaim::ok(Digest::ZERO, Vec::new());
+        let public_inputs = |values: &[&str]| PublicInputsJson {
+            values: values.iter().map(|x| x.to_string()).collect(),
+        };
+
+        let err = Groth16Receipt::from_json(
+            snarkjs_proof(),
+            public_inputs(&["1", "2", "3"]),
+            claim.clone(),
+            &ctx,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("expected 5 public inputs"),
+            "{err}"
+        );
+
+        let err = Groth16Receipt::from_json(
+            snarkjs_proof(),
+            public_inputs(&["1", "2", "3", "4", "5"]),
+            claim,
+            &ctx,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("control root"), "{err}");
+    }
+
+    #[test]
+    fn deserialization_checks_seal_format() {
+        let mut receipt = groth16_receipt(ReceiptClaim::ok(Digest::ZERO, Vec::new()));
+        let json = serde_json::to_string(&receipt).unwrap();
+        let decoded: Groth16Receipt<ReceiptClaim> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, receipt);
+
+        receipt.seal.truncate(129);
+        let json = serde_json::to_string(&receipt).unwrap();
+        assert!(serde_json::from_str::<Groth16Receipt<ReceiptClaim>>(&json).is_err());
+    }
 }
@@ -0,0 +1,166 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifier parameters of each release of the zkVM.
+//!
+//! Every value here is a literal copy of the parameters as they were shipped in the release, rather
+//! than a reference to the constants of the current circuits, so that a change to the circuits or
+//! the Groth16 setup cannot silently change what an earlier release verifies.
+
+use alloc::collections::BTreeSet;
+
+use anyhow::Result;
+use risc0_zkp::{adapter::ProtocolInfo, core::digest::Digest, digest};
+
+use super::{
+    Groth16ReceiptVerifierParameters, SegmentReceiptVerifierParameters,
+    SuccinctReceiptVerifierParameters, VerifierContext,
+};
+
+/// Verifier parameters of release 1.0.0-rc.5.
+pub(super) mod v1_0_0_rc_5 {
+    use super::*;
+
+    const PROOF_SYSTEM_INFO: ProtocolInfo = ProtocolInfo(*b"RISC0_STARK:v1__");
+    const RV32IM_CIRCUIT_INFO: ProtocolInfo = ProtocolInfo(*b"RV32IM:rev1v1___");
+    const RECURSION_CIRCUIT_INFO: ProtocolInfo = ProtocolInfo(*b"RECURSION:rev1v1");
+
+    /// Control IDs of the rv32im circuit for each power-of-two and each of the Poseidon2, SHA-256
+    /// and Blake2b hash functions.
+    const SEGMENT_CONTROL_IDS: &[Digest] = &[
+        digest!("bebb4c5f19d4973b590313766e476d3d7cce5c18b2d600188d7476767a4a8614"),
+        digest!("0296f8241298c95f780501761519030966b9902bc508b539881b0c49bf326b6e"),
+        digest!("b3b5d8727f259c31dfa971583c8db640a4a78b5b791fd76c15e90d2c5a85e45b"),
+        digest!("3376da16801abe4c46bf9c508d67c559a6ba4a46e0f8c602bfd9a337c9b0f40b"),
+        digest!("61c4fc0b6df2a935904374720c967b081deb844653f81956b71c4e1e863e6e3e"),
+        digest!("1f2a072b0e7ae7113aa1193120e2543daf39ad727c2128539c84072ae150c960"),
+        digest!("0c31d02a1900786d81bdae23ce96ce474c8b0b7182a91457aeac5a25b79f5c65"),
+        digest!("1ef23b55919a8313dfb56057c4174431839660377a3e22738e279c02901ad345"),
+        digest!("d695e66e4db81a4b6e8d535a5ba1370757291d1ef7fe1973db9cb83716565637"),
+        digest!("9ef20b736835d16fb922710871488552582aee312f0925599d3fee12934b3465"),
+        digest!("da824e2ecc179c2c1359bb75b154f8490086cd0f85e3391cc96ffc3b8e3afa11"),
+        digest!("eab745d650de7f5f837a3fe5f1cb23b4bef2d190b7a470453f92dbdf7083c7a0"),
+        digest!("09b42cb611dcaeae5606d6309b1b1aeb8482163cacc3225573c915eb5c28abcc"),
+        digest!("dc9424cbe65a98cd160f1450c17ce9a3419b50aea1967f7fedb65e3f3a1db38e"),
+        digest!("b687aa43eb3adae3661312ac42eecd123d05dc63c260c586271fc36f48c7588c"),
+        digest!("52d851bfbb1ab05e69e9205403d7909d80025fb51c943badd5efc16b2e325da1"),
+        digest!("6542f024bfbf6699511baae55c2c9bc85befda6188f54ccdc8d8dc1825567d75"),
+        digest!("901332de51232ff2e3c55ba64b217dd82728ec28a6fa087f02ef6f7d409f7d1a"),
+        digest!("831fd974bcd951dc3d0d0e802235788d4089b90a9d2578d72b67e0a80ce8715a"),
+        digest!("802ce6a9c33242f0ff0dc5e6f63def4dea194c2016137387ef36d1c8d145e462"),
+        digest!("ad1eda57cea9446c14c6d93859de058ca8d624e270f1f38aab7336db8aa4ea30"),
+        digest!("97be9ed7e5f1f24c3e527c07ded11418900b128725480e179156b5dd6b032cae"),
+        digest!("f68c89b078e1a360c7b3d4b9c7de5d47e3f46ce92e8ddf65b8c6ca32befb45fe"),
+        digest!("5be8f08be4f88aef8928da60b240832aa73c8e22be2cc06271a5b53b07636a2a"),
+        digest!("3a8089e20f6f7cd55d09c861b32fedc4ccd34283ea597a5c98b5de19efc3cca5"),
+        digest!("ee2a276a311656871b094b96844424683b1cf5a83e8e6be6cc10b8821ea7d9c5"),
+        digest!("59d2053991478d0819e6aaa071b6d9d325093a253798b247c6746c9fc5a57594"),
+        digest!("62a99496914f245543f96b6cee785794ce330864eb315639be6253df3ed9a80b"),
+        digest!("3e8fc320dc63e512258f638a028b494c6dd767d813d43e1ae270120f63e9dec8"),
+        digest!("f6df38c8532e9bb75e0eb5f9413c7feecac464ae43bd117c43e86fb932bed983"),
+        digest!("1b368a75de706ab0c103103c65915e21e01079eea654d65180e3a0d6260c2036"),
+        digest!("4a507faef43cd9d7bbf7561c7971c12ef0d1429ff73919cfea9479424a476bc6"),
+        digest!("3d8fad6ffcd1b9bd31ea4730021df8f882c9afac6a55cda04f419171cf8a848e"),
+    ];
+
+    const CONTROL_ROOT: Digest =
+        digest!("2c4aec26b74fdb27cd637d6106cfd64f6222aa55de73cd2b73189315b901ca09");
+
+    const BN254_CONTROL_ID: Digest =
+        digest!("0df1dcdc119a670072f4baac5a076a03e372a726cd1e20bacb2cf6be4d83ff10");
+
+    const VK_ALPHA: [&str; 2] = [
+        "20491192805390485299153009773594534940189261866228447918068658471970481763042",
+        "9383485363053290200918347156157836566562967994039712273449902621266178545958",
+    ];
+    const VK_BETA: [[&str; 2]; 2] = [
+        [
+            "4252822878758300859123897981450591353533073413197771768651442665752259397132",
+            "6375614351688725206403948262868962793625744043794305715222011528459656738731",
+        ],
+        [
+            "21847035105528745403288232691147584728191162732299865338377159692350059136679",
+            "10505242626370262277552901082094356697409835680220590971873171140371331206856",
+        ],
+    ];
+    const VK_GAMMA: [[&str; 2]; 2] = [
+        [
+            "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+            "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+        ],
+        [
+            "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+            "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+        ],
+    ];
+    const VK_DELTA: [[&str; 2]; 2] = [
+        [
+            "1668323501672964604911431804142266013250380587483576094566949227275849579036",
+            "12043754404802191763554326994664886008979042643626290185762540825416902247219",
+        ],
+        [
+            "7710631539206257456743780535472368339139328733484942210876916214502466455394",
+            "13740680757317479711909903993315946540841369848973133181051452051592786724563",
+        ],
+    ];
+    const VK_IC: &[[&str; 2]] = &[
+        [
+            "8446592859352799428420270221449902464741693648963397251242447530457567083492",
+            "1064796367193003797175961162477173481551615790032213185848276823815288302804",
+        ],
+        [
+            "3179835575189816632597428042194253779818690147323192973511715175294048485951",
+            "20895841676865356752879376687052266198216014795822152491318012491767775979074",
+        ],
+        [
+            "5332723250224941161709478398807683311971555792614491788690328996478511465287",
+            "21199491073419440416471372042641226693637837098357067793586556692319371762571",
+        ],
+        [
+            "12457994489566736295787256452575216703923664299075106359829199968023158780583",
+            "19706766271952591897761291684837117091856807401404423804318744964752784280790",
+        ],
+        [
+            "19617808913178163826953378459323299110911217259216006187355745713323154132237",
+            "21663537384585072695701846972542344484111393047775983928357046779215877070466",
+        ],
+        [
+            "6834578911681792552110317589222010969491336870276623105249474534788043166867",
+            "15060583660288623605191393599883223885678013570733629274538391874953353488393",
+        ],
+    ];
+
+    pub(crate) fn verifier_context() -> Result<VerifierContext> {
+        Ok(VerifierContext::empty()
+            .with_suites(VerifierContext::default_hash_suites())
+            .with_segment_verifier_parameters(SegmentReceiptVerifierParameters {
+                control_ids: BTreeSet::from_iter(SEGMENT_CONTROL_IDS.iter().copied()),
+                proof_system_info: PROOF_SYSTEM_INFO,
+                circuit_info: RV32IM_CIRCUIT_INFO,
+            })
+            .with_succinct_verifier_parameters(SuccinctReceiptVerifierParameters {
+                control_root: CONTROL_ROOT,
+                inner_control_root: None,
+                proof_system_info: PROOF_SYSTEM_INFO,
+                circuit_info: RECURSION_CIRCUIT_INFO,
+            })
+            .with_groth16_verifier_parameters(Groth16ReceiptVerifierParameters {
+                control_root: CONTROL_ROOT,
+                bn254_control_id: BN254_CONTROL_ID,
+                verifying_key: risc0_groth16::VerifyingKey::from_u256(
+                    VK_ALPHA, VK_BETA, VK_GAMMA, VK_DELTA, VK_IC,
+                )?,
+            }))
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use alloc::{collections::BTreeSet, string::String, vec::Vec};
-use core::fmt::Debug;
+use core::{fmt::Debug, mem::size_of_val};
 
 use anyhow::Result;
 use risc0_binfmt::{tagged_iter, tagged_struct, Digestible, ExitCode, SystemState};
@@ -74,6 +74,7 @@ impl SegmentReceipt {
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
+        ctx.limits.check_seal(size_of_val(self.seal.as_slice()))?;
         let params = ctx
             .segment_verifier_parameters
             .as_ref()
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use alloc::{collections::VecDeque, string::String, vec::Vec};
-use core::fmt::Debug;
+use core::{fmt::Debug, mem::size_of_val};
 
 use risc0_binfmt::{read_sha_halfs, tagged_struct, Digestible};
 use risc0_circuit_recursion::{control_id::ALLOWED_CONTROL_ROOT, CircuitImpl, CIRCUIT};
@@ -89,6 +89,7 @@ where
         &self,
         ctx: &VerifierContext,
     ) -> Result<(), VerificationError> {
+        ctx.limits.check_seal(size_of_val(self.seal.as_slice()))?;
         let params = ctx
             .succinct_verifier_parameters
             .as_ref()
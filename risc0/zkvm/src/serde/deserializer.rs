@@ -16,9 +16,13 @@ use alloc::{string::String, vec};
 
 use bytemuck::Pod;
 use risc0_zkvm_platform::WORD_SIZE;
-use serde::de::{DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
 
-use super::err::{Error, Result};
+use super::{
+    encoding::Lane,
+    err::{Error, Result},
+    Encoding,
+};
 use crate::align_up;
 
 /// A reader for reading streams with serialized word-based data
@@ -88,10 +92,63 @@ pub fn from_slice<T: DeserializeOwned, P: Pod>(slice: &[P]) -> Result<T> {
     }
 }
 
+/// Deserialize a slice into the specified type, borrowing from the slice where possible.
+///
+/// Unlike [from_slice], strings and byte buffers in `T` may borrow directly from `slice` (e.g.
+/// `&'de str` or a `&'de [u8]` deserialized with `deserialize_bytes`) instead of being copied
+/// into a fresh allocation. Returns an `Err` if deserialization isn't possible, such as if `slice`
+/// is not the serialized form of an object of type `T`.
+pub fn from_slice_borrowed<'de, T: Deserialize<'de>>(slice: &'de [u32]) -> Result<T> {
+    let mut deserializer = Deserializer {
+        borrow: borrow_padded_bytes,
+        ..Deserializer::new(slice)
+    };
+    T::deserialize(&mut deserializer)
+}
+
+/// Deserialize a slice that was serialized with the given [Encoding].
+///
+/// Like [from_slice], but for data produced by
+/// [to_vec_with_encoding][super::to_vec_with_encoding] or another [Serializer][super::Serializer]
+/// configured with `encoding`.
+pub fn from_slice_with_encoding<T: DeserializeOwned, P: Pod>(
+    slice: &[P],
+    encoding: Encoding,
+) -> Result<T> {
+    match bytemuck::try_cast_slice(slice) {
+        Ok(slice) => {
+            let mut deserializer = Deserializer::with_encoding(slice, encoding);
+            T::deserialize(&mut deserializer)
+        }
+        // P is u8 or another value without word-alignment. Data must be copied.
+        Err(bytemuck::PodCastError::TargetAlignmentGreaterAndInputNotAligned) => {
+            let vec = bytemuck::allocation::pod_collect_to_vec::<P, u32>(slice);
+            let mut deserializer = Deserializer::with_encoding(vec.as_slice(), encoding);
+            T::deserialize(&mut deserializer)
+        }
+        Err(ref e) => panic!("failed to cast or read slice as [u32]: {}", e),
+    }
+}
+
+/// Takes `len` bytes, and the padding up to the next word boundary, from the front of `slice`.
+fn borrow_padded_bytes<'de>(slice: &mut &'de [u32], len: usize) -> Result<Option<&'de [u8]>> {
+    let bytes: &'de [u8] = bytemuck::cast_slice(slice);
+    if len > bytes.len() {
+        return Err(Error::DeserializeUnexpectedEnd);
+    }
+    *slice = &slice[align_up(len, WORD_SIZE) / WORD_SIZE..];
+    Ok(Some(&bytes[..len]))
+}
+
+/// Returns bytes borrowed from the input of a [Deserializer], or `None` if its reader copies.
+type BorrowFn<'de, R> = fn(&mut R, usize) -> Result<Option<&'de [u8]>>;
+
 /// Enables deserializing from a WordRead
 pub struct Deserializer<'de, R: WordRead + 'de> {
     reader: R,
-    phantom: core::marker::PhantomData<&'de ()>,
+    borrow: BorrowFn<'de, R>,
+    encoding: Encoding,
+    lane: Lane,
 }
 
 struct SeqAccess<'a, 'de, R: WordRead + 'de> {
@@ -191,13 +248,56 @@ impl<'de, R: WordRead + 'de> Deserializer<'de, R> {
     ///
     /// Creates a deserializer for deserializing from the given WordRead
     pub fn new(reader: R) -> Self {
+        Self::with_encoding(reader, Encoding::default())
+    }
+
+    /// Construct a Deserializer for data serialized with the given [Encoding]
+    pub fn with_encoding(reader: R, encoding: Encoding) -> Self {
+        Self::resume(reader, encoding, Lane::default())
+    }
+
+    /// Construct a Deserializer that continues unpacking from `lane`, as
+    /// taken from a previous deserializer on the same stream by
+    /// [Deserializer::suspend].
+    pub(crate) fn resume(reader: R, encoding: Encoding, lane: Lane) -> Self {
         Deserializer {
             reader,
-            phantom: core::marker::PhantomData,
+            borrow: |_, _| Ok(None),
+            encoding,
+            lane,
         }
     }
 
+    /// Take the partially unpacked word, so that a later deserializer on the
+    /// same stream can continue unpacking from it.
+    pub(crate) fn suspend(&mut self) -> Lane {
+        core::mem::take(&mut self.lane)
+    }
+
+    /// Reads a value of `nbytes` bytes or fewer, zero-extended to a word.
+    ///
+    /// In [Encoding::V2], consecutive small values are unpacked from a shared word.
+    fn try_take_small(&mut self, nbytes: usize) -> Result<u32> {
+        if self.encoding == Encoding::V1 {
+            return self.try_take_word();
+        }
+        if self.lane.len < nbytes {
+            self.lane.word = self.try_take_word()?;
+            self.lane.len = WORD_SIZE;
+        }
+        let shift = 8 * (WORD_SIZE - self.lane.len);
+        let mask = u32::MAX >> (8 * (WORD_SIZE - nbytes));
+        self.lane.len -= nbytes;
+        Ok((self.lane.word >> shift) & mask)
+    }
+
+    fn try_take_padded_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
+        self.lane.len = 0;
+        self.reader.read_padded_bytes(bytes)
+    }
+
     fn try_take_word(&mut self) -> Result<u32> {
+        self.lane.len = 0;
         let mut val = 0u32;
         self.reader.read_words(core::slice::from_mut(&mut val))?;
         Ok(val)
@@ -228,7 +328,7 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
     where
         V: Visitor<'de>,
     {
-        let val = match self.try_take_word()? {
+        let val = match self.try_take_small(1)? {
             0 => false,
             1 => true,
             _ => return Err(Error::DeserializeBadBool),
@@ -240,14 +340,20 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
     where
         V: Visitor<'de>,
     {
-        visitor.visit_i32(self.try_take_word()? as i32)
+        match self.encoding {
+            Encoding::V1 => visitor.visit_i32(self.try_take_word()? as i32),
+            _ => visitor.visit_i8(self.try_take_small(1)? as u8 as i8),
+        }
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_i32(self.try_take_word()? as i32)
+        match self.encoding {
+            Encoding::V1 => visitor.visit_i32(self.try_take_word()? as i32),
+            _ => visitor.visit_i16(self.try_take_small(2)? as u16 as i16),
+        }
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value>
@@ -269,7 +375,7 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
         V: Visitor<'de>,
     {
         let mut bytes = [0u8; 16];
-        self.reader.read_padded_bytes(&mut bytes)?;
+        self.try_take_padded_bytes(&mut bytes)?;
         visitor.visit_i128(i128::from_le_bytes(bytes))
     }
 
@@ -277,14 +383,14 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
     where
         V: Visitor<'de>,
     {
-        visitor.visit_u32(self.try_take_word()?)
+        visitor.visit_u32(self.try_take_small(1)?)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_u32(self.try_take_word()?)
+        visitor.visit_u32(self.try_take_small(2)?)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value>
@@ -306,7 +412,7 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
         V: Visitor<'de>,
     {
         let mut bytes = [0u8; 16];
-        self.reader.read_padded_bytes(&mut bytes)?;
+        self.try_take_padded_bytes(&mut bytes)?;
         visitor.visit_u128(u128::from_le_bytes(bytes))
     }
 
@@ -337,11 +443,15 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
         V: Visitor<'de>,
     {
         let len_bytes = self.try_take_word()? as usize;
+        if let Some(bytes) = (self.borrow)(&mut self.reader, len_bytes)? {
+            let str = core::str::from_utf8(bytes).map_err(|_| Error::DeserializeBadChar)?;
+            return visitor.visit_borrowed_str(str);
+        }
         // TODO: Can we use MaybeUninit here instead of zeroing out?
         // The documentation for sys::io::Read implies that it's not
         // safe; is there another way to not do double writes here?
         let mut bytes = vec![0u8; len_bytes];
-        self.reader.read_padded_bytes(&mut bytes)?;
+        self.try_take_padded_bytes(&mut bytes)?;
         visitor.visit_string(String::from_utf8(bytes).map_err(|_| Error::DeserializeBadChar)?)
     }
 
@@ -357,11 +467,14 @@ impl<'de, 'a, R: WordRead + 'de> serde::Deserializer<'de> for &'a mut Deserializ
         V: Visitor<'de>,
     {
         let len_bytes = self.try_take_word()? as usize;
+        if let Some(bytes) = (self.borrow)(&mut self.reader, len_bytes)? {
+            return visitor.visit_borrowed_bytes(bytes);
+        }
         // TODO: Can we use MaybeUninit here instead of zeroing out?
         // The documentation for sys::io::Read implies that it's not
         // safe; is there another way to not do double writes here?
         let mut bytes = vec![0u8; len_bytes];
-        self.reader.read_padded_bytes(&mut bytes)?;
+        self.try_take_padded_bytes(&mut bytes)?;
         visitor.visit_byte_buf(bytes)
     }
 
@@ -588,4 +701,77 @@ mod tests {
         };
         assert_eq!(expected, from_slice(&words).unwrap());
     }
+
+    #[test]
+    fn test_str_borrowed() {
+        use serde::Deserialize;
+
+        #[derive(Deserialize, PartialEq, Debug)]
+        struct Test<'a> {
+            first: &'a str,
+            second: &'a str,
+            third: String,
+        }
+
+        let words = [1, 0x00000061, 3, 0x00636261, 2, 0x00006564];
+        let expected = Test {
+            first: "a",
+            second: "abc",
+            third: "de".into(),
+        };
+        let actual: Test = from_slice_borrowed(&words).unwrap();
+        assert_eq!(expected, actual);
+
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+        assert_eq!(actual.second.as_ptr(), bytes[12..].as_ptr());
+    }
+
+    #[test]
+    fn test_bytes_borrowed() {
+        use core::fmt;
+
+        use serde::{de, Deserialize, Deserializer};
+
+        #[derive(PartialEq, Debug)]
+        struct Bytes<'a>(&'a [u8]);
+
+        impl<'de> Deserialize<'de> for Bytes<'de> {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<Self, D::Error> {
+                struct BytesVisitor;
+
+                impl<'de> de::Visitor<'de> for BytesVisitor {
+                    type Value = Bytes<'de>;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("borrowed bytes")
+                    }
+
+                    fn visit_borrowed_bytes<E: de::Error>(
+                        self,
+                        v: &'de [u8],
+                    ) -> core::result::Result<Self::Value, E> {
+                        Ok(Bytes(v))
+                    }
+                }
+
+                deserializer.deserialize_bytes(BytesVisitor)
+            }
+        }
+
+        let a = [1u8, 2, 3, 4, 5];
+        let mut encoded: Vec<u32> = Vec::new();
+        serde::Serializer::serialize_bytes(&mut crate::serde::Serializer::new(&mut encoded), &a)
+            .unwrap();
+        encoded.push(7);
+        let decoded: (Bytes, u32) = from_slice_borrowed(&encoded).unwrap();
+        assert_eq!(decoded, (Bytes(&a), 7));
+
+        // A length longer than the remaining input is an error.
+        assert_eq!(
+            from_slice_borrowed::<Bytes>(&[8, 0x04030201]),
+            Err(Error::DeserializeUnexpectedEnd)
+        );
+    }
 }
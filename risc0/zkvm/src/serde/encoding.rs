@@ -0,0 +1,48 @@
+// Copyright 2024 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Versions of the word-oriented encoding used by [Serializer][super::Serializer] and
+/// [Deserializer][super::Deserializer].
+///
+/// Data must be deserialized with the same encoding it was serialized with. The encoding is not
+/// recorded in the serialized data, so the host and guest must agree on it ahead of time. This
+/// includes the journal, which `Journal::decode` always decodes with [Encoding::V1].
+///
+/// Both encodings are stable under concatenation: serializing values one at a time to the same
+/// stream, e.g. with repeated calls to `env::commit`, produces the same words as serializing them
+/// as a single tuple.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// Every value occupies at least one word. A `u8`, `u16` or `bool` is widened to a full
+    /// word, so a `Vec<u8>` takes four times its length unless it is serialized with
+    /// `serde_bytes`. This is the default.
+    #[default]
+    V1,
+
+    /// Like [Encoding::V1], except that consecutive `u8`, `i8`, `u16`, `i16` and `bool` values
+    /// are packed into shared words. This packs a `Vec<u8>` four bytes to a word.
+    V2,
+}
+
+/// A word of packed small values in [Encoding::V2], and the number of its bytes in use when
+/// writing, or not yet read when reading.
+///
+/// This is carried from one [Serializer][super::Serializer] or [Deserializer][super::Deserializer]
+/// to the next on the same stream, which keeps the stream stable under concatenation.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Lane {
+    pub(crate) word: u32,
+    pub(crate) len: usize,
+}
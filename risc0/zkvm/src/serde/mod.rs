@@ -34,23 +34,54 @@
 //! [`env::commit`], so this crate rarely needs to be directly used in the
 //! guest.
 //!
+//! By default, every `u8`, `u16` and `bool` occupies a full word, so a
+//! `Vec<u8>` is four times larger than its contents. [Encoding::V2] packs
+//! these values densely. It is opt-in, and both sides must agree on it:
+//! ```rust
+//! use risc0_zkvm::serde::{from_slice_with_encoding, to_vec_with_encoding, Encoding};
+//! let input: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+//! let encoded = to_vec_with_encoding(&input, Encoding::V2).unwrap();
+//! assert_eq!(encoded.len(), 3);
+//! let output: Vec<u8> = from_slice_with_encoding(&encoded, Encoding::V2).unwrap();
+//! assert_eq!(input, output);
+//! ```
+//!
 //! [`env`]: ../guest/env/index.html
 //! [`env::commit`]: ../guest/env/fn.commit.html
 //! [`env::read`]: ../guest/env/fn.read.html
 
 mod deserializer;
+mod encoding;
 mod err;
 mod serializer;
 
-pub use deserializer::{from_slice, Deserializer, WordRead};
+pub use deserializer::{
+    from_slice, from_slice_borrowed, from_slice_with_encoding, Deserializer, WordRead,
+};
+pub use encoding::Encoding;
+pub(crate) use encoding::Lane;
 pub use err::{Error, Result};
-pub use serializer::{to_vec, to_vec_with_capacity, Serializer, WordWrite};
+pub use serializer::{to_vec, to_vec_with_capacity, to_vec_with_encoding, Serializer, WordWrite};
 
 #[cfg(test)]
 mod tests {
     use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 
-    use crate::serde::{from_slice, to_vec};
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    use crate::serde::{
+        from_slice, from_slice_with_encoding, to_vec, to_vec_with_encoding, Encoding,
+    };
+
+    fn round_trip<T>(input: &T, encoding: Encoding) -> usize
+    where
+        T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug,
+    {
+        let data = to_vec_with_encoding(input, encoding).unwrap();
+        let output: T = from_slice_with_encoding(data.as_slice(), encoding).unwrap();
+        assert_eq!(input, &output);
+        data.len()
+    }
 
     #[test]
     fn test_vec_round_trip() {
@@ -76,4 +107,112 @@ mod tests {
         let output: (u32, u64) = from_slice(data.as_slice()).unwrap();
         assert_eq!(input, output);
     }
+
+    #[test]
+    fn test_v1_matches_default() {
+        let input: (Vec<u8>, bool, i16, String) = (vec![1, 2, 3], true, -2, "abc".into());
+        assert_eq!(
+            to_vec(&input).unwrap(),
+            to_vec_with_encoding(&input, Encoding::V1).unwrap()
+        );
+        round_trip(&input, Encoding::V1);
+    }
+
+    #[test]
+    fn test_v2_bytes_round_trip() {
+        for len in 0..9 {
+            let input: Vec<u8> = (0..len).map(|i| 0xf0 | i).collect();
+            let words = round_trip(&input, Encoding::V2);
+            // One word for the length, then four bytes per word.
+            assert_eq!(words, 1 + (len as usize).div_ceil(4));
+            assert_eq!(round_trip(&input, Encoding::V1), 1 + len as usize);
+        }
+    }
+
+    #[test]
+    fn test_v2_small_ints_round_trip() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Test {
+            bool: bool,
+            i8: i8,
+            u16: u16,
+            i16: i16,
+            u8: u8,
+            u32: u32,
+            bytes: Vec<u8>,
+            i8s: [i8; 3],
+            string: String,
+            u16s: Vec<u16>,
+            opt: Option<u8>,
+            u64: u64,
+        }
+
+        let input = Test {
+            bool: true,
+            i8: -4,
+            u16: 0xfffe,
+            i16: -5,
+            u8: 0xff,
+            u32: 6,
+            bytes: vec![1, 2, 3, 4, 5],
+            i8s: [-1, 0, i8::MIN],
+            string: "abcde".into(),
+            u16s: vec![1, u16::MAX, 3],
+            opt: Some(7),
+            u64: u64::MAX,
+        };
+        let v1 = round_trip(&input, Encoding::V1);
+        let v2 = round_trip(&input, Encoding::V2);
+        assert!(v2 < v1, "{v2} >= {v1}");
+    }
+
+    #[test]
+    fn test_v2_concatenation() {
+        use crate::serde::{Deserializer, Lane, Serializer};
+
+        let input: (u8, Vec<u8>, i16, bool, u8) = (1, vec![2, 3, 4], -5, true, 6);
+        let tuple = to_vec_with_encoding(&input, Encoding::V2).unwrap();
+
+        // Serialize the fields one at a time, carrying packed values over.
+        fn write<T: Serialize>(words: &mut Vec<u32>, lane: Lane, value: &T) -> Lane {
+            let mut serializer = Serializer::resume(words, Encoding::V2, lane);
+            value.serialize(&mut serializer).unwrap();
+            serializer.suspend()
+        }
+        let mut words: Vec<u32> = Vec::new();
+        let mut lane = write(&mut words, Lane::default(), &input.0);
+        lane = write(&mut words, lane, &input.1);
+        lane = write(&mut words, lane, &input.2);
+        lane = write(&mut words, lane, &input.3);
+        lane = write(&mut words, lane, &input.4);
+        Serializer::resume(&mut words, Encoding::V2, lane)
+            .flush()
+            .unwrap();
+        assert_eq!(words, tuple);
+
+        // Deserialize the fields one at a time from the tuple.
+        let mut reader = tuple.as_slice();
+        let mut deserializer = Deserializer::with_encoding(&mut reader, Encoding::V2);
+        let first = u8::deserialize(&mut deserializer).unwrap();
+        let lane = deserializer.suspend();
+        let mut deserializer = Deserializer::resume(&mut reader, Encoding::V2, lane);
+        let rest = <(Vec<u8>, i16, bool, u8)>::deserialize(&mut deserializer).unwrap();
+        assert_eq!((first, rest.0, rest.1, rest.2, rest.3), input);
+    }
+
+    #[test]
+    fn test_serializer_flushes_on_drop() {
+        let mut words: Vec<u32> = Vec::new();
+        let mut serializer = crate::serde::Serializer::with_encoding(&mut words, Encoding::V2);
+        (1u8, 2u8).serialize(&mut serializer).unwrap();
+        drop(serializer);
+        assert_eq!(words, [0x0201]);
+    }
+
+    #[test]
+    fn test_v2_is_not_v1() {
+        let input: Vec<u8> = vec![1, 2, 3, 4];
+        let data = to_vec_with_encoding(&input, Encoding::V2).unwrap();
+        assert!(from_slice::<Vec<u8>, _>(&data).is_err());
+    }
 }
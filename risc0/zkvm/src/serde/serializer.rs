@@ -16,7 +16,11 @@ use alloc::vec::Vec;
 
 use risc0_zkvm_platform::WORD_SIZE;
 
-use super::err::{Error, Result};
+use super::{
+    encoding::Lane,
+    err::{Error, Result},
+    Encoding,
+};
 
 /// A writer for writing streams preferring word-based data.
 pub trait WordWrite {
@@ -72,8 +76,7 @@ where
     // Use the in-memory size of the value as a guess for the length
     // of the serialized value.
     let mut vec: Vec<u32> = Vec::with_capacity(core::mem::size_of_val(value));
-    let mut serializer = Serializer::new(&mut vec);
-    value.serialize(&mut serializer)?;
+    value.serialize(&mut Serializer::new(&mut vec))?;
     Ok(vec)
 }
 
@@ -86,14 +89,31 @@ where
     T: serde::Serialize + ?Sized,
 {
     let mut vec: Vec<u32> = Vec::with_capacity(cap);
-    let mut serializer = Serializer::new(&mut vec);
+    value.serialize(&mut Serializer::new(&mut vec))?;
+    Ok(vec)
+}
+
+/// Serialize to a vector of u32 words using the given [Encoding]
+///
+/// The result must be deserialized with the same encoding, e.g. using
+/// [from_slice_with_encoding][super::from_slice_with_encoding].
+pub fn to_vec_with_encoding<T>(value: &T, encoding: Encoding) -> Result<Vec<u32>>
+where
+    T: serde::Serialize + ?Sized,
+{
+    let mut vec: Vec<u32> = Vec::with_capacity(core::mem::size_of_val(value));
+    let mut serializer = Serializer::with_encoding(&mut vec, encoding);
     value.serialize(&mut serializer)?;
+    serializer.flush()?;
+    drop(serializer);
     Ok(vec)
 }
 
 /// Enables serializing to a stream
 pub struct Serializer<W: WordWrite> {
     stream: W,
+    encoding: Encoding,
+    lane: Lane,
 }
 
 impl<W: WordWrite> Serializer<W> {
@@ -101,7 +121,73 @@ impl<W: WordWrite> Serializer<W> {
     ///
     /// Creates a serializer that writes to `stream`.
     pub fn new(stream: W) -> Self {
-        Serializer { stream }
+        Self::with_encoding(stream, Encoding::default())
+    }
+
+    /// Construct a Serializer using the given [Encoding]
+    ///
+    /// With [Encoding::V2], small values may be held back to be packed with
+    /// the next ones. They are written when the serializer is dropped, or by
+    /// [Serializer::flush], which also reports any error from the stream.
+    pub fn with_encoding(stream: W, encoding: Encoding) -> Self {
+        Self::resume(stream, encoding, Lane::default())
+    }
+
+    /// Construct a Serializer that continues packing into `lane`, as taken
+    /// from a previous serializer on the same stream by [Serializer::suspend].
+    pub(crate) fn resume(stream: W, encoding: Encoding, lane: Lane) -> Self {
+        Serializer {
+            stream,
+            encoding,
+            lane,
+        }
+    }
+
+    /// Take the partially packed word, without writing it, so that a later
+    /// serializer on the same stream can continue packing into it.
+    pub(crate) fn suspend(&mut self) -> Lane {
+        core::mem::take(&mut self.lane)
+    }
+
+    /// Write any partially packed word to the stream.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.lane.len == 0 {
+            return Ok(());
+        }
+        let lane = self.suspend();
+        self.stream.write_words(&[lane.word])
+    }
+
+    /// Writes a value of `nbytes` bytes or fewer.
+    ///
+    /// In [Encoding::V2], consecutive small values are packed into a shared word.
+    fn write_small(&mut self, v: u32, nbytes: usize) -> Result<()> {
+        if self.encoding == Encoding::V1 {
+            return self.write_word(v);
+        }
+        if self.lane.len + nbytes > WORD_SIZE {
+            self.flush()?;
+        }
+        self.lane.word |= v << (8 * self.lane.len);
+        self.lane.len += nbytes;
+        Ok(())
+    }
+
+    fn write_word(&mut self, v: u32) -> Result<()> {
+        self.flush()?;
+        self.stream.write_words(&[v])
+    }
+
+    fn write_padded_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.flush()?;
+        self.stream.write_padded_bytes(bytes)
+    }
+}
+
+impl<W: WordWrite> Drop for Serializer<W> {
+    fn drop(&mut self) {
+        // Errors cannot be returned from here; call flush to handle them.
+        let _ = self.flush();
     }
 }
 
@@ -132,11 +218,17 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_i8(self, v: i8) -> Result<()> {
-        self.serialize_i32(v as i32)
+        match self.encoding {
+            Encoding::V1 => self.serialize_i32(v as i32),
+            _ => self.write_small(v as u8 as u32, 1),
+        }
     }
 
     fn serialize_i16(self, v: i16) -> Result<()> {
-        self.serialize_i32(v as i32)
+        match self.encoding {
+            Encoding::V1 => self.serialize_i32(v as i32),
+            _ => self.write_small(v as u16 as u32, 2),
+        }
     }
 
     fn serialize_i32(self, v: i32) -> Result<()> {
@@ -152,15 +244,15 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_u8(self, v: u8) -> Result<()> {
-        self.serialize_u32(v as u32)
+        self.write_small(v as u32, 1)
     }
 
     fn serialize_u16(self, v: u16) -> Result<()> {
-        self.serialize_u32(v as u32)
+        self.write_small(v as u32, 2)
     }
 
     fn serialize_u32(self, v: u32) -> Result<()> {
-        self.stream.write_words(&[v])
+        self.write_word(v)
     }
 
     fn serialize_u64(self, v: u64) -> Result<()> {
@@ -169,7 +261,7 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     }
 
     fn serialize_u128(self, v: u128) -> Result<()> {
-        self.stream.write_padded_bytes(&v.to_le_bytes())
+        self.write_padded_bytes(&v.to_le_bytes())
     }
 
     fn serialize_f32(self, v: f32) -> Result<()> {
@@ -187,7 +279,7 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     fn serialize_str(self, v: &str) -> Result<()> {
         let bytes = v.as_bytes();
         self.serialize_u32(bytes.len() as u32)?;
-        self.stream.write_padded_bytes(bytes)
+        self.write_padded_bytes(bytes)
     }
 
     // NOTE: Serializing byte slices _does not_ currently call serialize_bytes. This
@@ -201,7 +293,7 @@ impl<'a, W: WordWrite> serde::ser::Serializer for &'a mut Serializer<W> {
     //    features.
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
         self.serialize_u32(v.len() as u32)?;
-        self.stream.write_padded_bytes(v)
+        self.write_padded_bytes(v)
     }
 
     fn serialize_none(self) -> Result<()> {